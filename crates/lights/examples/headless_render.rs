@@ -0,0 +1,44 @@
+//! Headless rendering example.
+//!
+//! Renders a small world with the ReferenceRenderer and exports the frame
+//! as PNG and OpenEXR. No display or GPU is required, which makes this the
+//! pattern to follow for visual-regression tests and thumbnail generation.
+
+use lights::renderer::prelude::*;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let output_directory = std::env::args()
+        .nth(1)
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+
+    let mut world = World::new();
+    world.set_camera(Camera::new().with_aspect_ratio(1.0));
+    world.set_background_color(0.05, 0.05, 0.08, 1.0);
+
+    let colors = [[1.0, 0.2, 0.2], [0.2, 1.0, 0.2], [0.2, 0.2, 1.0]];
+    for (index, color) in colors.iter().enumerate() {
+        let offset = index as f64 - 1.0;
+        world.add_splat(
+            GaussianSplat::new(0, Point3D::new(offset, 0.0, offset * 0.5))
+                .with_color(color[0], color[1], color[2], 1.0)
+                .with_covariance([0.15, 0.0, 0.0, 0.15, 0.0, 0.15]),
+        );
+    }
+
+    let mut renderer = ReferenceRenderer::new();
+    renderer.set_world(world)?;
+    renderer.set_viewport_size(256, 256);
+    renderer.start()?;
+    renderer.render_frame()?;
+
+    let framebuffer = renderer.read_framebuffer()?;
+    let png_path = output_directory.join("headless_render.png");
+    let exr_path = output_directory.join("headless_render.exr");
+    framebuffer.save_png(&png_path)?;
+    framebuffer.save_exr(&exr_path)?;
+
+    println!("Wrote {}", png_path.display());
+    println!("Wrote {}", exr_path.display());
+    Ok(())
+}
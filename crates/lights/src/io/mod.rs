@@ -0,0 +1,65 @@
+//! Import and export of splat scenes.
+//!
+//! Public submodules:
+//! - native (versioned bincode scene format for `World` and `SceneGraph`)
+//! - ply (standard 3D Gaussian Splatting `.ply` files)
+//! - splat (compact 32-byte-per-splat `.splat` files used by web viewers)
+//! - spz (quantized, gzip-compressed container)
+//! - report (size and quality reports for the lossy formats)
+
+pub mod native;
+pub mod ply;
+pub mod report;
+pub mod splat;
+pub mod spz;
+
+pub use native::{load_scene, load_world, read_scene, save_scene, save_world, write_scene, SceneDocument};
+pub use ply::{load_ply, read_ply, save_ply, write_ply, PlyFormat};
+pub use report::CompressionReport;
+pub use splat::{load_splat, read_splat, save_splat, splat_report, write_splat};
+pub use spz::{load_spz, read_spz, save_spz, spz_report, write_spz, SpzOptions};
+
+use std::fmt;
+
+/// Errors that can occur while reading or writing scene files.
+#[derive(Debug)]
+pub enum IoError {
+    /// The underlying reader or writer failed.
+    Io(std::io::Error),
+
+    /// The input is malformed.
+    /// Contains a descriptive message about what was wrong.
+    InvalidFormat(String),
+
+    /// The input is well-formed but uses a feature we do not support.
+    Unsupported(String),
+
+    /// The scene contains data that cannot be encoded in the target format.
+    InvalidData(String),
+}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IoError::Io(error) => write!(f, "I/O error: {}", error),
+            IoError::InvalidFormat(msg) => write!(f, "Invalid format: {}", msg),
+            IoError::Unsupported(msg) => write!(f, "Unsupported: {}", msg),
+            IoError::InvalidData(msg) => write!(f, "Invalid data: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for IoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IoError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for IoError {
+    fn from(error: std::io::Error) -> Self {
+        IoError::Io(error)
+    }
+}
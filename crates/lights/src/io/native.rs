@@ -0,0 +1,457 @@
+//! Native versioned scene format.
+//!
+//! A native scene file caches a `World` and/or a `SceneGraph` so tools can
+//! reload scenes without re-parsing interchange formats. The layout is:
+//!
+//! - magic: the 8 bytes `LIGHTSSC`
+//! - version: major `u16` LE, minor `u16` LE
+//! - chunks: 4-byte tag, payload length `u64` LE, bincode payload
+//! - an `END ` chunk with an empty payload
+//!
+//! Chunks carry one section each: `CAMR` (camera), `META` (background color,
+//! metadata and ID allocation), `SPLT` (splats) and `GRPH` (scene graph,
+//! prefixed with the size of its float type in bytes).
+//!
+//! Forward compatibility follows PNG: readers skip unknown chunks whose tag
+//! starts with a lowercase letter, and reject unknown chunks whose tag starts
+//! with an uppercase letter because they cannot be ignored safely. Readers
+//! also ignore trailing bytes inside a known chunk, so later minor versions
+//! may append fields. Files with a different major version are rejected;
+//! version 2.0 changed the splat and scene graph encodings of 1.0.
+
+use super::IoError;
+use crate::numerics::types::traits::FloatingPoint;
+use crate::renderer::world::{Camera, GaussianSplat, World};
+use crate::scene::graph::SceneGraph;
+use bincode::{Decode, Encode};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of every native scene file.
+pub const SCENE_MAGIC: [u8; 8] = *b"LIGHTSSC";
+
+/// Major format version. Readers reject files with any other major version.
+pub const SCENE_VERSION_MAJOR: u16 = 2;
+
+/// Minor format version. Newer minor versions only add skippable data.
+pub const SCENE_VERSION_MINOR: u16 = 0;
+
+const CHUNK_CAMERA: [u8; 4] = *b"CAMR";
+const CHUNK_METADATA: [u8; 4] = *b"META";
+const CHUNK_SPLATS: [u8; 4] = *b"SPLT";
+const CHUNK_SCENE_GRAPH: [u8; 4] = *b"GRPH";
+const CHUNK_END: [u8; 4] = *b"END ";
+
+/// World-level settings stored in the `META` chunk.
+#[derive(Debug, Clone, Encode, Decode)]
+struct WorldMetadata {
+    background_color: [f64; 4],
+    metadata: HashMap<String, String>,
+    next_id: u64,
+}
+
+/// The contents of a native scene file.
+#[derive(Debug, Clone)]
+pub struct SceneDocument<T: FloatingPoint = f32> {
+    /// The world, if the file contains one
+    pub world: Option<World>,
+
+    /// The scene graph, if the file contains one
+    pub scene_graph: Option<SceneGraph<T>>,
+
+    /// Version the file was written with (major, minor)
+    pub version: (u16, u16),
+}
+
+impl<T: FloatingPoint> SceneDocument<T> {
+    /// Create an empty document at the current format version.
+    pub fn new() -> Self {
+        Self {
+            world: None,
+            scene_graph: None,
+            version: (SCENE_VERSION_MAJOR, SCENE_VERSION_MINOR),
+        }
+    }
+
+    /// Set the world stored in this document.
+    pub fn with_world(mut self, world: World) -> Self {
+        self.world = Some(world);
+        self
+    }
+
+    /// Set the scene graph stored in this document.
+    pub fn with_scene_graph(mut self, scene_graph: SceneGraph<T>) -> Self {
+        self.scene_graph = Some(scene_graph);
+        self
+    }
+}
+
+impl<T: FloatingPoint> Default for SceneDocument<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Write a document in the native scene format.
+///
+/// The document's `version` field is ignored; files are always written at
+/// the current version.
+pub fn write_scene<T, W>(document: &SceneDocument<T>, writer: W) -> Result<(), IoError>
+where
+    T: FloatingPoint + Encode,
+    W: Write,
+{
+    write_sections(document.world.as_ref(), document.scene_graph.as_ref(), writer)
+}
+
+fn write_sections<T, W>(world: Option<&World>, scene_graph: Option<&SceneGraph<T>>, writer: W) -> Result<(), IoError>
+where
+    T: FloatingPoint + Encode,
+    W: Write,
+{
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(&SCENE_MAGIC)?;
+    writer.write_all(&SCENE_VERSION_MAJOR.to_le_bytes())?;
+    writer.write_all(&SCENE_VERSION_MINOR.to_le_bytes())?;
+
+    if let Some(world) = world {
+        write_chunk(&mut writer, CHUNK_CAMERA, &world.camera)?;
+        let metadata = WorldMetadata {
+            background_color: world.background_color,
+            metadata: world.metadata.clone(),
+            next_id: world.next_id,
+        };
+        write_chunk(&mut writer, CHUNK_METADATA, &metadata)?;
+        write_chunk(&mut writer, CHUNK_SPLATS, &world.splats())?;
+    }
+    if let Some(scene_graph) = scene_graph {
+        write_chunk(&mut writer, CHUNK_SCENE_GRAPH, &(std::mem::size_of::<T>() as u8, scene_graph))?;
+    }
+    write_chunk(&mut writer, CHUNK_END, &())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read a document in the native scene format.
+pub fn read_scene<T, R>(reader: R) -> Result<SceneDocument<T>, IoError>
+where
+    T: FloatingPoint + Decode<()>,
+    R: Read,
+{
+    let mut reader = BufReader::new(reader);
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if magic != SCENE_MAGIC {
+        return Err(IoError::InvalidFormat("Not a native scene file".to_string()));
+    }
+    let mut version = [0u8; 4];
+    reader.read_exact(&mut version)?;
+    let major = u16::from_le_bytes([version[0], version[1]]);
+    let minor = u16::from_le_bytes([version[2], version[3]]);
+    if major != SCENE_VERSION_MAJOR {
+        return Err(IoError::Unsupported(format!(
+            "Scene format version {}.{} (supported is {}.x)",
+            major, minor, SCENE_VERSION_MAJOR
+        )));
+    }
+
+    let mut document = SceneDocument::new();
+    document.version = (major, minor);
+    let mut camera: Option<Camera> = None;
+    let mut metadata: Option<WorldMetadata> = None;
+    let mut splats: Option<Vec<GaussianSplat>> = None;
+
+    loop {
+        let mut tag = [0u8; 4];
+        reader.read_exact(&mut tag)?;
+        let mut length = [0u8; 8];
+        reader.read_exact(&mut length)?;
+        let length = u64::from_le_bytes(length);
+
+        if tag == CHUNK_END {
+            break;
+        }
+        if ![CHUNK_CAMERA, CHUNK_METADATA, CHUNK_SPLATS, CHUNK_SCENE_GRAPH].contains(&tag) {
+            if tag[0].is_ascii_lowercase() {
+                std::io::copy(&mut (&mut reader).take(length), &mut std::io::sink())?;
+                continue;
+            }
+            return Err(IoError::Unsupported(format!(
+                "Required chunk '{}'",
+                String::from_utf8_lossy(&tag)
+            )));
+        }
+
+        let mut payload = Vec::new();
+        (&mut reader).take(length).read_to_end(&mut payload)?;
+        if payload.len() as u64 != length {
+            return Err(IoError::InvalidFormat("Truncated chunk".to_string()));
+        }
+        match tag {
+            CHUNK_CAMERA => camera = Some(decode_payload(&payload)?),
+            CHUNK_METADATA => metadata = Some(decode_payload(&payload)?),
+            CHUNK_SPLATS => splats = Some(decode_payload(&payload)?),
+            _ => {
+                let size: u8 = decode_payload(&payload)?;
+                if size as usize != std::mem::size_of::<T>() {
+                    return Err(IoError::InvalidData(format!(
+                        "Scene graph stores {}-byte floats, expected {}",
+                        size,
+                        std::mem::size_of::<T>()
+                    )));
+                }
+                let (_, scene_graph): (u8, SceneGraph<T>) = decode_payload(&payload)?;
+                document.scene_graph = Some(scene_graph);
+            }
+        }
+    }
+
+    if camera.is_some() || metadata.is_some() || splats.is_some() {
+        let mut world = World::new();
+        if let Some(camera) = camera {
+            world.camera = camera;
+        }
+        if let Some(metadata) = metadata {
+            world.background_color = metadata.background_color;
+            world.metadata = metadata.metadata;
+            world.next_id = metadata.next_id;
+        }
+        world.replace_splats(splats.unwrap_or_default());
+        document.world = Some(world);
+    }
+    Ok(document)
+}
+
+/// Save a document to a native scene file on disk.
+pub fn save_scene<T>(document: &SceneDocument<T>, path: impl AsRef<Path>) -> Result<(), IoError>
+where
+    T: FloatingPoint + Encode,
+{
+    write_scene(document, File::create(path)?)
+}
+
+/// Load a document from a native scene file on disk.
+pub fn load_scene<T>(path: impl AsRef<Path>) -> Result<SceneDocument<T>, IoError>
+where
+    T: FloatingPoint + Decode<()>,
+{
+    read_scene(File::open(path)?)
+}
+
+/// Save a world on its own to a native scene file on disk.
+pub fn save_world(world: &World, path: impl AsRef<Path>) -> Result<(), IoError> {
+    write_sections::<f32, _>(Some(world), None, File::create(path)?)
+}
+
+/// Load the world from a native scene file on disk.
+pub fn load_world(path: impl AsRef<Path>) -> Result<World, IoError> {
+    load_scene::<f32>(path)?
+        .world
+        .ok_or_else(|| IoError::InvalidData("Scene file contains no world".to_string()))
+}
+
+fn write_chunk<W: Write, V: Encode>(writer: &mut W, tag: [u8; 4], value: &V) -> Result<(), IoError> {
+    let payload = bincode::encode_to_vec(value, bincode::config::standard())
+        .map_err(|e| IoError::InvalidData(e.to_string()))?;
+    writer.write_all(&tag)?;
+    writer.write_all(&(payload.len() as u64).to_le_bytes())?;
+    writer.write_all(&payload)?;
+    Ok(())
+}
+
+/// Bytes a decoded value may claim per payload byte. Containers claim their
+/// in-memory size, which exceeds the compact encoding of their items.
+const DECODE_EXPANSION: usize = 64;
+
+/// `1 << shift`, saturating at `usize::MAX` on narrow targets.
+const fn decode_tier(shift: u32) -> usize {
+    if shift >= usize::BITS {
+        usize::MAX
+    } else {
+        1 << shift
+    }
+}
+
+fn decode_payload<V: Decode<()>>(payload: &[u8]) -> Result<V, IoError> {
+    // Length prefixes are untrusted, so limit what decoding may allocate to a
+    // multiple of the payload size. bincode limits are const generics, hence
+    // the tiers.
+    match payload.len().saturating_mul(DECODE_EXPANSION) {
+        budget if budget <= decode_tier(16) => decode_limited::<V, { decode_tier(16) }>(payload),
+        budget if budget <= decode_tier(20) => decode_limited::<V, { decode_tier(20) }>(payload),
+        budget if budget <= decode_tier(24) => decode_limited::<V, { decode_tier(24) }>(payload),
+        budget if budget <= decode_tier(28) => decode_limited::<V, { decode_tier(28) }>(payload),
+        budget if budget <= decode_tier(32) => decode_limited::<V, { decode_tier(32) }>(payload),
+        budget if budget <= decode_tier(36) => decode_limited::<V, { decode_tier(36) }>(payload),
+        budget if budget <= decode_tier(40) => decode_limited::<V, { decode_tier(40) }>(payload),
+        _ => decode_limited::<V, { usize::MAX }>(payload),
+    }
+}
+
+fn decode_limited<V: Decode<()>, const LIMIT: usize>(payload: &[u8]) -> Result<V, IoError> {
+    // Trailing bytes are allowed so later minor versions can append fields
+    bincode::decode_from_slice(payload, bincode::config::standard().with_limit::<LIMIT>())
+        .map(|(value, _)| value)
+        .map_err(|e| IoError::InvalidFormat(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numerics::types::quaternion::Quaternion;
+    use crate::renderer::world::Point3D;
+    use crate::scene::graph::Node;
+    use crate::scene::primitive::Splat;
+    use crate::scene::spherical_harmonics::SphericalHarmonics;
+    use crate::scene::transform::Transform;
+
+    fn test_world() -> World {
+        let mut world = World::new();
+        world.set_camera(Camera::new().with_position(Point3D::new(1.0, 2.0, 3.0)).with_fov(50.0));
+        world.set_background_color(0.1, 0.2, 0.3, 1.0);
+        world.set_metadata("source".to_string(), "capture.ply".to_string());
+        world.add_splat(
+            GaussianSplat::new(0, Point3D::new(0.5, 0.25, -1.0))
+                .with_rotation_scale(Quaternion::new(0.9, 0.1, 0.2, 0.3), [0.3, 0.2, 0.1])
+                .with_spherical_harmonics(SphericalHarmonics::from_color([0.2, 0.4, 0.6], 2).unwrap())
+                .with_opacity(0.8)
+                .with_metadata("label".to_string(), "chair".to_string()),
+        );
+        world.add_splat_at(Point3D::new(-1.0, 0.0, 0.0));
+        world
+    }
+
+    fn test_graph() -> SceneGraph<f64> {
+        let mut graph = SceneGraph::new("scene");
+        let mut child = Node::new("child").with_transform(
+            Transform::from_translation([1.0, 2.0, 3.0]).with_rotation(Quaternion::new(0.5, 0.5, 0.5, 0.5)),
+        );
+        child.attach_splat(
+            Splat::new([1.0, 2.0, 3.0], 0.5, [1.0, 0.0, 0.0, 1.0])
+                .with_spherical_harmonics(SphericalHarmonics::from_color([0.1, 0.2, 0.3], 1).unwrap()),
+        );
+        child.add_child(Node::new("grandchild"));
+        graph.add_node_to_root(child);
+        graph
+    }
+
+    fn encode(document: &SceneDocument<f64>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_scene(document, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_world_and_graph_roundtrip() {
+        let world = test_world();
+        let bytes = encode(&SceneDocument::new().with_world(world.clone()).with_scene_graph(test_graph()));
+        assert_eq!(&bytes[..8], b"LIGHTSSC");
+
+        let document: SceneDocument<f64> = read_scene(bytes.as_slice()).unwrap();
+        assert_eq!(document.version, (SCENE_VERSION_MAJOR, SCENE_VERSION_MINOR));
+
+        let loaded = document.world.unwrap();
+        assert_eq!(loaded.splat_count(), 2);
+        assert_eq!(loaded.camera.position, world.camera.position);
+        assert_eq!(loaded.camera.fov, 50.0);
+        assert_eq!(loaded.background_color, world.background_color);
+        assert_eq!(loaded.get_metadata("source"), Some(&"capture.ply".to_string()));
+        for (a, b) in world.splats().iter().zip(loaded.splats().iter()) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.position, b.position);
+            assert_eq!(a.covariance, b.covariance);
+            assert_eq!(a.color, b.color);
+            assert_eq!(a.opacity, b.opacity);
+            assert_eq!(a.spherical_harmonics, b.spherical_harmonics);
+            assert_eq!(a.metadata, b.metadata);
+        }
+
+        // ID allocation continues where the saved world left off
+        let mut loaded = loaded;
+        let new_id = loaded.add_splat_at(Point3D::origin());
+        assert!(world.splats().iter().all(|splat| splat.id != new_id));
+
+        let graph = document.scene_graph.unwrap();
+        assert_eq!(graph.name, "scene");
+        let child = graph.find_node_by_name("child").unwrap();
+        assert_eq!(child.splats[0], test_graph().root.children[0].splats[0]);
+        assert_eq!(child.transform, test_graph().root.children[0].transform);
+        assert!(graph.find_node_by_name("grandchild").is_some());
+    }
+
+    #[test]
+    fn test_optional_sections() {
+        let bytes = encode(&SceneDocument::new().with_scene_graph(test_graph()));
+        let document: SceneDocument<f64> = read_scene(bytes.as_slice()).unwrap();
+        assert!(document.world.is_none());
+        assert!(document.scene_graph.is_some());
+
+        // The graph precision must match
+        assert!(matches!(read_scene::<f32, _>(bytes.as_slice()), Err(IoError::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_forward_compatibility() {
+        let bytes = encode(&SceneDocument::new().with_world(test_world()));
+        let end = bytes.len() - 12;
+
+        // An unknown optional chunk from a newer minor version is skipped
+        let mut newer = bytes[..end].to_vec();
+        newer[10] = 7;
+        newer.extend_from_slice(b"thmb");
+        newer.extend_from_slice(&3u64.to_le_bytes());
+        newer.extend_from_slice(&[1, 2, 3]);
+        newer.extend_from_slice(&bytes[end..]);
+        let document: SceneDocument<f32> = read_scene(newer.as_slice()).unwrap();
+        assert_eq!(document.version, (SCENE_VERSION_MAJOR, 7));
+        assert_eq!(document.world.unwrap().splat_count(), 2);
+
+        // An unknown required chunk is rejected
+        let mut required = bytes[..end].to_vec();
+        required.extend_from_slice(b"LODS");
+        required.extend_from_slice(&0u64.to_le_bytes());
+        required.extend_from_slice(&bytes[end..]);
+        assert!(matches!(read_scene::<f32, _>(required.as_slice()), Err(IoError::Unsupported(_))));
+
+        // Newer and older major versions are rejected
+        for version in [SCENE_VERSION_MAJOR + 1, 1] {
+            let mut major = bytes.clone();
+            major[8..10].copy_from_slice(&version.to_le_bytes());
+            assert!(matches!(read_scene::<f32, _>(major.as_slice()), Err(IoError::Unsupported(_))));
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(matches!(read_scene::<f32, _>(b"NOTASCENEFILE".as_slice()), Err(IoError::InvalidFormat(_))));
+
+        // Missing end chunk
+        let bytes = encode(&SceneDocument::new().with_world(test_world()));
+        assert!(read_scene::<f32, _>(&bytes[..bytes.len() - 12]).is_err());
+        // Truncated payload
+        assert!(read_scene::<f32, _>(&bytes[..40]).is_err());
+
+        // A splat count far beyond the payload size fails without allocating
+        let mut huge = SCENE_MAGIC.to_vec();
+        huge.extend_from_slice(&SCENE_VERSION_MAJOR.to_le_bytes());
+        huge.extend_from_slice(&SCENE_VERSION_MINOR.to_le_bytes());
+        let count = bincode::encode_to_vec(u64::MAX >> 8, bincode::config::standard()).unwrap();
+        huge.extend_from_slice(&CHUNK_SPLATS);
+        huge.extend_from_slice(&(count.len() as u64).to_le_bytes());
+        huge.extend_from_slice(&count);
+        assert!(matches!(read_scene::<f32, _>(huge.as_slice()), Err(IoError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_file_roundtrip() {
+        let path = std::env::temp_dir().join(format!("lights_scene_{}.lights", std::process::id()));
+        save_world(&test_world(), &path).unwrap();
+        assert_eq!(load_world(&path).unwrap().splat_count(), 2);
+
+        save_scene(&SceneDocument::<f32>::new(), &path).unwrap();
+        assert!(matches!(load_world(&path), Err(IoError::InvalidData(_))));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
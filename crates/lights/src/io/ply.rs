@@ -0,0 +1,724 @@
+//! Standard 3D Gaussian Splatting `.ply` import and export.
+//!
+//! Trained 3DGS scenes are stored as a single `vertex` element with one
+//! property per parameter, written by the reference trainer as `float`:
+//!
+//! - `x`, `y`, `z`: position
+//! - `nx`, `ny`, `nz`: unused normals (written as zero)
+//! - `f_dc_0..2`: spherical harmonic DC term
+//! - `f_rest_*`: higher SH bands, channel-major (all red, then green, then blue)
+//! - `opacity`: opacity logit, activated with a sigmoid
+//! - `scale_0..2`: log scale, activated with `exp`
+//! - `rot_0..3`: rotation quaternion `[w, x, y, z]`, normalized on load
+//!
+//! Plain point clouds with `red`/`green`/`blue` colors load as flat-colored
+//! splats. ASCII, binary little-endian and binary big-endian bodies are
+//! supported for both reading and writing.
+
+use super::IoError;
+use crate::numerics::types::quaternion::Quaternion;
+use crate::renderer::world::{GaussianSplat, Point3D, World};
+use crate::scene::spherical_harmonics::{coefficient_count, SphericalHarmonics, MAX_SH_DEGREE};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Opacities are clamped to this distance from 0 and 1 before taking the logit.
+const OPACITY_EPSILON: f64 = 1e-6;
+
+/// Encoding of the body of a PLY file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+impl PlyFormat {
+    fn header_name(self) -> &'static str {
+        match self {
+            PlyFormat::Ascii => "ascii",
+            PlyFormat::BinaryLittleEndian => "binary_little_endian",
+            PlyFormat::BinaryBigEndian => "binary_big_endian",
+        }
+    }
+}
+
+/// Scalar property types defined by the PLY specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScalarType {
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Float32,
+    Float64,
+}
+
+impl ScalarType {
+    fn parse(name: &str) -> Result<Self, IoError> {
+        Ok(match name {
+            "char" | "int8" => ScalarType::Int8,
+            "uchar" | "uint8" => ScalarType::UInt8,
+            "short" | "int16" => ScalarType::Int16,
+            "ushort" | "uint16" => ScalarType::UInt16,
+            "int" | "int32" => ScalarType::Int32,
+            "uint" | "uint32" => ScalarType::UInt32,
+            "float" | "float32" => ScalarType::Float32,
+            "double" | "float64" => ScalarType::Float64,
+            _ => return Err(IoError::InvalidFormat(format!("Unknown PLY property type '{}'", name))),
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            ScalarType::Int8 | ScalarType::UInt8 => 1,
+            ScalarType::Int16 | ScalarType::UInt16 => 2,
+            ScalarType::Int32 | ScalarType::UInt32 | ScalarType::Float32 => 4,
+            ScalarType::Float64 => 8,
+        }
+    }
+
+    fn decode(self, bytes: &[u8], big_endian: bool) -> f64 {
+        macro_rules! decode {
+            ($ty:ty) => {{
+                let array = bytes.try_into().expect("slice length matches scalar size");
+                if big_endian {
+                    <$ty>::from_be_bytes(array) as f64
+                } else {
+                    <$ty>::from_le_bytes(array) as f64
+                }
+            }};
+        }
+        match self {
+            ScalarType::Int8 => decode!(i8),
+            ScalarType::UInt8 => decode!(u8),
+            ScalarType::Int16 => decode!(i16),
+            ScalarType::UInt16 => decode!(u16),
+            ScalarType::Int32 => decode!(i32),
+            ScalarType::UInt32 => decode!(u32),
+            ScalarType::Float32 => decode!(f32),
+            ScalarType::Float64 => decode!(f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PropertyKind {
+    Scalar(ScalarType),
+    List { count: ScalarType, item: ScalarType },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Property {
+    name: String,
+    kind: PropertyKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Header {
+    format: PlyFormat,
+    elements: Vec<Element>,
+}
+
+/// Read a 3DGS `.ply` file into a new world.
+///
+/// Splats are assigned fresh IDs in file order. Elements other than `vertex`
+/// are skipped. Element counts that cannot fit in the rest of the file are
+/// rejected before anything is allocated for them.
+pub fn read_ply<R: BufRead>(mut reader: R) -> Result<World, IoError> {
+    let header = read_header(&mut reader)?;
+    match header.format {
+        PlyFormat::Ascii => {
+            let mut body = String::new();
+            reader.read_to_string(&mut body)?;
+            let mut tokens = body.split_whitespace();
+            read_body(&header, body.len(), |scalar_type| {
+                let token = tokens.next().ok_or_else(unexpected_end)?;
+                parse_ascii_value(token, scalar_type)
+            })
+        }
+        PlyFormat::BinaryLittleEndian | PlyFormat::BinaryBigEndian => {
+            let big_endian = header.format == PlyFormat::BinaryBigEndian;
+            let mut body = Vec::new();
+            reader.read_to_end(&mut body)?;
+            let mut remaining = body.as_slice();
+            read_body(&header, body.len(), |scalar_type| {
+                if remaining.len() < scalar_type.size() {
+                    return Err(unexpected_end());
+                }
+                let (bytes, rest) = remaining.split_at(scalar_type.size());
+                remaining = rest;
+                Ok(scalar_type.decode(bytes, big_endian))
+            })
+        }
+    }
+}
+
+/// Load a 3DGS `.ply` file from disk.
+pub fn load_ply(path: impl AsRef<Path>) -> Result<World, IoError> {
+    read_ply(BufReader::new(File::open(path)?))
+}
+
+/// Write the splats of a world in the 3DGS `.ply` layout.
+///
+/// All splats are written with the highest SH degree present in the world;
+/// splats with fewer bands are padded with zeros. Fails if a splat's
+/// covariance cannot be decomposed into rotation and scale.
+pub fn write_ply<W: Write>(world: &World, writer: W, format: PlyFormat) -> Result<(), IoError> {
+    let mut writer = BufWriter::new(writer);
+    let degree = world
+        .splats()
+        .iter()
+        .filter_map(|splat| splat.spherical_harmonics.as_ref().map(|harmonics| harmonics.degree()))
+        .max()
+        .unwrap_or(0);
+    let rest_count = 3 * (coefficient_count(degree) - 1);
+
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format {} 1.0", format.header_name())?;
+    writeln!(writer, "element vertex {}", world.splats().len())?;
+    let mut names: Vec<String> = ["x", "y", "z", "nx", "ny", "nz", "f_dc_0", "f_dc_1", "f_dc_2"]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    names.extend((0..rest_count).map(|index| format!("f_rest_{}", index)));
+    names.extend(
+        ["opacity", "scale_0", "scale_1", "scale_2", "rot_0", "rot_1", "rot_2", "rot_3"]
+            .iter()
+            .map(|name| name.to_string()),
+    );
+    for name in &names {
+        writeln!(writer, "property float {}", name)?;
+    }
+    writeln!(writer, "end_header")?;
+
+    let mut values = Vec::with_capacity(names.len());
+    for splat in world.splats() {
+        values.clear();
+        encode_splat(splat, degree, &mut values)?;
+        match format {
+            PlyFormat::Ascii => {
+                let line: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                writeln!(writer, "{}", line.join(" "))?;
+            }
+            PlyFormat::BinaryLittleEndian => {
+                for value in &values {
+                    writer.write_all(&value.to_le_bytes())?;
+                }
+            }
+            PlyFormat::BinaryBigEndian => {
+                for value in &values {
+                    writer.write_all(&value.to_be_bytes())?;
+                }
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Save the splats of a world to a 3DGS `.ply` file on disk.
+pub fn save_ply(world: &World, path: impl AsRef<Path>, format: PlyFormat) -> Result<(), IoError> {
+    write_ply(world, File::create(path)?, format)
+}
+
+fn read_header<R: BufRead>(reader: &mut R) -> Result<Header, IoError> {
+    let mut line = String::new();
+    let mut next_line = |line: &mut String| -> Result<(), IoError> {
+        line.clear();
+        if reader.read_line(line)? == 0 {
+            return Err(IoError::InvalidFormat("Unexpected end of PLY header".to_string()));
+        }
+        Ok(())
+    };
+
+    next_line(&mut line)?;
+    if line.trim_end() != "ply" {
+        return Err(IoError::InvalidFormat("Missing 'ply' magic line".to_string()));
+    }
+
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    loop {
+        next_line(&mut line)?;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["end_header"] => break,
+            [] | ["comment", ..] | ["obj_info", ..] => {}
+            ["format", name, _version] => {
+                format = Some(match *name {
+                    "ascii" => PlyFormat::Ascii,
+                    "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                    _ => return Err(IoError::Unsupported(format!("PLY format '{}'", name))),
+                });
+            }
+            ["element", name, count] => {
+                let count = count
+                    .parse()
+                    .map_err(|_| IoError::InvalidFormat(format!("Invalid element count '{}'", count)))?;
+                elements.push(Element {
+                    name: name.to_string(),
+                    count,
+                    properties: Vec::new(),
+                });
+            }
+            ["property", "list", count, item, name] => {
+                let element = elements
+                    .last_mut()
+                    .ok_or_else(|| IoError::InvalidFormat("Property declared before any element".to_string()))?;
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    kind: PropertyKind::List {
+                        count: ScalarType::parse(count)?,
+                        item: ScalarType::parse(item)?,
+                    },
+                });
+            }
+            ["property", scalar_type, name] => {
+                let element = elements
+                    .last_mut()
+                    .ok_or_else(|| IoError::InvalidFormat("Property declared before any element".to_string()))?;
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    kind: PropertyKind::Scalar(ScalarType::parse(scalar_type)?),
+                });
+            }
+            _ => {
+                return Err(IoError::InvalidFormat(format!(
+                    "Unrecognised PLY header line '{}'",
+                    line.trim_end()
+                )))
+            }
+        }
+    }
+
+    let format = format.ok_or_else(|| IoError::InvalidFormat("Missing PLY format line".to_string()))?;
+    Ok(Header { format, elements })
+}
+
+fn parse_ascii_value(token: &str, scalar_type: ScalarType) -> Result<f64, IoError> {
+    let value: f64 = token
+        .parse()
+        .map_err(|_| IoError::InvalidFormat(format!("Invalid PLY value '{}'", token)))?;
+    match scalar_type {
+        ScalarType::Float32 | ScalarType::Float64 => Ok(value),
+        _ if value.fract() == 0.0 => Ok(value),
+        _ => Err(IoError::InvalidFormat(format!("Expected an integer, found '{}'", token))),
+    }
+}
+
+fn unexpected_end() -> IoError {
+    IoError::InvalidFormat("Unexpected end of PLY data".to_string())
+}
+
+/// Fewest body bytes one record of `element` can take: the scalar and list
+/// count sizes in binary files, one character per value in ASCII files.
+fn min_record_size(element: &Element, format: PlyFormat) -> usize {
+    element
+        .properties
+        .iter()
+        .map(|property| match (format, &property.kind) {
+            (PlyFormat::Ascii, _) => 1,
+            (_, &PropertyKind::Scalar(scalar_type)) => scalar_type.size(),
+            (_, &PropertyKind::List { count, .. }) => count.size(),
+        })
+        .sum()
+}
+
+/// Read the elements of a PLY body of `body_size` bytes.
+fn read_body<F>(header: &Header, body_size: usize, mut next_value: F) -> Result<World, IoError>
+where
+    F: FnMut(ScalarType) -> Result<f64, IoError>,
+{
+    // The header is untrusted: a count too large for the file would otherwise
+    // drive the allocation and loop sizes below
+    for element in &header.elements {
+        let min_size = min_record_size(element, header.format);
+        if element.count.checked_mul(min_size).is_none_or(|size| size > body_size) {
+            return Err(IoError::InvalidFormat(format!(
+                "PLY element '{}' declares {} records but the body holds only {} bytes",
+                element.name, element.count, body_size
+            )));
+        }
+    }
+
+    let mut world = World::new();
+    for element in &header.elements {
+        if element.properties.is_empty() {
+            // Records without properties take no space
+            continue;
+        }
+        if element.name != "vertex" {
+            // Skip other elements; anything after the vertices is never read
+            for _ in 0..element.count {
+                for property in &element.properties {
+                    match property.kind {
+                        PropertyKind::Scalar(scalar_type) => {
+                            next_value(scalar_type)?;
+                        }
+                        PropertyKind::List { count, item } => {
+                            for _ in 0..next_value(count)? as usize {
+                                next_value(item)?;
+                            }
+                        }
+                    }
+                }
+            }
+            continue;
+        }
+
+        let layout = VertexLayout::new(element)?;
+        world.reserve(element.count);
+        let mut values = vec![0.0; element.properties.len()];
+        for _ in 0..element.count {
+            for (value, property) in values.iter_mut().zip(element.properties.iter()) {
+                if let PropertyKind::Scalar(scalar_type) = property.kind {
+                    *value = next_value(scalar_type)?;
+                }
+            }
+            world.add_splat(layout.decode(&values)?);
+        }
+        return Ok(world);
+    }
+    Err(IoError::InvalidFormat("PLY file has no vertex element".to_string()))
+}
+
+/// Property indices of the 3DGS parameters within a vertex record.
+#[derive(Debug)]
+struct VertexLayout {
+    position: [usize; 3],
+    dc: Option<[usize; 3]>,
+    rest: Vec<usize>,
+    degree: usize,
+    rgb: Option<([usize; 3], f64)>,
+    opacity: Option<usize>,
+    scale: Option<[usize; 3]>,
+    rotation: Option<[usize; 4]>,
+}
+
+impl VertexLayout {
+    fn new(element: &Element) -> Result<Self, IoError> {
+        if let Some(property) = element
+            .properties
+            .iter()
+            .find(|property| matches!(property.kind, PropertyKind::List { .. }))
+        {
+            return Err(IoError::Unsupported(format!("List property '{}' on vertices", property.name)));
+        }
+
+        let find = |name: &str| element.properties.iter().position(|property| property.name == name);
+        let find_all = |names: &[&str]| -> Option<Vec<usize>> { names.iter().map(|name| find(name)).collect() };
+
+        let position = find_all(&["x", "y", "z"])
+            .ok_or_else(|| IoError::InvalidFormat("Vertices need x, y and z properties".to_string()))?;
+        let dc = find_all(&["f_dc_0", "f_dc_1", "f_dc_2"]);
+        let opacity = find("opacity");
+        let scale = find_all(&["scale_0", "scale_1", "scale_2"]);
+        let rotation = find_all(&["rot_0", "rot_1", "rot_2", "rot_3"]);
+
+        // Flat colors from plain point clouds; integer channels are 0..255
+        let rgb = find_all(&["red", "green", "blue"]).map(|indices| {
+            let normalization = match element.properties[indices[0]].kind {
+                PropertyKind::Scalar(ScalarType::Float32 | ScalarType::Float64) => 1.0,
+                _ => 255.0,
+            };
+            ([indices[0], indices[1], indices[2]], normalization)
+        });
+
+        let rest_count = element
+            .properties
+            .iter()
+            .filter(|property| property.name.starts_with("f_rest_"))
+            .count();
+        let rest = (0..rest_count)
+            .map(|index| find(&format!("f_rest_{}", index)))
+            .collect::<Option<Vec<usize>>>()
+            .ok_or_else(|| IoError::InvalidFormat("f_rest_* properties are not numbered contiguously".to_string()))?;
+        let degree = (0..=MAX_SH_DEGREE)
+            .find(|&degree| 3 * (coefficient_count(degree) - 1) == rest_count)
+            .ok_or_else(|| {
+                IoError::Unsupported(format!("{} f_rest_* properties do not match SH degree 0-3", rest_count))
+            })?;
+        if dc.is_none() && rest_count > 0 {
+            return Err(IoError::InvalidFormat("f_rest_* properties without f_dc_*".to_string()));
+        }
+
+        Ok(Self {
+            position: [position[0], position[1], position[2]],
+            dc: dc.map(|indices| [indices[0], indices[1], indices[2]]),
+            rest,
+            degree,
+            rgb,
+            opacity,
+            scale: scale.map(|indices| [indices[0], indices[1], indices[2]]),
+            rotation: rotation.map(|indices| [indices[0], indices[1], indices[2], indices[3]]),
+        })
+    }
+
+    fn decode(&self, values: &[f64]) -> Result<GaussianSplat, IoError> {
+        let [x, y, z] = self.position.map(|index| values[index]);
+        let mut splat = GaussianSplat::new(0, Point3D::new(x, y, z));
+
+        if let Some(index) = self.opacity {
+            splat = splat.with_opacity(sigmoid(values[index]));
+        }
+
+        if let Some(scale) = self.scale {
+            let rotation = self.rotation.map_or(Quaternion::identity(), |rotation| rotation.map(|index| values[index]).into());
+            splat = splat.with_rotation_scale(rotation, scale.map(|index| values[index].exp()));
+        }
+
+        if let Some(dc) = self.dc {
+            let bands = coefficient_count(self.degree) - 1;
+            let mut coefficients = vec![dc.map(|index| values[index])];
+            coefficients.extend((0..bands).map(|band| {
+                core::array::from_fn(|channel| values[self.rest[channel * bands + band]])
+            }));
+            let harmonics = SphericalHarmonics::from_coefficients(self.degree, coefficients)
+                .map_err(IoError::InvalidFormat)?;
+            splat = splat.with_spherical_harmonics(harmonics);
+        } else if let Some((rgb, normalization)) = self.rgb {
+            let [r, g, b] = rgb.map(|index| values[index] / normalization);
+            splat = splat.with_color(r, g, b, 1.0);
+        }
+
+        Ok(splat)
+    }
+}
+
+/// Append the PLY values of one splat, in header order.
+fn encode_splat(splat: &GaussianSplat, degree: usize, values: &mut Vec<f32>) -> Result<(), IoError> {
+    let harmonics = match &splat.spherical_harmonics {
+        Some(harmonics) => {
+            let mut harmonics = harmonics.clone();
+            harmonics.set_degree(degree).map_err(IoError::InvalidData)?;
+            harmonics
+        }
+        None => SphericalHarmonics::from_color([splat.color[0], splat.color[1], splat.color[2]], degree)
+            .map_err(IoError::InvalidData)?,
+    };
+    let (rotation, scale) = splat.rotation_scale().map_err(IoError::InvalidData)?;
+
+    values.extend([splat.position.x, splat.position.y, splat.position.z].map(|value| value as f32));
+    values.extend([0.0; 3]);
+    values.extend(harmonics.dc().map(|value| value as f32));
+    let coefficients = harmonics.coefficients();
+    for channel in 0..3 {
+        values.extend(coefficients[1..].iter().map(|coefficient| coefficient[channel] as f32));
+    }
+    values.push(logit(splat.opacity * splat.color[3]) as f32);
+    values.extend(scale.map(|value| value.ln() as f32));
+    values.extend(<[f64; 4]>::from(rotation).map(|value| value as f32));
+    Ok(())
+}
+
+fn sigmoid(value: f64) -> f64 {
+    1.0 / (1.0 + (-value).exp())
+}
+
+fn logit(probability: f64) -> f64 {
+    let probability = probability.clamp(OPACITY_EPSILON, 1.0 - OPACITY_EPSILON);
+    (probability / (1.0 - probability)).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_world() -> World {
+        let mut world = World::new();
+
+        let mut harmonics = SphericalHarmonics::from_color([0.8, 0.4, 0.2], 3).unwrap();
+        for (index, coefficient) in harmonics.coefficients_mut().iter_mut().enumerate().skip(1) {
+            *coefficient = [index as f64 * 0.01, -(index as f64) * 0.02, 0.05];
+        }
+        world.add_splat(
+            GaussianSplat::new(0, Point3D::new(1.0, -2.0, 3.5))
+                .with_rotation_scale(Quaternion::new(0.9, 0.1, -0.3, 0.2), [0.5, 0.25, 0.1])
+                .with_opacity(0.75)
+                .with_spherical_harmonics(harmonics),
+        );
+        // A flat colored splat is written with zero higher bands
+        world.add_splat(
+            GaussianSplat::new(0, Point3D::new(-1.0, 0.0, 0.0))
+                .with_covariance([0.04, 0.0, 0.0, 0.09, 0.0, 0.01])
+                .with_color(0.1, 0.9, 0.3, 1.0)
+                .with_opacity(0.2),
+        );
+        world
+    }
+
+    fn assert_close(actual: &[f64], expected: &[f64], tolerance: f64) {
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < tolerance, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    fn assert_roundtrip(format: PlyFormat) {
+        let world = test_world();
+        let mut bytes = Vec::new();
+        write_ply(&world, &mut bytes, format).unwrap();
+        let loaded = read_ply(bytes.as_slice()).unwrap();
+
+        assert_eq!(loaded.splat_count(), world.splat_count());
+        for (original, loaded) in world.splats().iter().zip(loaded.splats().iter()) {
+            assert_eq!(loaded.position, Point3D::new(
+                original.position.x as f32 as f64,
+                original.position.y as f32 as f64,
+                original.position.z as f32 as f64,
+            ));
+            assert!((loaded.opacity - original.opacity).abs() < 1e-5);
+            assert_close(&loaded.covariance, &original.covariance, 1e-6);
+            assert_close(&loaded.color, &original.color, 1e-5);
+
+            let harmonics = loaded.spherical_harmonics.as_ref().unwrap();
+            assert_eq!(harmonics.degree(), 3);
+            if let Some(original_harmonics) = &original.spherical_harmonics {
+                for (a, b) in harmonics.coefficients().iter().zip(original_harmonics.coefficients()) {
+                    assert_close(a, b, 1e-5);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_binary_little_endian_roundtrip() {
+        assert_roundtrip(PlyFormat::BinaryLittleEndian);
+    }
+
+    #[test]
+    fn test_binary_big_endian_roundtrip() {
+        assert_roundtrip(PlyFormat::BinaryBigEndian);
+    }
+
+    #[test]
+    fn test_ascii_roundtrip() {
+        assert_roundtrip(PlyFormat::Ascii);
+    }
+
+    #[test]
+    fn test_header_layout() {
+        let mut bytes = Vec::new();
+        write_ply(&test_world(), &mut bytes, PlyFormat::BinaryLittleEndian).unwrap();
+        let header_end = bytes.windows(11).position(|window| window == b"end_header\n").unwrap();
+        let header = std::str::from_utf8(&bytes[..header_end]).unwrap();
+
+        assert!(header.starts_with("ply\nformat binary_little_endian 1.0\nelement vertex 2\n"));
+        assert!(header.contains("property float f_rest_44\n"));
+        assert!(!header.contains("f_rest_45"));
+        // 62 float properties per splat
+        assert_eq!(bytes.len() - header_end - 11, 2 * 62 * 4);
+    }
+
+    #[test]
+    fn test_activation_functions() {
+        let ply = "ply\n\
+            format ascii 1.0\n\
+            comment written by hand\n\
+            element vertex 1\n\
+            property float x\nproperty float y\nproperty float z\n\
+            property float f_dc_0\nproperty float f_dc_1\nproperty float f_dc_2\n\
+            property float opacity\n\
+            property float scale_0\nproperty float scale_1\nproperty float scale_2\n\
+            property float rot_0\nproperty float rot_1\nproperty float rot_2\nproperty float rot_3\n\
+            end_header\n\
+            1 2 3  0 0 0  0  0 0.6931471805599453 -0.6931471805599453  2 0 0 0\n";
+        let world = read_ply(ply.as_bytes()).unwrap();
+        let splat = &world.splats()[0];
+
+        assert_eq!(splat.position, Point3D::new(1.0, 2.0, 3.0));
+        assert!((splat.opacity - 0.5).abs() < 1e-12);
+        assert_close(&splat.color[..3], &[0.5, 0.5, 0.5], 1e-12);
+        // exp(scale) squared on the diagonal; the quaternion is normalized
+        assert_close(&splat.covariance, &[1.0, 0.0, 0.0, 4.0, 0.0, 0.25], 1e-9);
+        assert_eq!(splat.spherical_harmonics.as_ref().unwrap().degree(), 0);
+    }
+
+    #[test]
+    fn test_point_cloud_colors_and_skipped_elements() {
+        let ply = "ply\n\
+            format ascii 1.0\n\
+            element camera 1\n\
+            property float fx\n\
+            property list uchar int indices\n\
+            element vertex 2\n\
+            property double x\nproperty double y\nproperty double z\n\
+            property uchar red\nproperty uchar green\nproperty uchar blue\n\
+            element face 0\n\
+            property list uchar int vertex_indices\n\
+            end_header\n\
+            500.0 3 1 2 3\n\
+            0 0 0 255 0 51\n\
+            1 1 1 0 255 0\n";
+        let world = read_ply(ply.as_bytes()).unwrap();
+
+        assert_eq!(world.splat_count(), 2);
+        assert_close(&world.splats()[0].color, &[1.0, 0.0, 0.2, 1.0], 1e-12);
+        assert!(world.splats()[0].spherical_harmonics.is_none());
+        assert_eq!(world.splats()[1].position, Point3D::new(1.0, 1.0, 1.0));
+        assert_ne!(world.splats()[0].id, world.splats()[1].id);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(matches!(read_ply("nope\n".as_bytes()), Err(IoError::InvalidFormat(_))));
+        assert!(matches!(
+            read_ply("ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n1\n".as_bytes()),
+            Err(IoError::InvalidFormat(_))
+        ));
+        // Truncated body
+        assert!(read_ply(
+            "ply\nformat binary_little_endian 1.0\nelement vertex 1\nproperty float x\nproperty float y\nproperty float z\nend_header\n"
+                .as_bytes()
+        )
+        .is_err());
+        // Counts the body cannot hold are rejected up front, in every element
+        for element in ["vertex 1000000000000", "face 18446744073709551615"] {
+            let ply = format!(
+                "ply\nformat binary_little_endian 1.0\nelement {}\nproperty float x\nend_header\n\0\0\0\0",
+                element
+            );
+            assert!(matches!(read_ply(ply.as_bytes()), Err(IoError::InvalidFormat(_))));
+        }
+        let ply = "ply\nformat ascii 1.0\nelement vertex 1000000\nproperty float x\nproperty float y\nend_header\n1 2\n";
+        assert!(matches!(read_ply(ply.as_bytes()), Err(IoError::InvalidFormat(_))));
+        // Five f_rest values cannot form an SH degree
+        let mut header = String::from("ply\nformat ascii 1.0\nelement vertex 0\n");
+        for name in ["x", "y", "z", "f_dc_0", "f_dc_1", "f_dc_2"] {
+            header.push_str(&format!("property float {}\n", name));
+        }
+        for index in 0..5 {
+            header.push_str(&format!("property float f_rest_{}\n", index));
+        }
+        header.push_str("end_header\n");
+        assert!(matches!(read_ply(header.as_bytes()), Err(IoError::Unsupported(_))));
+
+        // Splats whose covariance cannot be decomposed are rejected on write
+        let mut world = World::new();
+        world.add_splat(GaussianSplat::new(0, Point3D::origin()).with_covariance([0.0; 6]));
+        assert!(matches!(
+            write_ply(&world, Vec::new(), PlyFormat::Ascii),
+            Err(IoError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn test_file_roundtrip() {
+        let path = std::env::temp_dir().join(format!("lights_ply_{}.ply", std::process::id()));
+        save_ply(&test_world(), &path, PlyFormat::BinaryLittleEndian).unwrap();
+        let world = load_ply(&path).unwrap();
+        assert_eq!(world.splat_count(), 2);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
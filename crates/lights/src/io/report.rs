@@ -0,0 +1,193 @@
+//! Size and quality reports for lossy splat formats.
+
+use super::ply::{write_ply, PlyFormat};
+use super::IoError;
+use crate::renderer::world::World;
+use std::fmt;
+use std::io::Write;
+
+/// How much an encoding shrank a scene, and how much it changed it.
+///
+/// Sizes are compared against the binary little-endian PLY encoding of the
+/// same world. Errors are measured between the original world and the world
+/// decoded back from the encoded bytes, splat by splat.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompressionReport {
+    /// Number of splats in the scene
+    pub splat_count: usize,
+
+    /// Size of the scene as a binary PLY file
+    pub ply_bytes: usize,
+
+    /// Size of the encoded scene
+    pub encoded_bytes: usize,
+
+    /// Largest distance between an original and decoded splat center
+    pub max_position_error: f64,
+
+    /// Mean distance between original and decoded splat centers
+    pub mean_position_error: f64,
+
+    /// Largest per-channel difference in base (view-independent) color
+    pub max_color_error: f64,
+
+    /// Largest difference in effective opacity
+    pub max_opacity_error: f64,
+
+    /// Largest covariance difference, relative to the splat's largest covariance entry
+    pub max_covariance_error: f64,
+}
+
+impl CompressionReport {
+    /// Measure an encoding by comparing the original world with its decoded form.
+    pub fn measure(original: &World, decoded: &World, encoded_bytes: usize) -> Result<Self, IoError> {
+        if original.splat_count() != decoded.splat_count() {
+            return Err(IoError::InvalidData(format!(
+                "Decoded {} splats, expected {}",
+                decoded.splat_count(),
+                original.splat_count()
+            )));
+        }
+
+        let mut counter = ByteCounter(0);
+        write_ply(original, &mut counter, PlyFormat::BinaryLittleEndian)?;
+
+        let mut report = Self {
+            splat_count: original.splat_count(),
+            ply_bytes: counter.0,
+            encoded_bytes,
+            ..Self::default()
+        };
+
+        let mut total_position_error = 0.0;
+        for (a, b) in original.splats().iter().zip(decoded.splats().iter()) {
+            let dx = a.position.x - b.position.x;
+            let dy = a.position.y - b.position.y;
+            let dz = a.position.z - b.position.z;
+            let position_error = (dx * dx + dy * dy + dz * dz).sqrt();
+            total_position_error += position_error;
+            report.max_position_error = report.max_position_error.max(position_error);
+
+            for channel in 0..3 {
+                report.max_color_error = report.max_color_error.max((a.color[channel] - b.color[channel]).abs());
+            }
+            let opacity_error = (a.opacity * a.color[3] - b.opacity * b.color[3]).abs();
+            report.max_opacity_error = report.max_opacity_error.max(opacity_error);
+
+            let magnitude = a.covariance.iter().fold(0.0_f64, |max, value| max.max(value.abs()));
+            if magnitude > 0.0 {
+                let difference = a
+                    .covariance
+                    .iter()
+                    .zip(b.covariance.iter())
+                    .fold(0.0_f64, |max, (x, y)| max.max((x - y).abs()));
+                report.max_covariance_error = report.max_covariance_error.max(difference / magnitude);
+            }
+        }
+        if report.splat_count > 0 {
+            report.mean_position_error = total_position_error / report.splat_count as f64;
+        }
+        Ok(report)
+    }
+
+    /// PLY size divided by encoded size.
+    pub fn compression_ratio(&self) -> f64 {
+        if self.encoded_bytes == 0 {
+            0.0
+        } else {
+            self.ply_bytes as f64 / self.encoded_bytes as f64
+        }
+    }
+
+    /// Average encoded size of one splat.
+    pub fn bytes_per_splat(&self) -> f64 {
+        if self.splat_count == 0 {
+            0.0
+        } else {
+            self.encoded_bytes as f64 / self.splat_count as f64
+        }
+    }
+}
+
+impl fmt::Display for CompressionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} splats: {} -> {} bytes ({:.1}x, {:.1} B/splat), position error max {:.2e} mean {:.2e}, \
+             color error max {:.3}, opacity error max {:.3}, covariance error max {:.2}%",
+            self.splat_count,
+            self.ply_bytes,
+            self.encoded_bytes,
+            self.compression_ratio(),
+            self.bytes_per_splat(),
+            self.max_position_error,
+            self.mean_position_error,
+            self.max_color_error,
+            self.max_opacity_error,
+            self.max_covariance_error * 100.0
+        )
+    }
+}
+
+/// A writer that only counts bytes.
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buffer: &[u8]) -> std::io::Result<usize> {
+        self.0 += buffer.len();
+        Ok(buffer.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::world::{GaussianSplat, Point3D};
+
+    #[test]
+    fn test_identical_worlds() {
+        let mut world = World::new();
+        world.add_splat(GaussianSplat::new(0, Point3D::new(1.0, 2.0, 3.0)));
+        let report = CompressionReport::measure(&world, &world.clone(), 32).unwrap();
+
+        assert_eq!(report.splat_count, 1);
+        assert_eq!(report.max_position_error, 0.0);
+        assert_eq!(report.max_covariance_error, 0.0);
+        // Header plus 17 floats for a degree 0 splat
+        assert!(report.ply_bytes > 17 * 4);
+        assert!(report.compression_ratio() > 1.0);
+        assert_eq!(report.bytes_per_splat(), 32.0);
+        assert!(report.to_string().starts_with("1 splats"));
+    }
+
+    #[test]
+    fn test_measured_errors() {
+        let mut original = World::new();
+        let first = original.add_splat(GaussianSplat::new(0, Point3D::origin()).with_color(0.5, 0.5, 0.5, 1.0));
+        let second = original.add_splat(GaussianSplat::new(0, Point3D::origin()));
+
+        let mut decoded = original.clone();
+        let mut splat = decoded.get_splat_mut(first).unwrap();
+        splat.set_position(Point3D::new(0.0, 0.3, 0.4));
+        splat.set_color([0.5, 0.6, 0.5, 1.0]);
+        let mut splat = decoded.get_splat_mut(second).unwrap();
+        splat.set_opacity(0.75);
+        let mut covariance = splat.covariance;
+        covariance[0] = 1.1;
+        splat.set_covariance(covariance);
+
+        let report = CompressionReport::measure(&original, &decoded, 64).unwrap();
+        assert!((report.max_position_error - 0.5).abs() < 1e-12);
+        assert!((report.mean_position_error - 0.25).abs() < 1e-12);
+        assert!((report.max_color_error - 0.1).abs() < 1e-12);
+        assert!((report.max_opacity_error - 0.25).abs() < 1e-12);
+        assert!((report.max_covariance_error - 0.1).abs() < 1e-12);
+
+        decoded.remove_splat(second);
+        assert!(CompressionReport::measure(&original, &decoded, 64).is_err());
+    }
+}
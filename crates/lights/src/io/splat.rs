@@ -0,0 +1,194 @@
+//! Compact `.splat` format used by web viewers.
+//!
+//! Each splat is a fixed 32-byte little-endian record with no header:
+//!
+//! - position: 3 x `f32`
+//! - scale: 3 x `f32` (linear, not log)
+//! - color: RGBA as 4 x `u8`, where alpha is the opacity
+//! - rotation: quaternion `[w, x, y, z]` as 4 x `u8`, mapped from `[-1, 1]`
+//!   with `byte = q * 128 + 128`
+//!
+//! Only the view-independent color is stored, so spherical harmonics are
+//! dropped on export. Splats are written in world order; viewers that expect
+//! the records pre-sorted by size and opacity re-sort on load.
+
+use super::report::CompressionReport;
+use super::IoError;
+use crate::numerics::types::quaternion::Quaternion;
+use crate::renderer::world::{GaussianSplat, Point3D, World};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Size of one `.splat` record in bytes.
+pub const SPLAT_RECORD_SIZE: usize = 32;
+
+/// Read a `.splat` file into a new world.
+pub fn read_splat<R: Read>(mut reader: R) -> Result<World, IoError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    if bytes.len() % SPLAT_RECORD_SIZE != 0 {
+        return Err(IoError::InvalidFormat(format!(
+            ".splat data is {} bytes, not a multiple of {}",
+            bytes.len(),
+            SPLAT_RECORD_SIZE
+        )));
+    }
+
+    let mut world = World::new();
+    world.reserve(bytes.len() / SPLAT_RECORD_SIZE);
+    for record in bytes.chunks_exact(SPLAT_RECORD_SIZE) {
+        world.add_splat(decode_record(record));
+    }
+    Ok(world)
+}
+
+/// Load a `.splat` file from disk.
+pub fn load_splat(path: impl AsRef<Path>) -> Result<World, IoError> {
+    read_splat(BufReader::new(File::open(path)?))
+}
+
+/// Write the splats of a world as `.splat` records.
+///
+/// Fails if a splat's covariance cannot be decomposed into rotation and scale.
+pub fn write_splat<W: Write>(world: &World, writer: W) -> Result<(), IoError> {
+    let mut writer = BufWriter::new(writer);
+    for splat in world.splats() {
+        writer.write_all(&encode_record(splat)?)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Save the splats of a world to a `.splat` file on disk.
+pub fn save_splat(world: &World, path: impl AsRef<Path>) -> Result<(), IoError> {
+    write_splat(world, File::create(path)?)
+}
+
+/// Encode a world as `.splat` and report the size and quality trade-off.
+pub fn splat_report(world: &World) -> Result<CompressionReport, IoError> {
+    let mut bytes = Vec::new();
+    write_splat(world, &mut bytes)?;
+    let decoded = read_splat(bytes.as_slice())?;
+    CompressionReport::measure(world, &decoded, bytes.len())
+}
+
+fn encode_record(splat: &GaussianSplat) -> Result<[u8; SPLAT_RECORD_SIZE], IoError> {
+    let (rotation, scale) = splat.rotation_scale().map_err(IoError::InvalidData)?;
+    let opacity = splat.opacity * splat.color[3];
+
+    let mut record = [0u8; SPLAT_RECORD_SIZE];
+    let floats = [
+        splat.position.x,
+        splat.position.y,
+        splat.position.z,
+        scale[0],
+        scale[1],
+        scale[2],
+    ];
+    for (chunk, value) in record[..24].chunks_exact_mut(4).zip(floats.iter()) {
+        chunk.copy_from_slice(&(*value as f32).to_le_bytes());
+    }
+    let color = [splat.color[0], splat.color[1], splat.color[2], opacity];
+    for (byte, value) in record[24..28].iter_mut().zip(color.iter()) {
+        *byte = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+    for (byte, value) in record[28..32].iter_mut().zip(<[f64; 4]>::from(rotation)) {
+        *byte = (value * 128.0 + 128.0).round().clamp(0.0, 255.0) as u8;
+    }
+    Ok(record)
+}
+
+fn decode_record(record: &[u8]) -> GaussianSplat {
+    let float = |index: usize| {
+        let offset = index * 4;
+        f32::from_le_bytes(record[offset..offset + 4].try_into().expect("record holds six floats")) as f64
+    };
+    let unit = |byte: u8| byte as f64 / 255.0;
+    let rotation: [f64; 4] = core::array::from_fn(|index| (record[28 + index] as f64 - 128.0) / 128.0);
+
+    GaussianSplat::new(0, Point3D::new(float(0), float(1), float(2)))
+        .with_rotation_scale(Quaternion::from(rotation), [float(3), float(4), float(5)])
+        .with_color(unit(record[24]), unit(record[25]), unit(record[26]), 1.0)
+        .with_opacity(unit(record[27]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::spherical_harmonics::SphericalHarmonics;
+
+    fn test_world() -> World {
+        let mut world = World::new();
+        world.add_splat(
+            GaussianSplat::new(0, Point3D::new(0.5, -1.25, 2.0))
+                .with_rotation_scale(Quaternion::new(0.8, 0.2, 0.4, -0.1), [0.3, 0.1, 0.02])
+                .with_color(1.0, 0.5, 0.0, 1.0)
+                .with_opacity(0.6),
+        );
+        world.add_splat(
+            GaussianSplat::new(0, Point3D::new(-3.0, 0.0, 1.0))
+                .with_covariance([0.04, 0.0, 0.0, 0.04, 0.0, 0.04])
+                .with_spherical_harmonics(SphericalHarmonics::from_color([0.2, 0.3, 0.4], 2).unwrap()),
+        );
+        world
+    }
+
+    #[test]
+    fn test_record_layout() {
+        let mut world = World::new();
+        world.add_splat(
+            GaussianSplat::new(0, Point3D::new(1.0, 2.0, 3.0))
+                .with_covariance([4.0, 0.0, 0.0, 1.0, 0.0, 0.25])
+                .with_color(1.0, 0.0, 0.5, 1.0)
+                .with_opacity(0.5),
+        );
+        let mut bytes = Vec::new();
+        write_splat(&world, &mut bytes).unwrap();
+
+        assert_eq!(bytes.len(), SPLAT_RECORD_SIZE);
+        assert_eq!(&bytes[0..4], &1.0f32.to_le_bytes());
+        assert_eq!(&bytes[12..16], &2.0f32.to_le_bytes());
+        assert_eq!(&bytes[20..24], &0.5f32.to_le_bytes());
+        assert_eq!(&bytes[24..28], &[255, 0, 128, 128]);
+        // Identity rotation
+        assert_eq!(&bytes[28..32], &[255, 128, 128, 128]);
+    }
+
+    #[test]
+    fn test_roundtrip_quality() {
+        let world = test_world();
+        let mut bytes = Vec::new();
+        write_splat(&world, &mut bytes).unwrap();
+        let decoded = read_splat(bytes.as_slice()).unwrap();
+
+        assert_eq!(decoded.splat_count(), 2);
+        assert!(decoded.splats()[1].spherical_harmonics.is_none());
+
+        let report = splat_report(&world).unwrap();
+        assert_eq!(report.encoded_bytes, 2 * SPLAT_RECORD_SIZE);
+        assert!(report.max_position_error < 1e-6);
+        assert!(report.max_color_error <= 0.5 / 255.0 + 1e-12);
+        assert!(report.max_opacity_error <= 0.5 / 255.0 + 1e-12);
+        assert!(report.max_covariance_error < 0.05);
+        assert!(report.compression_ratio() > 4.0);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(matches!(read_splat([0u8; 33].as_slice()), Err(IoError::InvalidFormat(_))));
+        assert_eq!(read_splat([0u8; 0].as_slice()).unwrap().splat_count(), 0);
+
+        let mut world = World::new();
+        world.add_splat(GaussianSplat::new(0, Point3D::origin()).with_covariance([0.0; 6]));
+        assert!(matches!(write_splat(&world, Vec::new()), Err(IoError::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_file_roundtrip() {
+        let path = std::env::temp_dir().join(format!("lights_splat_{}.splat", std::process::id()));
+        save_splat(&test_world(), &path).unwrap();
+        assert_eq!(load_splat(&path).unwrap().splat_count(), 2);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,216 @@
+// src/numerics/batch/kernels.rs
+// Batch kernels written once against the `Lanes` trait and instantiated for
+// every instruction set, so all paths perform the same operations in the
+// same order and produce bit-identical results.
+
+use super::{CovarianceProjection, Covariances, Points, PointsMut, ProjectedCovariancesMut};
+
+/// A vector of `f32` lanes.
+///
+/// Implementations must not fuse multiplies and adds, and `min`/`max` must
+/// follow x86 `minps`/`maxps` semantics, which `f32` mirrors exactly.
+pub(super) trait Lanes: Copy {
+    /// Number of `f32` values processed at once
+    const WIDTH: usize;
+
+    unsafe fn splat(value: f32) -> Self;
+
+    /// Load `WIDTH` values starting at `offset`.
+    unsafe fn load(values: &[f32], offset: usize) -> Self;
+
+    /// Store `WIDTH` values starting at `offset`.
+    unsafe fn store(self, values: &mut [f32], offset: usize);
+
+    unsafe fn add(self, other: Self) -> Self;
+    unsafe fn sub(self, other: Self) -> Self;
+    unsafe fn mul(self, other: Self) -> Self;
+    unsafe fn div(self, other: Self) -> Self;
+
+    /// `self < other ? self : other`, so NaN and equal inputs give `other`.
+    unsafe fn min(self, other: Self) -> Self;
+
+    /// `self > other ? self : other`, so NaN and equal inputs give `other`.
+    unsafe fn max(self, other: Self) -> Self;
+
+    /// `self / w`, except where `w` is zero or one, which keep `self`.
+    unsafe fn divide_by_w(self, w: Self) -> Self;
+}
+
+impl Lanes for f32 {
+    const WIDTH: usize = 1;
+
+    #[inline(always)]
+    unsafe fn splat(value: f32) -> Self {
+        value
+    }
+
+    #[inline(always)]
+    unsafe fn load(values: &[f32], offset: usize) -> Self {
+        values[offset]
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, values: &mut [f32], offset: usize) {
+        values[offset] = self;
+    }
+
+    #[inline(always)]
+    unsafe fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    #[inline(always)]
+    unsafe fn sub(self, other: Self) -> Self {
+        self - other
+    }
+
+    #[inline(always)]
+    unsafe fn mul(self, other: Self) -> Self {
+        self * other
+    }
+
+    #[inline(always)]
+    unsafe fn div(self, other: Self) -> Self {
+        self / other
+    }
+
+    #[inline(always)]
+    unsafe fn min(self, other: Self) -> Self {
+        if self < other { self } else { other }
+    }
+
+    #[inline(always)]
+    unsafe fn max(self, other: Self) -> Self {
+        if self > other { self } else { other }
+    }
+
+    #[inline(always)]
+    unsafe fn divide_by_w(self, w: Self) -> Self {
+        if w == 1.0 || w == 0.0 { self } else { self / w }
+    }
+}
+
+/// `m[0] * x + m[1] * y + m[2] * z + m[3]`, evaluated left to right.
+#[inline(always)]
+unsafe fn dot_row<L: Lanes>(row: &[L; 4], x: L, y: L, z: L) -> L {
+    row[0].mul(x).add(row[1].mul(y)).add(row[2].mul(z)).add(row[3])
+}
+
+#[inline(always)]
+unsafe fn splat_matrix<L: Lanes>(matrix: &[[f32; 4]; 4]) -> [[L; 4]; 4] {
+    let mut lanes = [[L::splat(0.0); 4]; 4];
+    for (lane_row, row) in lanes.iter_mut().zip(matrix) {
+        for (lane, &value) in lane_row.iter_mut().zip(row) {
+            *lane = L::splat(value);
+        }
+    }
+    lanes
+}
+
+/// Transform points from `start` on, returning the index of the first point
+/// left over because fewer than `L::WIDTH` remain.
+#[inline(always)]
+pub(super) unsafe fn transform_points<L: Lanes>(
+    matrix: &[[f32; 4]; 4],
+    projective: bool,
+    input: &Points<'_>,
+    output: &mut PointsMut<'_>,
+    start: usize,
+) -> usize {
+    let m = splat_matrix::<L>(matrix);
+    let mut offset = start;
+    while offset + L::WIDTH <= input.x.len() {
+        let (x, y, z) = (L::load(input.x, offset), L::load(input.y, offset), L::load(input.z, offset));
+        let mut transformed = [dot_row(&m[0], x, y, z), dot_row(&m[1], x, y, z), dot_row(&m[2], x, y, z)];
+        if projective {
+            let w = dot_row(&m[3], x, y, z);
+            transformed = transformed.map(|value| value.divide_by_w(w));
+        }
+        transformed[0].store(output.x, offset);
+        transformed[1].store(output.y, offset);
+        transformed[2].store(output.z, offset);
+        offset += L::WIDTH;
+    }
+    offset
+}
+
+/// View-space depths from `start` on; see `transform_points` for the return value.
+#[inline(always)]
+pub(super) unsafe fn view_depths<L: Lanes>(
+    view: &[[f32; 4]; 4],
+    input: &Points<'_>,
+    output: &mut [f32],
+    start: usize,
+) -> usize {
+    let m = splat_matrix::<L>(view);
+    let zero = L::splat(0.0);
+    let mut offset = start;
+    while offset + L::WIDTH <= input.x.len() {
+        let (x, y, z) = (L::load(input.x, offset), L::load(input.y, offset), L::load(input.z, offset));
+        // The camera looks down -Z, so depth is the negated view-space z
+        zero.sub(dot_row(&m[2], x, y, z)).store(output, offset);
+        offset += L::WIDTH;
+    }
+    offset
+}
+
+/// 2D covariances from `start` on; see `transform_points` for the return value.
+#[inline(always)]
+pub(super) unsafe fn project_covariances<L: Lanes>(
+    projection: &CovarianceProjection,
+    positions: &Points<'_>,
+    covariances: &Covariances<'_>,
+    output: &mut ProjectedCovariancesMut<'_>,
+    start: usize,
+) -> usize {
+    let mut r = [[L::splat(0.0); 3]; 3];
+    for (lane_row, row) in r.iter_mut().zip(&projection.rotation) {
+        for (lane, &value) in lane_row.iter_mut().zip(row) {
+            *lane = L::splat(value);
+        }
+    }
+    let zero = L::splat(0.0);
+    let (fx, fy) = (L::splat(projection.focal[0]), L::splat(projection.focal[1]));
+    let (limit_x, limit_y) = (L::splat(projection.jacobian_limit[0]), L::splat(projection.jacobian_limit[1]));
+    let (negative_limit_x, negative_limit_y) = (zero.sub(limit_x), zero.sub(limit_y));
+
+    let mut offset = start;
+    while offset + L::WIDTH <= positions.x.len() {
+        let (x, y, z) = (L::load(positions.x, offset), L::load(positions.y, offset), L::load(positions.z, offset));
+        let depth = zero.sub(z);
+        let depth_squared = depth.mul(depth);
+
+        // Jacobian of the perspective projection, with the point clamped near the frustum edge
+        let tx = x.div(depth).max(negative_limit_x).min(limit_x).mul(depth);
+        let ty = y.div(depth).max(negative_limit_y).min(limit_y).mul(depth);
+        let j00 = fx.div(depth);
+        let j02 = fx.mul(tx).div(depth_squared);
+        let j11 = zero.sub(fy).div(depth);
+        let j12 = zero.sub(fy).mul(ty).div(depth_squared);
+
+        // T = J * W, where W is the world-to-view rotation
+        let t0 = [0, 1, 2].map(|c| j00.mul(r[0][c]).add(j02.mul(r[2][c])));
+        let t1 = [0, 1, 2].map(|c| j11.mul(r[1][c]).add(j12.mul(r[2][c])));
+
+        let xx = L::load(covariances.xx, offset);
+        let xy = L::load(covariances.xy, offset);
+        let xz = L::load(covariances.xz, offset);
+        let yy = L::load(covariances.yy, offset);
+        let yz = L::load(covariances.yz, offset);
+        let zz = L::load(covariances.zz, offset);
+        let sigma = [[xx, xy, xz], [xy, yy, yz], [xz, yz, zz]];
+
+        // cov2d = T * Sigma * T^T
+        let times_sigma = |t: &[L; 3]| {
+            [0, 1, 2].map(|c| t[0].mul(sigma[0][c]).add(t[1].mul(sigma[1][c])).add(t[2].mul(sigma[2][c])))
+        };
+        let dot = |a: &[L; 3], b: &[L; 3]| a[0].mul(b[0]).add(a[1].mul(b[1])).add(a[2].mul(b[2]));
+        let (t0_sigma, t1_sigma) = (times_sigma(&t0), times_sigma(&t1));
+
+        dot(&t0_sigma, &t0).store(output.xx, offset);
+        dot(&t0_sigma, &t1).store(output.xy, offset);
+        dot(&t1_sigma, &t1).store(output.yy, offset);
+        offset += L::WIDTH;
+    }
+    offset
+}
@@ -0,0 +1,489 @@
+// src/numerics/batch/mod.rs
+// Batch kernels for splat projection and sorting: transform N points by a
+// Matrix4x4, compute N view-space depths and project N covariances to 2D.
+//
+// Data is laid out as structure-of-arrays `f32` slices. Each kernel has
+// SSE2/AVX2 (x86_64) and NEON (aarch64) paths chosen at runtime plus a
+// scalar fallback, all bit-identical. Slices carved out of a forge pool
+// created with `ForgeConfig::optimized_for_simd(level.alignment())` are
+// vector-aligned, but any slice works.
+//
+// `renderer::sorting::view_depths` computes the depths `DepthSorter` orders
+// splats by with `view_depths`. The rasterizer still projects splats one at a
+// time in f64, so moving its projection onto `transform_points` and
+// `project_covariances` is left for a later change.
+
+mod kernels;
+#[cfg(target_arch = "aarch64")]
+mod neon;
+#[cfg(target_arch = "x86_64")]
+mod x86;
+
+use crate::numerics::types::matrix::Matrix4x4;
+use forge::MemoryPool;
+
+/// Instruction set used by the batch kernels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SimdLevel {
+    /// One value at a time, available everywhere
+    Scalar,
+
+    /// 4 lanes, available on every x86_64 CPU
+    Sse2,
+
+    /// 8 lanes on x86_64 CPUs with AVX2
+    Avx2,
+
+    /// 4 lanes, available on every aarch64 CPU
+    Neon,
+}
+
+impl SimdLevel {
+    /// The widest instruction set this CPU supports.
+    pub fn detect() -> Self {
+        Self::supported().pop().unwrap_or(Self::Scalar)
+    }
+
+    /// Every instruction set this CPU supports, narrowest first.
+    pub fn supported() -> Vec<Self> {
+        [Self::Scalar, Self::Sse2, Self::Avx2, Self::Neon]
+            .into_iter()
+            .filter(|level| level.is_supported())
+            .collect()
+    }
+
+    /// Whether this CPU can run the kernels at this level.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Scalar => true,
+            #[cfg(target_arch = "x86_64")]
+            Self::Sse2 => std::arch::is_x86_feature_detected!("sse2"),
+            #[cfg(target_arch = "x86_64")]
+            Self::Avx2 => std::arch::is_x86_feature_detected!("avx2"),
+            #[cfg(target_arch = "aarch64")]
+            Self::Neon => std::arch::is_aarch64_feature_detected!("neon"),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Number of `f32` values processed at once.
+    pub fn lanes(self) -> usize {
+        match self {
+            Self::Scalar => 1,
+            Self::Sse2 | Self::Neon => 4,
+            Self::Avx2 => 8,
+        }
+    }
+
+    /// Vector width in bytes, the alignment to request from forge pools.
+    pub fn alignment(self) -> usize {
+        self.lanes() * std::mem::size_of::<f32>()
+    }
+}
+
+/// Positions as structure-of-arrays slices of equal length.
+#[derive(Debug, Clone, Copy)]
+pub struct Points<'a> {
+    pub x: &'a [f32],
+    pub y: &'a [f32],
+    pub z: &'a [f32],
+}
+
+impl<'a> Points<'a> {
+    pub fn new(x: &'a [f32], y: &'a [f32], z: &'a [f32]) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Number of points, taken from the x slice.
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty()
+    }
+}
+
+/// Output positions as structure-of-arrays slices.
+#[derive(Debug)]
+pub struct PointsMut<'a> {
+    pub x: &'a mut [f32],
+    pub y: &'a mut [f32],
+    pub z: &'a mut [f32],
+}
+
+impl<'a> PointsMut<'a> {
+    pub fn new(x: &'a mut [f32], y: &'a mut [f32], z: &'a mut [f32]) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// 3D covariances in the packed `[xx, xy, xz, yy, yz, zz]` layout of
+/// `GaussianSplat::covariance`, one slice per entry.
+#[derive(Debug, Clone, Copy)]
+pub struct Covariances<'a> {
+    pub xx: &'a [f32],
+    pub xy: &'a [f32],
+    pub xz: &'a [f32],
+    pub yy: &'a [f32],
+    pub yz: &'a [f32],
+    pub zz: &'a [f32],
+}
+
+impl<'a> Covariances<'a> {
+    pub fn new(xx: &'a [f32], xy: &'a [f32], xz: &'a [f32], yy: &'a [f32], yz: &'a [f32], zz: &'a [f32]) -> Self {
+        Self { xx, xy, xz, yy, yz, zz }
+    }
+}
+
+/// Output 2D covariances `[xx, xy, yy]` in pixels squared, one slice per entry.
+#[derive(Debug)]
+pub struct ProjectedCovariancesMut<'a> {
+    pub xx: &'a mut [f32],
+    pub xy: &'a mut [f32],
+    pub yy: &'a mut [f32],
+}
+
+impl<'a> ProjectedCovariancesMut<'a> {
+    pub fn new(xx: &'a mut [f32], xy: &'a mut [f32], yy: &'a mut [f32]) -> Self {
+        Self { xx, xy, yy }
+    }
+}
+
+/// Camera parameters for projecting covariances, as in `project_splat`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CovarianceProjection {
+    /// World-to-view rotation (rows are the camera right, up and backward axes)
+    pub rotation: [[f32; 3]; 3],
+
+    /// Focal lengths in pixels (x, y)
+    pub focal: [f32; 2],
+
+    /// Bounds on `x / depth` and `y / depth` where the Jacobian is evaluated,
+    /// avoiding extreme distortion near the frustum edge
+    pub jacobian_limit: [f32; 2],
+}
+
+impl CovarianceProjection {
+    /// Take the rotation from the linear part of a view matrix.
+    pub fn new(view: &Matrix4x4<f32>, focal: [f32; 2], jacobian_limit: [f32; 2]) -> Self {
+        let m = &view.data;
+        Self {
+            rotation: core::array::from_fn(|row| [m[row][0], m[row][1], m[row][2]]),
+            focal,
+            jacobian_limit,
+        }
+    }
+}
+
+/// Batch kernels bound to one instruction set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchKernels {
+    level: SimdLevel,
+}
+
+impl BatchKernels {
+    /// Kernels using the widest instruction set this CPU supports.
+    pub fn new() -> Self {
+        Self { level: SimdLevel::detect() }
+    }
+
+    /// Kernels using `level`, failing if this CPU does not support it.
+    pub fn with_level(level: SimdLevel) -> Result<Self, String> {
+        if level.is_supported() {
+            Ok(Self { level })
+        } else {
+            Err(format!("SIMD level {:?} is not supported on this CPU", level))
+        }
+    }
+
+    /// The instruction set in use.
+    pub fn level(&self) -> SimdLevel {
+        self.level
+    }
+
+    /// Transform every point by `matrix`, as `Matrix4x4::transform_point` does.
+    pub fn transform_points(&self, matrix: &Matrix4x4<f32>, input: Points<'_>, mut output: PointsMut<'_>) -> Result<(), String> {
+        check_lengths(
+            "transform_points",
+            input.len(),
+            &[input.y.len(), input.z.len(), output.x.len(), output.y.len(), output.z.len()],
+        )?;
+        let (m, projective) = (&matrix.data, !matrix.is_affine());
+
+        // SAFETY: `with_level` only accepts levels this CPU supports, and all slices have the same length
+        unsafe {
+            let done = match self.level {
+                #[cfg(target_arch = "x86_64")]
+                SimdLevel::Sse2 => x86::transform_points_sse2(m, projective, &input, &mut output),
+                #[cfg(target_arch = "x86_64")]
+                SimdLevel::Avx2 => x86::transform_points_avx2(m, projective, &input, &mut output),
+                #[cfg(target_arch = "aarch64")]
+                SimdLevel::Neon => neon::transform_points(m, projective, &input, &mut output),
+                _ => 0,
+            };
+            kernels::transform_points::<f32>(m, projective, &input, &mut output, done);
+        }
+        Ok(())
+    }
+
+    /// Distance of every point along the viewing direction of `view`, a
+    /// view matrix looking down -Z as built by `Matrix4x4::look_at`.
+    pub fn view_depths(&self, view: &Matrix4x4<f32>, input: Points<'_>, output: &mut [f32]) -> Result<(), String> {
+        check_lengths("view_depths", input.len(), &[input.y.len(), input.z.len(), output.len()])?;
+        let m = &view.data;
+
+        // SAFETY: as in `transform_points`
+        unsafe {
+            let done = match self.level {
+                #[cfg(target_arch = "x86_64")]
+                SimdLevel::Sse2 => x86::view_depths_sse2(m, &input, output),
+                #[cfg(target_arch = "x86_64")]
+                SimdLevel::Avx2 => x86::view_depths_avx2(m, &input, output),
+                #[cfg(target_arch = "aarch64")]
+                SimdLevel::Neon => neon::view_depths(m, &input, output),
+                _ => 0,
+            };
+            kernels::view_depths::<f32>(m, &input, output, done);
+        }
+        Ok(())
+    }
+
+    /// Project the covariance of each splat at the given view-space position
+    /// to a 2D screen-space covariance, `J W Sigma W^T J^T`.
+    ///
+    /// No low-pass filter is added; points must be in front of the camera.
+    pub fn project_covariances(
+        &self,
+        projection: &CovarianceProjection,
+        positions: Points<'_>,
+        covariances: Covariances<'_>,
+        mut output: ProjectedCovariancesMut<'_>,
+    ) -> Result<(), String> {
+        let c = &covariances;
+        check_lengths(
+            "project_covariances",
+            positions.len(),
+            &[
+                positions.y.len(),
+                positions.z.len(),
+                c.xx.len(),
+                c.xy.len(),
+                c.xz.len(),
+                c.yy.len(),
+                c.yz.len(),
+                c.zz.len(),
+                output.xx.len(),
+                output.xy.len(),
+                output.yy.len(),
+            ],
+        )?;
+
+        // SAFETY: as in `transform_points`
+        unsafe {
+            let done = match self.level {
+                #[cfg(target_arch = "x86_64")]
+                SimdLevel::Sse2 => x86::project_covariances_sse2(projection, &positions, c, &mut output),
+                #[cfg(target_arch = "x86_64")]
+                SimdLevel::Avx2 => x86::project_covariances_avx2(projection, &positions, c, &mut output),
+                #[cfg(target_arch = "aarch64")]
+                SimdLevel::Neon => neon::project_covariances(projection, &positions, c, &mut output),
+                _ => 0,
+            };
+            kernels::project_covariances::<f32>(projection, &positions, c, &mut output, done);
+        }
+        Ok(())
+    }
+}
+
+impl Default for BatchKernels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Carve zeroed `f32` buffers of the given lengths out of `pool`, each
+/// aligned to the pool's base alignment.
+///
+/// The buffers borrow the pool, so it cannot be reset or dropped while they
+/// are in use.
+pub fn pool_buffers<'a>(pool: &'a mut MemoryPool, lengths: &[usize]) -> Result<Vec<&'a mut [f32]>, String> {
+    let alignment = pool.base_alignment().max(std::mem::align_of::<f32>());
+    let mut buffers = Vec::with_capacity(lengths.len());
+    for &length in lengths {
+        let size = length
+            .checked_mul(std::mem::size_of::<f32>())
+            .ok_or_else(|| format!("Buffer of {} floats is too large", length))?;
+        let pointer = pool.allocate(size, alignment).map_err(|error| error.to_string())?.cast::<f32>();
+
+        // SAFETY: the allocation is `size` bytes, aligned for f32, disjoint from
+        // every other allocation, and outlives the `'a` borrow of the pool
+        let buffer = unsafe {
+            std::ptr::write_bytes(pointer.as_ptr(), 0, length);
+            std::slice::from_raw_parts_mut(pointer.as_ptr(), length)
+        };
+        buffers.push(buffer);
+    }
+    Ok(buffers)
+}
+
+fn check_lengths(kernel: &str, expected: usize, lengths: &[usize]) -> Result<(), String> {
+    match lengths.iter().find(|&&length| length != expected) {
+        Some(length) => Err(format!("{}: buffer of length {} does not match {} inputs", kernel, length, expected)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numerics::types::affine::DepthRange;
+    use crate::numerics::types::quaternion::Quaternion;
+    use crate::numerics::types::vector::Vector3;
+    use forge::ForgeConfig;
+
+    /// Deterministic values in [-1, 1), with a length that leaves a tail for every lane width.
+    fn values(count: usize, seed: u32) -> Vec<f32> {
+        let mut state = seed.wrapping_mul(2_654_435_761).wrapping_add(1);
+        (0..count)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (state as f32 / u32::MAX as f32) * 2.0 - 1.0
+            })
+            .collect()
+    }
+
+    fn assert_bits_equal(actual: &[f32], expected: &[f32], context: &str) {
+        for (index, (a, b)) in actual.iter().zip(expected).enumerate() {
+            assert_eq!(a.to_bits(), b.to_bits(), "{} differs at {}: {} != {}", context, index, a, b);
+        }
+    }
+
+    fn kernels() -> Vec<BatchKernels> {
+        SimdLevel::supported()
+            .into_iter()
+            .map(|level| BatchKernels::with_level(level).unwrap())
+            .collect()
+    }
+
+    const COUNT: usize = 37;
+
+    #[test]
+    fn test_transform_points_matches_scalar() {
+        let (x, y, z) = (values(COUNT, 1), values(COUNT, 2), values(COUNT, 3));
+        let affine = Matrix4x4::from_trs(
+            Vector3::new(0.5f32, -2.0, 3.0),
+            Quaternion::from_euler(0.3, 0.1, -0.4),
+            Vector3::new(1.5, 0.5, 2.0),
+        );
+        let projective = Matrix4x4::perspective(1.2f32, 1.5, 0.1, 50.0, DepthRange::ZeroToOne);
+
+        for matrix in [affine, projective] {
+            let expected: Vec<Vector3<f32>> = (0..COUNT)
+                .map(|i| matrix.transform_point(Vector3::new(x[i], y[i], z[i])))
+                .collect();
+            for kernels in kernels() {
+                let (mut ox, mut oy, mut oz) = (vec![0.0; COUNT], vec![0.0; COUNT], vec![0.0; COUNT]);
+                kernels
+                    .transform_points(&matrix, Points::new(&x, &y, &z), PointsMut::new(&mut ox, &mut oy, &mut oz))
+                    .unwrap();
+                let context = format!("{:?}", kernels.level());
+                assert_bits_equal(&ox, &expected.iter().map(|p| p.x).collect::<Vec<_>>(), &context);
+                assert_bits_equal(&oy, &expected.iter().map(|p| p.y).collect::<Vec<_>>(), &context);
+                assert_bits_equal(&oz, &expected.iter().map(|p| p.z).collect::<Vec<_>>(), &context);
+            }
+        }
+    }
+
+    #[test]
+    fn test_view_depths_and_covariances_match_scalar() {
+        let eye = Vector3::new(0.3f32, 0.2, 6.0);
+        let view = Matrix4x4::look_at(eye, Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+        let (x, y, z) = (values(COUNT, 4), values(COUNT, 5), values(COUNT, 6));
+        let scalar = BatchKernels::with_level(SimdLevel::Scalar).unwrap();
+
+        let mut expected_depths = vec![0.0; COUNT];
+        scalar.view_depths(&view, Points::new(&x, &y, &z), &mut expected_depths).unwrap();
+        for (i, depth) in expected_depths.iter().enumerate() {
+            let along_view = (Vector3::new(x[i], y[i], z[i]) - eye).dot(&Vector3::new(-0.3, -0.2, -6.0).normalize().unwrap());
+            assert!((depth - along_view).abs() < 1e-5);
+        }
+
+        // View-space positions in front of the camera, some far off-axis to hit the Jacobian clamp
+        let (vx, vy) = (values(COUNT, 7).iter().map(|v| v * 8.0).collect::<Vec<_>>(), values(COUNT, 8));
+        let vz: Vec<f32> = values(COUNT, 9).iter().map(|v| -3.0 - v).collect();
+        let packed = [values(COUNT, 10), values(COUNT, 11), values(COUNT, 12), values(COUNT, 13), values(COUNT, 14), values(COUNT, 15)];
+        let covariances = Covariances::new(&packed[0], &packed[1], &packed[2], &packed[3], &packed[4], &packed[5]);
+        let projection = CovarianceProjection::new(&view, [400.0, 300.0], [0.5, 0.4]);
+
+        let mut expected = [vec![0.0; COUNT], vec![0.0; COUNT], vec![0.0; COUNT]];
+        let [exx, exy, eyy] = &mut expected;
+        scalar
+            .project_covariances(&projection, Points::new(&vx, &vy, &vz), covariances, ProjectedCovariancesMut::new(exx, exy, eyy))
+            .unwrap();
+
+        for kernels in kernels() {
+            let context = format!("{:?}", kernels.level());
+            let mut depths = vec![0.0; COUNT];
+            kernels.view_depths(&view, Points::new(&x, &y, &z), &mut depths).unwrap();
+            assert_bits_equal(&depths, &expected_depths, &context);
+
+            let mut actual = [vec![0.0; COUNT], vec![0.0; COUNT], vec![0.0; COUNT]];
+            let [xx, xy, yy] = &mut actual;
+            kernels
+                .project_covariances(&projection, Points::new(&vx, &vy, &vz), covariances, ProjectedCovariancesMut::new(xx, xy, yy))
+                .unwrap();
+            for (a, e) in actual.iter().zip(&expected) {
+                assert_bits_equal(a, e, &context);
+            }
+        }
+    }
+
+    #[test]
+    fn test_project_covariance_of_isotropic_splat() {
+        // A unit sphere on the optical axis at depth 2 spans focal / 2 pixels per unit
+        let projection = CovarianceProjection::new(&Matrix4x4::from_scale(Vector3::new(1.0, 1.0, 1.0)), [100.0, 80.0], [1.0, 1.0]);
+        let (x, y, z) = ([0.0f32], [0.0f32], [-2.0f32]);
+        let (one, zero) = ([1.0f32], [0.0f32]);
+        let (mut xx, mut xy, mut yy) = ([0.0f32], [0.0f32], [0.0f32]);
+        BatchKernels::new()
+            .project_covariances(
+                &projection,
+                Points::new(&x, &y, &z),
+                Covariances::new(&one, &zero, &zero, &one, &zero, &one),
+                ProjectedCovariancesMut::new(&mut xx, &mut xy, &mut yy),
+            )
+            .unwrap();
+        assert_eq!((xx[0], xy[0], yy[0]), (2500.0, 0.0, 1600.0));
+    }
+
+    #[test]
+    fn test_forge_pool_buffers() {
+        let level = SimdLevel::detect();
+        let mut pool = ForgeConfig::optimized_for_simd(level.alignment()).create_pool().unwrap();
+        let mut buffers = pool_buffers(&mut pool, &[COUNT; 6]).unwrap();
+        for buffer in &buffers {
+            assert_eq!(buffer.len(), COUNT);
+            assert_eq!(buffer.as_ptr() as usize % level.alignment(), 0);
+            assert!(buffer.iter().all(|&value| value == 0.0));
+        }
+
+        let [x, y, z, ox, oy, oz]: [&mut [f32]; 6] = std::mem::take(&mut buffers).try_into().unwrap();
+        for (i, ((x, y), z)) in x.iter_mut().zip(y.iter_mut()).zip(z.iter_mut()).enumerate() {
+            (*x, *y, *z) = (i as f32, 1.0, 2.0);
+        }
+        let matrix = Matrix4x4::from_translation(Vector3::new(1.0f32, 0.0, 0.0));
+        BatchKernels::new()
+            .transform_points(&matrix, Points::new(x, y, z), PointsMut::new(ox, oy, oz))
+            .unwrap();
+        assert_eq!(ox[COUNT - 1], COUNT as f32);
+
+        let error = BatchKernels::new()
+            .view_depths(&matrix, Points::new(x, y, &z[1..]), ox)
+            .unwrap_err();
+        assert!(error.contains("does not match"));
+    }
+}
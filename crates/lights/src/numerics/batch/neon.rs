@@ -0,0 +1,87 @@
+// src/numerics/batch/neon.rs
+// NEON lanes for the batch kernels.
+
+use super::kernels::{self, Lanes};
+use super::{CovarianceProjection, Covariances, Points, PointsMut, ProjectedCovariancesMut};
+use core::arch::aarch64::*;
+
+impl Lanes for float32x4_t {
+    const WIDTH: usize = 4;
+
+    #[inline(always)]
+    unsafe fn splat(value: f32) -> Self {
+        vdupq_n_f32(value)
+    }
+
+    #[inline(always)]
+    unsafe fn load(values: &[f32], offset: usize) -> Self {
+        vld1q_f32(values[offset..offset + 4].as_ptr())
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, values: &mut [f32], offset: usize) {
+        vst1q_f32(values[offset..offset + 4].as_mut_ptr(), self)
+    }
+
+    #[inline(always)]
+    unsafe fn add(self, other: Self) -> Self {
+        vaddq_f32(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn sub(self, other: Self) -> Self {
+        vsubq_f32(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn mul(self, other: Self) -> Self {
+        vmulq_f32(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn div(self, other: Self) -> Self {
+        vdivq_f32(self, other)
+    }
+
+    // vminq/vmaxq propagate NaN, so select explicitly to match the other paths
+    #[inline(always)]
+    unsafe fn min(self, other: Self) -> Self {
+        vbslq_f32(vcltq_f32(self, other), self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn max(self, other: Self) -> Self {
+        vbslq_f32(vcgtq_f32(self, other), self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn divide_by_w(self, w: Self) -> Self {
+        let keep = vorrq_u32(vceqq_f32(w, vdupq_n_f32(1.0)), vceqq_f32(w, vdupq_n_f32(0.0)));
+        vbslq_f32(keep, self, vdivq_f32(self, w))
+    }
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn transform_points(
+    matrix: &[[f32; 4]; 4],
+    projective: bool,
+    input: &Points<'_>,
+    output: &mut PointsMut<'_>,
+) -> usize {
+    kernels::transform_points::<float32x4_t>(matrix, projective, input, output, 0)
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn view_depths(view: &[[f32; 4]; 4], input: &Points<'_>, output: &mut [f32]) -> usize {
+    kernels::view_depths::<float32x4_t>(view, input, output, 0)
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn project_covariances(
+    projection: &CovarianceProjection,
+    positions: &Points<'_>,
+    covariances: &Covariances<'_>,
+    output: &mut ProjectedCovariancesMut<'_>,
+) -> usize {
+    kernels::project_covariances::<float32x4_t>(projection, positions, covariances, output, 0)
+}
@@ -0,0 +1,153 @@
+// src/numerics/batch/x86.rs
+// SSE2 and AVX2 lanes for the batch kernels.
+
+use super::kernels::{self, Lanes};
+use super::{CovarianceProjection, Covariances, Points, PointsMut, ProjectedCovariancesMut};
+use core::arch::x86_64::*;
+
+impl Lanes for __m128 {
+    const WIDTH: usize = 4;
+
+    #[inline(always)]
+    unsafe fn splat(value: f32) -> Self {
+        _mm_set1_ps(value)
+    }
+
+    #[inline(always)]
+    unsafe fn load(values: &[f32], offset: usize) -> Self {
+        _mm_loadu_ps(values[offset..offset + 4].as_ptr())
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, values: &mut [f32], offset: usize) {
+        _mm_storeu_ps(values[offset..offset + 4].as_mut_ptr(), self)
+    }
+
+    #[inline(always)]
+    unsafe fn add(self, other: Self) -> Self {
+        _mm_add_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn sub(self, other: Self) -> Self {
+        _mm_sub_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn mul(self, other: Self) -> Self {
+        _mm_mul_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn div(self, other: Self) -> Self {
+        _mm_div_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn min(self, other: Self) -> Self {
+        _mm_min_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn max(self, other: Self) -> Self {
+        _mm_max_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn divide_by_w(self, w: Self) -> Self {
+        let keep = _mm_or_ps(_mm_cmpeq_ps(w, _mm_set1_ps(1.0)), _mm_cmpeq_ps(w, _mm_setzero_ps()));
+        _mm_or_ps(_mm_and_ps(keep, self), _mm_andnot_ps(keep, _mm_div_ps(self, w)))
+    }
+}
+
+impl Lanes for __m256 {
+    const WIDTH: usize = 8;
+
+    #[inline(always)]
+    unsafe fn splat(value: f32) -> Self {
+        _mm256_set1_ps(value)
+    }
+
+    #[inline(always)]
+    unsafe fn load(values: &[f32], offset: usize) -> Self {
+        _mm256_loadu_ps(values[offset..offset + 8].as_ptr())
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, values: &mut [f32], offset: usize) {
+        _mm256_storeu_ps(values[offset..offset + 8].as_mut_ptr(), self)
+    }
+
+    #[inline(always)]
+    unsafe fn add(self, other: Self) -> Self {
+        _mm256_add_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn sub(self, other: Self) -> Self {
+        _mm256_sub_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn mul(self, other: Self) -> Self {
+        _mm256_mul_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn div(self, other: Self) -> Self {
+        _mm256_div_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn min(self, other: Self) -> Self {
+        _mm256_min_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn max(self, other: Self) -> Self {
+        _mm256_max_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn divide_by_w(self, w: Self) -> Self {
+        let keep = _mm256_or_ps(
+            _mm256_cmp_ps::<_CMP_EQ_OQ>(w, _mm256_set1_ps(1.0)),
+            _mm256_cmp_ps::<_CMP_EQ_OQ>(w, _mm256_setzero_ps()),
+        );
+        _mm256_blendv_ps(_mm256_div_ps(self, w), self, keep)
+    }
+}
+
+/// Kernel entry points compiled with `$feature` enabled, so the lane
+/// operations inline into vector instructions.
+macro_rules! entry_points {
+    ($feature:literal, $lanes:ty, $transform:ident, $depths:ident, $covariances:ident) => {
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn $transform(
+            matrix: &[[f32; 4]; 4],
+            projective: bool,
+            input: &Points<'_>,
+            output: &mut PointsMut<'_>,
+        ) -> usize {
+            kernels::transform_points::<$lanes>(matrix, projective, input, output, 0)
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn $depths(view: &[[f32; 4]; 4], input: &Points<'_>, output: &mut [f32]) -> usize {
+            kernels::view_depths::<$lanes>(view, input, output, 0)
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn $covariances(
+            projection: &CovarianceProjection,
+            positions: &Points<'_>,
+            covariances: &Covariances<'_>,
+            output: &mut ProjectedCovariancesMut<'_>,
+        ) -> usize {
+            kernels::project_covariances::<$lanes>(projection, positions, covariances, output, 0)
+        }
+    };
+}
+
+entry_points!("sse2", __m128, transform_points_sse2, view_depths_sse2, project_covariances_sse2);
+entry_points!("avx2", __m256, transform_points_avx2, view_depths_avx2, project_covariances_avx2);
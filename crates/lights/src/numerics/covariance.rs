@@ -0,0 +1,181 @@
+// src/numerics/covariance.rs
+// 3D Gaussian covariance math shared by the scene model and the renderer:
+// conversions between covariances and rotation/scale parameters, validation,
+// inversion and Mahalanobis distances along a line.
+//
+// 3DGS trainers store each splat as a unit quaternion rotation and per-axis
+// scale, with `Sigma = R * S * S^T * R^T`. Covariances use the packed
+// upper-triangular layout `[xx, xy, xz, yy, yz, zz]` of `Splat::covariance`.
+
+#![allow(dead_code)]
+
+use crate::numerics::types::decomposition::{Cholesky, SymmetricEigen};
+use crate::numerics::types::matrix::Matrix3x3;
+use crate::numerics::types::quaternion::Quaternion;
+
+/// Build a covariance from a rotation quaternion and per-axis scale.
+///
+/// The quaternion does not need to be normalized. Negative scales are
+/// treated as their absolute value.
+pub fn covariance_from_rotation_scale(rotation: Quaternion<f64>, scale: [f64; 3]) -> [f64; 6] {
+    let r = rotation.to_matrix3().data;
+    let s = scale.map(|axis| axis * axis);
+
+    // Sigma = R * diag(s) * R^T
+    let entry = |i: usize, j: usize| (0..3).map(|k| r[i][k] * s[k] * r[j][k]).sum::<f64>();
+    [entry(0, 0), entry(0, 1), entry(0, 2), entry(1, 1), entry(1, 2), entry(2, 2)]
+}
+
+/// Decompose a covariance into a rotation quaternion and per-axis scale.
+///
+/// Scales are sorted from largest to smallest and the quaternion is returned
+/// with a non-negative `w`. Fails if the covariance is not a valid
+/// symmetric positive definite matrix.
+pub fn rotation_scale_from_covariance(covariance: &[f64; 6]) -> Result<(Quaternion<f64>, [f64; 3]), String> {
+    validate_covariance(covariance)?;
+    let SymmetricEigen { eigenvalues, eigenvectors } = SymmetricEigen::new(unpack(covariance));
+    let mut axes = Matrix3x3::new(eigenvectors);
+
+    // Keep a proper rotation (det = +1) by flipping the last axis if needed
+    if axes.determinant() < 0.0 {
+        for row in axes.data.iter_mut() {
+            row[2] = -row[2];
+        }
+    }
+
+    Ok((Quaternion::from_matrix3(&axes), eigenvalues.map(f64::sqrt)))
+}
+
+/// Check that a covariance is finite, symmetric positive definite.
+pub fn validate_covariance(covariance: &[f64; 6]) -> Result<(), String> {
+    if covariance.iter().any(|value| !value.is_finite()) {
+        return Err("Covariance contains non-finite values".to_string());
+    }
+
+    // Cholesky succeeds exactly when the matrix is numerically positive definite
+    Cholesky::new(unpack(covariance))
+        .map(|_| ())
+        .map_err(|_| format!("Covariance {:?} is not positive definite", covariance))
+}
+
+/// Inverse of a packed covariance, or `None` if it is singular or not finite.
+pub fn invert_covariance(covariance: &[f64; 6]) -> Option<[f64; 6]> {
+    let [xx, xy, xz, yy, yz, zz] = *covariance;
+    let cofactors = [
+        yy * zz - yz * yz,
+        xz * yz - xy * zz,
+        xy * yz - xz * yy,
+        xx * zz - xz * xz,
+        xy * xz - xx * yz,
+        xx * yy - xy * xy,
+    ];
+    let det = xx * cofactors[0] + xy * cofactors[1] + xz * cofactors[2];
+    if det == 0.0 || !det.is_finite() {
+        return None;
+    }
+    Some(cofactors.map(|c| c / det))
+}
+
+/// Coefficients `[dd, pd, pp]` of the squared Mahalanobis distance
+/// `dd t^2 + 2 pd t + pp` along the line `offset + t * direction`.
+///
+/// `inverse` is a packed inverse covariance and `offset` the line origin
+/// relative to the Gaussian's center.
+pub fn mahalanobis_along_line(inverse: &[f64; 6], offset: &[f64; 3], direction: &[f64; 3]) -> [f64; 3] {
+    let a = inverse;
+    let quadratic_form = |u: &[f64; 3], v: &[f64; 3]| {
+        u[0] * (a[0] * v[0] + a[1] * v[1] + a[2] * v[2])
+            + u[1] * (a[1] * v[0] + a[3] * v[1] + a[4] * v[2])
+            + u[2] * (a[2] * v[0] + a[4] * v[1] + a[5] * v[2])
+    };
+    [
+        quadratic_form(direction, direction),
+        quadratic_form(offset, direction),
+        quadratic_form(offset, offset),
+    ]
+}
+
+fn unpack(c: &[f64; 6]) -> [[f64; 3]; 3] {
+    [[c[0], c[1], c[2]], [c[1], c[3], c[4]], [c[2], c[4], c[5]]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < 1e-9, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_invert_covariance() {
+        let covariance = covariance_from_rotation_scale(Quaternion::new(0.9, 0.2, -0.3, 0.25), [0.5, 0.2, 0.05]);
+        let inverse = invert_covariance(&covariance).unwrap();
+        let product: [[f64; 3]; 3] = core::array::from_fn(|i| {
+            core::array::from_fn(|j| (0..3).map(|k| unpack(&covariance)[i][k] * unpack(&inverse)[k][j]).sum())
+        });
+        assert_close(&product.concat(), &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+        assert!(invert_covariance(&[0.0; 6]).is_none());
+    }
+
+    #[test]
+    fn test_identity_rotation() {
+        let covariance = covariance_from_rotation_scale(Quaternion::identity(), [1.0, 2.0, 3.0]);
+        assert_close(&covariance, &[1.0, 0.0, 0.0, 4.0, 0.0, 9.0]);
+
+        let (rotation, scale) = rotation_scale_from_covariance(&covariance).unwrap();
+        assert_close(&scale, &[3.0, 2.0, 1.0]);
+        // Rebuilding from the decomposition gives back the same covariance
+        assert_close(&covariance_from_rotation_scale(rotation, scale), &covariance);
+    }
+
+    #[test]
+    fn test_rotation_scale_roundtrip() {
+        let rotation = Quaternion::new(0.9, 0.2, -0.3, 0.25).normalize().unwrap();
+        let scale = [0.5, 0.2, 0.05];
+        let covariance = covariance_from_rotation_scale(rotation, scale);
+        validate_covariance(&covariance).unwrap();
+
+        let (decomposed_rotation, decomposed_scale) = rotation_scale_from_covariance(&covariance).unwrap();
+        assert_close(&decomposed_scale, &scale);
+        assert_close(&covariance_from_rotation_scale(decomposed_rotation, decomposed_scale), &covariance);
+
+        // With distinct scales each principal axis is unique up to sign
+        let expected = rotation.to_matrix3().data;
+        let actual = decomposed_rotation.to_matrix3().data;
+        for column in 0..3 {
+            let dot: f64 = (0..3).map(|row| expected[row][column] * actual[row][column]).sum();
+            assert!((dot.abs() - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_mahalanobis_along_line() {
+        // Variances 4, 1, 0.25 along x, y, z
+        let inverse = invert_covariance(&[4.0, 0.0, 0.0, 1.0, 0.0, 0.25]).unwrap();
+        let [dd, pd, pp] = mahalanobis_along_line(&inverse, &[2.0, 1.0, 0.0], &[0.0, 0.0, 1.0]);
+        assert_close(&[dd, pd, pp], &[4.0, 0.0, 2.0]);
+
+        // Agrees with evaluating the quadratic form at a point on the line
+        let (offset, direction, t) = ([0.3, -0.2, 0.5], [0.6, 0.0, -0.8], 0.7);
+        let [dd, pd, pp] = mahalanobis_along_line(&inverse, &offset, &direction);
+        let point: [f64; 3] = std::array::from_fn(|i| offset[i] + t * direction[i]);
+        let [_, _, expected] = mahalanobis_along_line(&inverse, &point, &direction);
+        assert!((dd * t * t + 2.0 * pd * t + pp - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_validation_rejects_invalid_covariances() {
+        assert!(validate_covariance(&[1.0, 0.0, 0.0, 1.0, 0.0, 1.0]).is_ok());
+        // Negative variance
+        assert!(validate_covariance(&[-1.0, 0.0, 0.0, 1.0, 0.0, 1.0]).is_err());
+        // Singular (zero scale on one axis)
+        assert!(validate_covariance(&[1.0, 0.0, 0.0, 1.0, 0.0, 0.0]).is_err());
+        // Correlation larger than one
+        assert!(validate_covariance(&[1.0, 2.0, 0.0, 1.0, 0.0, 1.0]).is_err());
+        assert!(validate_covariance(&[f64::NAN, 0.0, 0.0, 1.0, 0.0, 1.0]).is_err());
+        assert!(rotation_scale_from_covariance(&[0.0; 6]).is_err());
+    }
+}
@@ -3,10 +3,17 @@
 
 #![allow(dead_code)]
 
+pub mod batch;
+pub mod covariance;
+
 pub mod types {
     // The submodules live in src/numerics/types/*.rs
     pub mod vector;
     pub mod matrix;
     pub mod point;
     pub mod traits;
+    pub mod half;
+    pub mod quaternion;
+    pub mod decomposition;
+    pub mod affine;
 }
@@ -0,0 +1,378 @@
+// src/numerics/types/affine.rs
+// Affine and projective transform builders for Matrix4x4, and the
+// MatrixConvention type documenting how those matrices are laid out.
+
+#![allow(dead_code)]
+
+use crate::numerics::types::matrix::{Matrix3x3, Matrix4x4};
+use crate::numerics::types::quaternion::Quaternion;
+use crate::numerics::types::traits::FloatingPoint;
+use crate::numerics::types::vector::Vector3;
+
+/// Order in which the 16 entries of a matrix are stored when flattened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatrixLayout {
+    /// Rows one after another, as in `Matrix4x4::data[row][column]`
+    RowMajor,
+
+    /// Columns one after another, as expected by OpenGL, WGSL and GLSL
+    ColumnMajor,
+}
+
+/// Clip-space depth range produced by projection matrices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DepthRange {
+    /// Near plane at -1, far plane at +1 (OpenGL)
+    NegativeOneToOne,
+
+    /// Near plane at 0, far plane at +1 (wgpu, Vulkan, Metal, Direct3D)
+    ZeroToOne,
+}
+
+/// Conventions a `Matrix4x4` follows.
+///
+/// Every matrix built in this crate uses column vectors (`M * v`, so
+/// `A * B` applies `B` first), a right-handed view space looking down -Z,
+/// and is stored as `data[row][column]`: `MatrixConvention::NATIVE`. The
+/// other conventions describe what graphics APIs expect, for use with
+/// `Matrix4x4::to_flat` and the projection builders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MatrixConvention {
+    /// Storage order of flattened matrices
+    pub layout: MatrixLayout,
+
+    /// Clip-space depth range of projection matrices
+    pub depth_range: DepthRange,
+}
+
+impl MatrixConvention {
+    /// The layout of `Matrix4x4::data` and the depth range used by `Camera`.
+    pub const NATIVE: Self = Self { layout: MatrixLayout::RowMajor, depth_range: DepthRange::NegativeOneToOne };
+
+    /// OpenGL uniforms.
+    pub const OPENGL: Self = Self { layout: MatrixLayout::ColumnMajor, depth_range: DepthRange::NegativeOneToOne };
+
+    /// wgpu uniforms, which also matches Vulkan and Metal.
+    pub const WGPU: Self = Self { layout: MatrixLayout::ColumnMajor, depth_range: DepthRange::ZeroToOne };
+}
+
+impl Default for MatrixConvention {
+    fn default() -> Self {
+        Self::NATIVE
+    }
+}
+
+impl<T: FloatingPoint> Matrix4x4<T> {
+    /// Translation by `offset`.
+    pub fn from_translation(offset: Vector3<T>) -> Self {
+        let mut data = identity();
+        data[0][3] = offset.x;
+        data[1][3] = offset.y;
+        data[2][3] = offset.z;
+        Matrix4x4 { data }
+    }
+
+    /// Non-uniform scale along the coordinate axes.
+    pub fn from_scale(scale: Vector3<T>) -> Self {
+        let mut data = identity();
+        data[0][0] = scale.x;
+        data[1][1] = scale.y;
+        data[2][2] = scale.z;
+        Matrix4x4 { data }
+    }
+
+    /// Rotation by `angle` radians counter-clockwise about `axis`.
+    ///
+    /// The axis does not need to be normalized.
+    pub fn from_axis_angle(axis: Vector3<T>, angle: T) -> Self {
+        Quaternion::from_axis_angle(axis, angle).to_matrix4()
+    }
+
+    /// Rotation by a quaternion, which does not need to be normalized.
+    pub fn from_quaternion(rotation: Quaternion<T>) -> Self {
+        rotation.to_matrix4()
+    }
+
+    /// Scale, then rotate, then translate: `T * R * S`.
+    pub fn from_trs(translation: Vector3<T>, rotation: Quaternion<T>, scale: Vector3<T>) -> Self {
+        let r = rotation.to_matrix3().data;
+        let scale = [scale.x, scale.y, scale.z];
+        let translation = [translation.x, translation.y, translation.z];
+
+        let mut data = identity();
+        for (row, data_row) in data.iter_mut().take(3).enumerate() {
+            for column in 0..3 {
+                data_row[column] = r[row][column] * scale[column];
+            }
+            data_row[3] = translation[row];
+        }
+        Matrix4x4 { data }
+    }
+
+    /// View matrix of a camera at `eye` looking at `target`.
+    ///
+    /// Maps world space into a right-handed view space looking down -Z with
+    /// `up` projected onto +Y. A target at the eye looks down -Z, and an up
+    /// vector parallel to the viewing direction falls back to +Z.
+    pub fn look_at(eye: Vector3<T>, target: Vector3<T>, up: Vector3<T>) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let forward = unit(target - eye).unwrap_or(Vector3::new(zero, zero, zero - one));
+        let right = unit(forward.cross(&up))
+            .or_else(|| unit(forward.cross(&Vector3::new(zero, zero, one))))
+            .unwrap_or(Vector3::new(one, zero, zero));
+        let true_up = right.cross(&forward);
+
+        Matrix4x4 {
+            data: [
+                [right.x, right.y, right.z, zero - right.dot(&eye)],
+                [true_up.x, true_up.y, true_up.z, zero - true_up.dot(&eye)],
+                [zero - forward.x, zero - forward.y, zero - forward.z, forward.dot(&eye)],
+                [zero, zero, zero, one],
+            ],
+        }
+    }
+
+    /// Perspective projection with vertical field of view `fov_y` in radians.
+    ///
+    /// `aspect_ratio` is width over height; `near` and `far` are positive
+    /// distances mapped onto `depth_range`.
+    pub fn perspective(fov_y: T, aspect_ratio: T, near: T, far: T, depth_range: DepthRange) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let half_fov = fov_y * T::from_f64(0.5);
+        let focal = half_fov.cos() / half_fov.sin();
+        let depth = near - far;
+        let (z_scale, z_offset) = match depth_range {
+            DepthRange::NegativeOneToOne => ((far + near) / depth, T::from_f64(2.0) * far * near / depth),
+            DepthRange::ZeroToOne => (far / depth, far * near / depth),
+        };
+
+        Matrix4x4 {
+            data: [
+                [focal / aspect_ratio, zero, zero, zero],
+                [zero, focal, zero, zero],
+                [zero, zero, z_scale, z_offset],
+                [zero, zero, zero - one, zero],
+            ],
+        }
+    }
+
+    /// Orthographic projection of the view-space box between the given planes.
+    ///
+    /// `near` and `far` are distances along -Z mapped onto `depth_range`.
+    pub fn orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T, depth_range: DepthRange) -> Self {
+        let (zero, one, two) = (T::zero(), T::one(), T::from_f64(2.0));
+        let (width, height, depth) = (right - left, top - bottom, far - near);
+        let (z_scale, z_offset) = match depth_range {
+            DepthRange::NegativeOneToOne => (zero - two / depth, zero - (far + near) / depth),
+            DepthRange::ZeroToOne => (zero - one / depth, zero - near / depth),
+        };
+
+        Matrix4x4 {
+            data: [
+                [two / width, zero, zero, zero - (right + left) / width],
+                [zero, two / height, zero, zero - (top + bottom) / height],
+                [zero, zero, z_scale, z_offset],
+                [zero, zero, zero, one],
+            ],
+        }
+    }
+
+    /// Whether the bottom row is `[0, 0, 0, 1]`, so the matrix has no
+    /// projective part.
+    pub fn is_affine(&self) -> bool {
+        let (zero, one) = (T::zero(), T::one());
+        self.data[3] == [zero, zero, zero, one]
+    }
+
+    /// Transform a point (`w = 1`), so translation applies.
+    ///
+    /// The result is divided by `w` when the matrix is projective and `w` is
+    /// not zero.
+    pub fn transform_point(&self, point: Vector3<T>) -> Vector3<T> {
+        let m = &self.data;
+        let p = [point.x, point.y, point.z];
+        let row = |r: usize| m[r][0] * p[0] + m[r][1] * p[1] + m[r][2] * p[2] + m[r][3];
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+        if w == T::one() || w == T::zero() {
+            Vector3::new(x, y, z)
+        } else {
+            Vector3::new(x / w, y / w, z / w)
+        }
+    }
+
+    /// Transform a direction (`w = 0`), so translation is ignored.
+    ///
+    /// Surface normals need `normal_matrix` instead whenever the scale is
+    /// not uniform.
+    pub fn transform_vector(&self, direction: Vector3<T>) -> Vector3<T> {
+        self.linear_part() * direction
+    }
+
+    /// The upper-left 3x3 block: rotation, scale and shear without translation.
+    pub fn linear_part(&self) -> Matrix3x3<T> {
+        let m = &self.data;
+        Matrix3x3::new(core::array::from_fn(|row| [m[row][0], m[row][1], m[row][2]]))
+    }
+
+    /// Inverse transpose of the linear part, which keeps transformed normals
+    /// perpendicular to transformed surfaces. `None` if the linear part is singular.
+    pub fn normal_matrix(&self) -> Option<Matrix3x3<T>> {
+        self.linear_part().inverse().map(|inverse| inverse.transpose())
+    }
+
+    /// Split an affine matrix into translation, rotation and scale, the
+    /// inverse of `from_trs`.
+    ///
+    /// A mirrored matrix gets a negative x scale. Shear cannot be represented,
+    /// so the result is only exact for matrices without it. `None` if the
+    /// matrix is projective or a scale is zero.
+    pub fn decompose(&self) -> Option<(Vector3<T>, Quaternion<T>, Vector3<T>)> {
+        if !self.is_affine() {
+            return None;
+        }
+        let m = &self.data;
+        let translation = Vector3::new(m[0][3], m[1][3], m[2][3]);
+
+        let mut columns: [Vector3<T>; 3] = core::array::from_fn(|c| Vector3::new(m[0][c], m[1][c], m[2][c]));
+        let mut scale = columns.map(|column| column.length());
+        let largest = scale.iter().fold(T::zero(), |max, &s| if s > max { s } else { max });
+        if scale.iter().any(|&s| s <= largest * T::epsilon()) {
+            return None;
+        }
+        if self.linear_part().determinant() < T::zero() {
+            scale[0] = T::zero() - scale[0];
+        }
+        for (column, &s) in columns.iter_mut().zip(&scale) {
+            *column /= s;
+        }
+
+        let rotation = Quaternion::from_matrix3(&Matrix3x3::from_columns(
+            columns[0].into(),
+            columns[1].into(),
+            columns[2].into(),
+        ));
+        Some((translation, rotation, Vector3::new(scale[0], scale[1], scale[2])))
+    }
+
+    /// The 16 entries in `layout` order, ready for upload as a uniform.
+    pub fn to_flat(&self, layout: MatrixLayout) -> [T; 16] {
+        let m = &self.data;
+        core::array::from_fn(|i| match layout {
+            MatrixLayout::RowMajor => m[i / 4][i % 4],
+            MatrixLayout::ColumnMajor => m[i % 4][i / 4],
+        })
+    }
+
+    /// Build a matrix from 16 entries stored in `layout` order.
+    pub fn from_flat(values: [T; 16], layout: MatrixLayout) -> Self {
+        Matrix4x4 {
+            data: core::array::from_fn(|row| {
+                core::array::from_fn(|column| match layout {
+                    MatrixLayout::RowMajor => values[row * 4 + column],
+                    MatrixLayout::ColumnMajor => values[column * 4 + row],
+                })
+            }),
+        }
+    }
+}
+
+fn identity<T: FloatingPoint>() -> [[T; 4]; 4] {
+    core::array::from_fn(|i| core::array::from_fn(|j| if i == j { T::one() } else { T::zero() }))
+}
+
+/// `v` normalized, or `None` if it is too short to have a direction.
+fn unit<T: FloatingPoint>(v: Vector3<T>) -> Option<Vector3<T>> {
+    if v.length() > T::epsilon() { v.normalize() } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn assert_vector_close(a: Vector3<f64>, b: Vector3<f64>) {
+        assert!((a - b).length() < 1e-12, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_points_and_directions() {
+        let matrix = Matrix4x4::from_translation(Vector3::new(1.0, 2.0, 3.0))
+            * Matrix4x4::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), FRAC_PI_2)
+            * Matrix4x4::from_scale(Vector3::new(2.0, 1.0, 1.0));
+        let x = Vector3::new(1.0, 0.0, 0.0);
+        assert_vector_close(matrix.transform_point(x), Vector3::new(1.0, 4.0, 3.0));
+        assert_vector_close(matrix.transform_vector(x), Vector3::new(0.0, 2.0, 0.0));
+        assert!(matrix.is_affine());
+    }
+
+    #[test]
+    fn test_decompose_round_trip() {
+        let translation = Vector3::new(-1.0, 0.5, 4.0);
+        let rotation = Quaternion::from_euler(0.3, -0.7, 1.2);
+        let scale = Vector3::new(0.5, 2.0, 3.0);
+        let matrix = Matrix4x4::from_trs(translation, rotation, scale);
+        let expected = Matrix4x4::from_translation(translation)
+            * Matrix4x4::from_quaternion(rotation)
+            * Matrix4x4::from_scale(scale);
+        for (a, b) in matrix.data.iter().flatten().zip(expected.data.iter().flatten()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        let (t, r, s) = matrix.decompose().unwrap();
+        assert_vector_close(t, translation);
+        assert!(r.dot(&rotation).abs() > 1.0 - 1e-12);
+        assert_vector_close(s, scale);
+
+        // A mirror keeps a proper rotation and moves the sign into the scale
+        let mirrored = Matrix4x4::from_scale(Vector3::new(1.0, -1.0, 1.0));
+        let (_, r, s) = mirrored.decompose().unwrap();
+        assert_vector_close(Matrix4x4::from_trs(Vector3::new(0.0, 0.0, 0.0), r, s).transform_vector(Vector3::new(1.0, 1.0, 1.0)), Vector3::new(1.0, -1.0, 1.0));
+
+        assert!(Matrix4x4::from_scale(Vector3::new(1.0, 0.0, 1.0)).decompose().is_none());
+        assert!(Matrix4x4::perspective(1.0, 1.0, 0.1, 10.0, DepthRange::ZeroToOne).decompose().is_none());
+    }
+
+    #[test]
+    fn test_normal_matrix() {
+        // Stretching a 45 degree surface along x tilts its normal towards y
+        let matrix = Matrix4x4::from_scale(Vector3::new(2.0, 1.0, 1.0));
+        let tangent = Vector3::new(1.0, -1.0, 0.0);
+        let normal = Vector3::new(1.0, 1.0, 0.0);
+        let transformed_normal = matrix.normal_matrix().unwrap() * normal;
+        assert!(matrix.transform_vector(tangent).dot(&transformed_normal).abs() < 1e-12);
+        assert!(matrix.transform_vector(tangent).dot(&matrix.transform_vector(normal)).abs() > 1.0);
+
+        assert!(Matrix4x4::from_scale(Vector3::new(0.0, 1.0, 1.0)).normal_matrix().is_none());
+    }
+
+    #[test]
+    fn test_look_at_and_projections() {
+        let eye = Vector3::new(0.0, 0.0, 5.0);
+        let view = Matrix4x4::look_at(eye, Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+        assert_vector_close(view.transform_point(Vector3::new(0.0, 0.0, 0.0)), Vector3::new(0.0, 0.0, -5.0));
+        // Degenerate up vector still gives an orthonormal frame
+        let view = Matrix4x4::look_at(eye, Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 1.0));
+        assert!((view.linear_part().determinant() - 1.0).abs() < 1e-12);
+
+        let (near, far) = (0.5, 20.0);
+        for (depth_range, near_depth) in [(DepthRange::NegativeOneToOne, -1.0), (DepthRange::ZeroToOne, 0.0)] {
+            let perspective = Matrix4x4::perspective(FRAC_PI_2, 2.0, near, far, depth_range);
+            let on_near = perspective.transform_point(Vector3::new(0.0, near, -near));
+            assert_vector_close(on_near, Vector3::new(0.0, 1.0, near_depth));
+            assert!((perspective.transform_point(Vector3::new(0.0, 0.0, -far)).z - 1.0).abs() < 1e-12);
+
+            let orthographic = Matrix4x4::orthographic(-2.0, 2.0, -1.0, 1.0, near, far, depth_range);
+            assert_vector_close(orthographic.transform_point(Vector3::new(2.0, -1.0, -near)), Vector3::new(1.0, -1.0, near_depth));
+            assert!((orthographic.transform_point(Vector3::new(0.0, 0.0, -far)).z - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_flat_layouts() {
+        let matrix = Matrix4x4::from_translation(Vector3::new(1.0f32, 2.0, 3.0));
+        let column_major = matrix.to_flat(MatrixConvention::WGPU.layout);
+        assert_eq!(&column_major[12..15], &[1.0, 2.0, 3.0]);
+        assert_eq!(matrix.to_flat(MatrixConvention::NATIVE.layout)[3], 1.0);
+        assert_eq!(Matrix4x4::from_flat(column_major, MatrixLayout::ColumnMajor), matrix);
+    }
+}
@@ -0,0 +1,853 @@
+// src/numerics/types/decomposition.rs
+// Matrix decompositions: LU with partial pivoting, Householder QR, Cholesky,
+// Jacobi symmetric eigen-decomposition and SVD.
+// They work on square `[[T; N]; N]` arrays; Matrix3x3 and Matrix4x4 expose
+// them as methods. Each decomposition reports how well conditioned the input
+// was, so callers can reject results that rounding has made meaningless.
+
+#![allow(dead_code)]
+
+use core::cmp::Ordering;
+use super::matrix::{Matrix3x3, Matrix4x4};
+use super::traits::FloatingPoint;
+
+/// Maximum number of sweeps for the Jacobi eigen-decomposition and SVD.
+const MAX_JACOBI_SWEEPS: usize = 64;
+
+/// LU decomposition with partial pivoting, `P A = L U`.
+///
+/// `L` is unit lower triangular and `U` upper triangular. A pivot at or below
+/// `N * epsilon` times the largest magnitude in its column of `A` counts as
+/// zero, so nearly singular matrices are reported as singular instead of
+/// producing huge, meaningless solutions. The test is relative to each
+/// column, so columns of very different scale (an affine transform with a
+/// tiny scale and a large translation) do not mask each other.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lu<T: FloatingPoint, const N: usize> {
+    /// `L` below the diagonal (its unit diagonal is implied), `U` on and above it
+    factors: [[T; N]; N],
+
+    /// Row `i` of `P A` is row `permutation[i]` of `A`
+    permutation: [usize; N],
+
+    /// Determinant of `P`: one for an even number of row swaps, minus one otherwise
+    sign: T,
+
+    /// 1-norm of the decomposed matrix
+    norm: T,
+
+    /// Pivot `k` is treated as zero at or below `tolerances[k]`
+    tolerances: [T; N],
+}
+
+impl<T: FloatingPoint, const N: usize> Lu<T, N> {
+    /// Decompose `matrix`.
+    pub fn new(matrix: [[T; N]; N]) -> Self {
+        let mut factors = matrix;
+        let mut permutation: [usize; N] = core::array::from_fn(|i| i);
+        let mut sign = T::one();
+
+        for k in 0..N {
+            let pivot_row = (k..N)
+                .max_by(|&i, &j| compare_magnitude(factors[i][k], factors[j][k]))
+                .unwrap_or(k);
+            if pivot_row != k {
+                factors.swap(pivot_row, k);
+                permutation.swap(pivot_row, k);
+                sign = T::zero() - sign;
+            }
+
+            let pivot = factors[k][k];
+            if pivot == T::zero() {
+                continue;
+            }
+            let (upper_rows, lower_rows) = factors.split_at_mut(k + 1);
+            let pivot_row = &upper_rows[k];
+            for row in lower_rows {
+                let factor = row[k] / pivot;
+                row[k] = factor;
+                for (value, &above) in row.iter_mut().zip(pivot_row).skip(k + 1) {
+                    *value = *value - factor * above;
+                }
+            }
+        }
+
+        Self {
+            factors,
+            permutation,
+            sign,
+            norm: norm1(&matrix),
+            tolerances: core::array::from_fn(|j| {
+                let column_max = matrix.iter().fold(T::zero(), |max, row| if row[j].abs() > max { row[j].abs() } else { max });
+                tolerance::<T, N>(column_max)
+            }),
+        }
+    }
+
+    /// The unit lower triangular factor `L`.
+    pub fn lower(&self) -> [[T; N]; N] {
+        core::array::from_fn(|i| {
+            core::array::from_fn(|j| match i.cmp(&j) {
+                Ordering::Greater => self.factors[i][j],
+                Ordering::Equal => T::one(),
+                Ordering::Less => T::zero(),
+            })
+        })
+    }
+
+    /// The upper triangular factor `U`.
+    pub fn upper(&self) -> [[T; N]; N] {
+        core::array::from_fn(|i| core::array::from_fn(|j| if j >= i { self.factors[i][j] } else { T::zero() }))
+    }
+
+    /// Row permutation: row `i` of `P A` is row `permutation()[i]` of `A`.
+    pub fn permutation(&self) -> [usize; N] {
+        self.permutation
+    }
+
+    /// Whether any pivot is too small to divide by safely.
+    pub fn is_singular(&self) -> bool {
+        (0..N).any(|k| self.factors[k][k].abs() <= self.tolerances[k])
+    }
+
+    /// Determinant of the decomposed matrix.
+    pub fn determinant(&self) -> T {
+        (0..N).fold(self.sign, |det, k| det * self.factors[k][k])
+    }
+
+    /// Solve `A x = b`, or `None` if the matrix is singular.
+    pub fn solve(&self, b: [T; N]) -> Option<[T; N]> {
+        if self.is_singular() {
+            return None;
+        }
+
+        // L y = P b
+        let mut x: [T; N] = core::array::from_fn(|i| b[self.permutation[i]]);
+        for i in 0..N {
+            for j in 0..i {
+                x[i] = x[i] - self.factors[i][j] * x[j];
+            }
+        }
+        // U x = y
+        for i in (0..N).rev() {
+            for j in i + 1..N {
+                x[i] = x[i] - self.factors[i][j] * x[j];
+            }
+            x[i] = x[i] / self.factors[i][i];
+        }
+        Some(x)
+    }
+
+    /// Inverse of the decomposed matrix, or `None` if it is singular.
+    pub fn inverse(&self) -> Option<[[T; N]; N]> {
+        let mut columns = [[T::zero(); N]; N];
+        for (i, column) in columns.iter_mut().enumerate() {
+            *column = self.solve(unit_vector(i))?;
+        }
+        Some(transpose(&columns))
+    }
+
+    /// Condition number in the 1-norm, `|A| |A^-1|`; infinite when singular.
+    ///
+    /// Roughly `10^k` means the last `k` significant digits of a solution
+    /// cannot be trusted.
+    pub fn condition_number(&self) -> T {
+        match self.inverse() {
+            Some(inverse) => self.norm * norm1(&inverse),
+            None => infinity(),
+        }
+    }
+}
+
+/// QR decomposition by Householder reflections, `A = Q R`.
+///
+/// `Q` is orthogonal and `R` upper triangular. Works for any square matrix,
+/// including singular ones, whose rank shows up as small diagonal entries of `R`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Qr<T: FloatingPoint, const N: usize> {
+    q: [[T; N]; N],
+    r: [[T; N]; N],
+
+    /// Diagonal entries of `R` at or below this magnitude count as zero
+    tolerance: T,
+}
+
+impl<T: FloatingPoint, const N: usize> Qr<T, N> {
+    /// Decompose `matrix`.
+    pub fn new(matrix: [[T; N]; N]) -> Self {
+        let two = T::from_f64(2.0);
+        let mut q = identity::<T, N>();
+        let mut r = matrix;
+
+        for k in 0..N.saturating_sub(1) {
+            // Householder vector v mapping column k below the diagonal onto e_k
+            let length = (k..N).fold(T::zero(), |sum, i| sum + r[i][k] * r[i][k]).sqrt();
+            if length == T::zero() {
+                continue;
+            }
+            let alpha = if r[k][k] < T::zero() { length } else { T::zero() - length };
+            let mut v = [T::zero(); N];
+            for i in k..N {
+                v[i] = r[i][k];
+            }
+            v[k] = v[k] - alpha;
+            let v_squared = (k..N).fold(T::zero(), |sum, i| sum + v[i] * v[i]);
+            if v_squared == T::zero() {
+                continue;
+            }
+
+            // R = H R and Q = Q H with H = I - 2 v v^T / (v^T v)
+            let scales: [T; N] =
+                core::array::from_fn(|j| two * (k..N).fold(T::zero(), |sum, i| sum + v[i] * r[i][j]) / v_squared);
+            for (row, &vi) in r.iter_mut().zip(&v).skip(k) {
+                for (value, &scale) in row.iter_mut().zip(&scales) {
+                    *value = *value - scale * vi;
+                }
+            }
+            for row in q.iter_mut() {
+                let dot = (k..N).fold(T::zero(), |sum, i| sum + row[i] * v[i]);
+                let scale = two * dot / v_squared;
+                for i in k..N {
+                    row[i] = row[i] - scale * v[i];
+                }
+            }
+            for row in r.iter_mut().skip(k + 1) {
+                row[k] = T::zero();
+            }
+        }
+
+        Self { q, r, tolerance: tolerance::<T, N>(max_abs(&matrix)) }
+    }
+
+    /// The orthogonal factor `Q`.
+    pub fn q(&self) -> [[T; N]; N] {
+        self.q
+    }
+
+    /// The upper triangular factor `R`.
+    pub fn r(&self) -> [[T; N]; N] {
+        self.r
+    }
+
+    /// Number of diagonal entries of `R` above the rank tolerance.
+    pub fn rank(&self) -> usize {
+        (0..N).filter(|&i| self.r[i][i].abs() > self.tolerance).count()
+    }
+
+    /// Whether the matrix is rank deficient.
+    pub fn is_singular(&self) -> bool {
+        self.rank() < N
+    }
+
+    /// Solve `A x = b`, or `None` if the matrix is singular.
+    pub fn solve(&self, b: [T; N]) -> Option<[T; N]> {
+        if self.is_singular() {
+            return None;
+        }
+        // R x = Q^T b
+        let mut x: [T; N] = core::array::from_fn(|i| (0..N).fold(T::zero(), |sum, k| sum + self.q[k][i] * b[k]));
+        for i in (0..N).rev() {
+            for j in i + 1..N {
+                x[i] = x[i] - self.r[i][j] * x[j];
+            }
+            x[i] = x[i] / self.r[i][i];
+        }
+        Some(x)
+    }
+
+    /// Ratio of the largest to the smallest diagonal magnitude of `R`.
+    ///
+    /// A cheap lower bound on the 2-norm condition number; infinite when
+    /// the matrix is singular.
+    pub fn condition_estimate(&self) -> T {
+        diagonal_ratio(&self.r, self.tolerance)
+    }
+}
+
+/// Cholesky decomposition of a symmetric positive definite matrix, `A = L L^T`.
+///
+/// Only the lower triangle of the input is read. Decomposition fails if the
+/// matrix is not numerically positive definite, which makes it the standard
+/// test for a valid covariance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cholesky<T: FloatingPoint, const N: usize> {
+    lower: [[T; N]; N],
+}
+
+impl<T: FloatingPoint, const N: usize> Cholesky<T, N> {
+    /// Decompose `matrix`, failing if it is not positive definite.
+    pub fn new(matrix: [[T; N]; N]) -> Result<Self, String> {
+        let diagonal_max = (0..N).fold(T::zero(), |max, i| if matrix[i][i] > max { matrix[i][i] } else { max });
+        let tolerance = tolerance::<T, N>(diagonal_max);
+        let mut lower = [[T::zero(); N]; N];
+
+        for j in 0..N {
+            let pivot = (0..j).fold(matrix[j][j], |sum, k| sum - lower[j][k] * lower[j][k]);
+            // Also rejects NaN
+            if pivot.partial_cmp(&tolerance) != Some(Ordering::Greater) {
+                return Err(format!("Matrix is not positive definite: pivot {} is {:?}", j, pivot));
+            }
+            let diagonal = pivot.sqrt();
+            lower[j][j] = diagonal;
+            for i in j + 1..N {
+                let sum = (0..j).fold(matrix[i][j], |sum, k| sum - lower[i][k] * lower[j][k]);
+                lower[i][j] = sum / diagonal;
+            }
+        }
+
+        Ok(Self { lower })
+    }
+
+    /// The lower triangular factor `L`.
+    pub fn lower(&self) -> [[T; N]; N] {
+        self.lower
+    }
+
+    /// Determinant of the decomposed matrix.
+    pub fn determinant(&self) -> T {
+        (0..N).fold(T::one(), |det, i| det * self.lower[i][i] * self.lower[i][i])
+    }
+
+    /// Solve `A x = b`.
+    pub fn solve(&self, b: [T; N]) -> [T; N] {
+        let l = &self.lower;
+        // L y = b
+        let mut x = b;
+        for i in 0..N {
+            for j in 0..i {
+                x[i] = x[i] - l[i][j] * x[j];
+            }
+            x[i] = x[i] / l[i][i];
+        }
+        // L^T x = y
+        for i in (0..N).rev() {
+            for j in i + 1..N {
+                x[i] = x[i] - l[j][i] * x[j];
+            }
+            x[i] = x[i] / l[i][i];
+        }
+        x
+    }
+
+    /// Inverse of the decomposed matrix.
+    pub fn inverse(&self) -> [[T; N]; N] {
+        transpose(&core::array::from_fn(|i| self.solve(unit_vector(i))))
+    }
+
+    /// Square of the ratio of the largest to the smallest diagonal entry of
+    /// `L`: a cheap lower bound on the 2-norm condition number.
+    pub fn condition_estimate(&self) -> T {
+        let ratio = diagonal_ratio(&self.lower, T::zero());
+        ratio * ratio
+    }
+}
+
+/// Eigen-decomposition of a symmetric matrix by cyclic Jacobi rotations,
+/// `A = V diag(eigenvalues) V^T`.
+///
+/// Jacobi is slower than QR iteration but computes small eigenvalues to
+/// high relative accuracy, which matters for flat splat covariances.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymmetricEigen<T: FloatingPoint, const N: usize> {
+    /// Eigenvalues in descending order
+    pub eigenvalues: [T; N],
+
+    /// Unit eigenvectors as columns, matching `eigenvalues`
+    pub eigenvectors: [[T; N]; N],
+}
+
+impl<T: FloatingPoint, const N: usize> SymmetricEigen<T, N> {
+    /// Decompose `matrix`. The input is symmetrized first, so small
+    /// asymmetries from rounding are averaged out.
+    pub fn new(matrix: [[T; N]; N]) -> Self {
+        let half = T::from_f64(0.5);
+        let mut a: [[T; N]; N] = core::array::from_fn(|i| core::array::from_fn(|j| (matrix[i][j] + matrix[j][i]) * half));
+        let mut v = identity::<T, N>();
+
+        for _ in 0..MAX_JACOBI_SWEEPS {
+            let mut off_diagonal = T::zero();
+            let mut scale = T::zero();
+            for (i, row) in a.iter().enumerate() {
+                scale = scale + row[i].abs();
+                off_diagonal = row.iter().skip(i + 1).fold(off_diagonal, |sum, x| sum + x.abs());
+            }
+            if off_diagonal <= T::epsilon() * scale {
+                break;
+            }
+
+            for p in 0..N {
+                for q in p + 1..N {
+                    if a[p][q] == T::zero() {
+                        continue;
+                    }
+                    let (c, s) = jacobi_rotation(a[p][p], a[q][q], a[p][q]);
+
+                    // A' = J^T A J for the rotation J in the (p, q) plane
+                    for row in a.iter_mut() {
+                        let (akp, akq) = (row[p], row[q]);
+                        row[p] = c * akp - s * akq;
+                        row[q] = s * akp + c * akq;
+                    }
+                    let (row_p, row_q) = (a[p], a[q]);
+                    a[p] = core::array::from_fn(|k| c * row_p[k] - s * row_q[k]);
+                    a[q] = core::array::from_fn(|k| s * row_p[k] + c * row_q[k]);
+                    for row in v.iter_mut() {
+                        let (vkp, vkq) = (row[p], row[q]);
+                        row[p] = c * vkp - s * vkq;
+                        row[q] = s * vkp + c * vkq;
+                    }
+                }
+            }
+        }
+
+        let mut order: [usize; N] = core::array::from_fn(|i| i);
+        order.sort_by(|&i, &j| a[j][j].partial_cmp(&a[i][i]).unwrap_or(Ordering::Equal));
+        Self {
+            eigenvalues: order.map(|i| a[i][i]),
+            eigenvectors: core::array::from_fn(|row| order.map(|i| v[row][i])),
+        }
+    }
+
+    /// Ratio of the largest to the smallest eigenvalue magnitude, which is the
+    /// 2-norm condition number; infinite when an eigenvalue is zero.
+    pub fn condition_number(&self) -> T {
+        magnitude_ratio(&self.eigenvalues)
+    }
+
+    /// Rebuild `V diag(eigenvalues) V^T`.
+    pub fn reconstruct(&self) -> [[T; N]; N] {
+        let v = &self.eigenvectors;
+        core::array::from_fn(|i| {
+            core::array::from_fn(|j| (0..N).fold(T::zero(), |sum, k| sum + v[i][k] * self.eigenvalues[k] * v[j][k]))
+        })
+    }
+}
+
+/// Singular value decomposition by one-sided Jacobi rotations, `A = U S V^T`.
+///
+/// `U` and `V` are orthogonal and the singular values are non-negative and
+/// sorted in descending order. Rank-deficient inputs get `U` columns that
+/// complete an orthonormal basis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Svd<T: FloatingPoint, const N: usize> {
+    /// Left singular vectors as columns
+    pub u: [[T; N]; N],
+
+    /// Singular values in descending order
+    pub singular_values: [T; N],
+
+    /// Right singular vectors as columns
+    pub v: [[T; N]; N],
+}
+
+impl<T: FloatingPoint, const N: usize> Svd<T, N> {
+    /// Decompose `matrix`.
+    pub fn new(matrix: [[T; N]; N]) -> Self {
+        let mut u = matrix;
+        let mut v = identity::<T, N>();
+
+        // Rotate column pairs until all columns of A V are mutually orthogonal
+        for _ in 0..MAX_JACOBI_SWEEPS {
+            let mut rotated = false;
+            for p in 0..N {
+                for q in p + 1..N {
+                    let (mut alpha, mut beta, mut gamma) = (T::zero(), T::zero(), T::zero());
+                    for row in u.iter() {
+                        alpha = alpha + row[p] * row[p];
+                        beta = beta + row[q] * row[q];
+                        gamma = gamma + row[p] * row[q];
+                    }
+                    if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+
+                    let (c, s) = jacobi_rotation(alpha, beta, gamma);
+                    for matrix in [&mut u, &mut v] {
+                        for row in matrix.iter_mut() {
+                            let (xp, xq) = (row[p], row[q]);
+                            row[p] = c * xp - s * xq;
+                            row[q] = s * xp + c * xq;
+                        }
+                    }
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+
+        let norms: [T; N] = core::array::from_fn(|j| (0..N).fold(T::zero(), |sum, i| sum + u[i][j] * u[i][j]).sqrt());
+        let mut order: [usize; N] = core::array::from_fn(|i| i);
+        order.sort_by(|&i, &j| norms[j].partial_cmp(&norms[i]).unwrap_or(Ordering::Equal));
+        let singular_values = order.map(|j| norms[j]);
+        let v = core::array::from_fn(|row| order.map(|j| v[row][j]));
+
+        // Normalize A V to get U; columns of zero singular values complete the basis
+        let tolerance = tolerance::<T, N>(singular_values.first().copied().unwrap_or(T::zero()));
+        let mut columns = [[T::zero(); N]; N];
+        let mut defined = [false; N];
+        for (column, &j) in order.iter().enumerate() {
+            if norms[j] > tolerance {
+                columns[column] = core::array::from_fn(|i| u[i][j] / norms[j]);
+                defined[column] = true;
+            }
+        }
+        for column in 0..N {
+            if !defined[column] {
+                columns[column] = complete_basis(&columns, &defined);
+                defined[column] = true;
+            }
+        }
+
+        Self {
+            u: core::array::from_fn(|row| core::array::from_fn(|column| columns[column][row])),
+            singular_values,
+            v,
+        }
+    }
+
+    /// Ratio of the largest to the smallest singular value, the 2-norm
+    /// condition number; infinite when the matrix is singular.
+    pub fn condition_number(&self) -> T {
+        magnitude_ratio(&self.singular_values)
+    }
+
+    /// Number of singular values above `N * epsilon * largest`.
+    pub fn rank(&self) -> usize {
+        let tolerance = tolerance::<T, N>(self.singular_values.first().copied().unwrap_or(T::zero()));
+        self.singular_values.iter().filter(|&&value| value > tolerance).count()
+    }
+
+    /// Moore-Penrose pseudo-inverse, `V S^+ U^T`, treating singular values
+    /// below the rank tolerance as zero.
+    pub fn pseudo_inverse(&self) -> [[T; N]; N] {
+        let tolerance = tolerance::<T, N>(self.singular_values.first().copied().unwrap_or(T::zero()));
+        let inverted = self.singular_values.map(|value| if value > tolerance { T::one() / value } else { T::zero() });
+        core::array::from_fn(|i| {
+            core::array::from_fn(|j| (0..N).fold(T::zero(), |sum, k| sum + self.v[i][k] * inverted[k] * self.u[j][k]))
+        })
+    }
+
+    /// Rebuild `U S V^T`.
+    pub fn reconstruct(&self) -> [[T; N]; N] {
+        core::array::from_fn(|i| {
+            core::array::from_fn(|j| {
+                (0..N).fold(T::zero(), |sum, k| sum + self.u[i][k] * self.singular_values[k] * self.v[j][k])
+            })
+        })
+    }
+}
+
+impl<T: FloatingPoint> Matrix3x3<T> {
+    /// LU decomposition with partial pivoting.
+    pub fn lu(&self) -> Lu<T, 3> {
+        Lu::new(self.data)
+    }
+
+    /// QR decomposition.
+    pub fn qr(&self) -> Qr<T, 3> {
+        Qr::new(self.data)
+    }
+
+    /// Cholesky decomposition, failing if the matrix is not positive definite.
+    pub fn cholesky(&self) -> Result<Cholesky<T, 3>, String> {
+        Cholesky::new(self.data)
+    }
+
+    /// Eigen-decomposition, treating the matrix as symmetric.
+    pub fn symmetric_eigen(&self) -> SymmetricEigen<T, 3> {
+        SymmetricEigen::new(self.data)
+    }
+
+    /// Singular value decomposition.
+    pub fn svd(&self) -> Svd<T, 3> {
+        Svd::new(self.data)
+    }
+}
+
+impl<T: FloatingPoint> Matrix4x4<T> {
+    /// LU decomposition with partial pivoting.
+    pub fn lu(&self) -> Lu<T, 4> {
+        Lu::new(self.data)
+    }
+
+    /// QR decomposition.
+    pub fn qr(&self) -> Qr<T, 4> {
+        Qr::new(self.data)
+    }
+
+    /// Cholesky decomposition, failing if the matrix is not positive definite.
+    pub fn cholesky(&self) -> Result<Cholesky<T, 4>, String> {
+        Cholesky::new(self.data)
+    }
+
+    /// Eigen-decomposition, treating the matrix as symmetric.
+    pub fn symmetric_eigen(&self) -> SymmetricEigen<T, 4> {
+        SymmetricEigen::new(self.data)
+    }
+
+    /// Singular value decomposition.
+    pub fn svd(&self) -> Svd<T, 4> {
+        Svd::new(self.data)
+    }
+}
+
+/// Cosine and sine of the Jacobi rotation that zeroes the off-diagonal entry
+/// `apq` of the symmetric 2x2 block `[[app, apq], [apq, aqq]]`.
+fn jacobi_rotation<T: FloatingPoint>(app: T, aqq: T, apq: T) -> (T, T) {
+    let one = T::one();
+    let theta = (aqq - app) / (T::from_f64(2.0) * apq);
+    let sign = if theta < T::zero() { T::zero() - one } else { one };
+    let t = sign / (theta.abs() + (theta * theta + one).sqrt());
+    let c = one / (t * t + one).sqrt();
+    (c, t * c)
+}
+
+/// A unit vector orthogonal to every defined column, taken from the standard
+/// basis vector with the largest component left after Gram-Schmidt.
+fn complete_basis<T: FloatingPoint, const N: usize>(columns: &[[T; N]; N], defined: &[bool; N]) -> [T; N] {
+    let mut best = [T::zero(); N];
+    let mut best_length = T::zero();
+    for k in 0..N {
+        let mut candidate: [T; N] = unit_vector(k);
+        for (column, _) in columns.iter().zip(defined).filter(|(_, &defined)| defined) {
+            let projection = (0..N).fold(T::zero(), |sum, i| sum + column[i] * candidate[i]);
+            for i in 0..N {
+                candidate[i] = candidate[i] - projection * column[i];
+            }
+        }
+        let length = candidate.iter().fold(T::zero(), |sum, &x| sum + x * x).sqrt();
+        if length > best_length {
+            best_length = length;
+            best = candidate;
+        }
+    }
+    best.map(|x| x / best_length)
+}
+
+fn identity<T: FloatingPoint, const N: usize>() -> [[T; N]; N] {
+    core::array::from_fn(unit_vector)
+}
+
+fn unit_vector<T: FloatingPoint, const N: usize>(i: usize) -> [T; N] {
+    core::array::from_fn(|j| if i == j { T::one() } else { T::zero() })
+}
+
+fn transpose<T: FloatingPoint, const N: usize>(matrix: &[[T; N]; N]) -> [[T; N]; N] {
+    core::array::from_fn(|i| core::array::from_fn(|j| matrix[j][i]))
+}
+
+fn infinity<T: FloatingPoint>() -> T {
+    T::from_f64(f64::INFINITY)
+}
+
+/// Magnitudes at or below `N * epsilon * scale` are indistinguishable from zero.
+fn tolerance<T: FloatingPoint, const N: usize>(scale: T) -> T {
+    scale * T::from_f64(N as f64) * T::epsilon()
+}
+
+fn compare_magnitude<T: FloatingPoint>(a: T, b: T) -> Ordering {
+    a.abs().partial_cmp(&b.abs()).unwrap_or(Ordering::Equal)
+}
+
+fn max_abs<T: FloatingPoint, const N: usize>(matrix: &[[T; N]; N]) -> T {
+    matrix
+        .iter()
+        .flatten()
+        .fold(T::zero(), |max, &value| if value.abs() > max { value.abs() } else { max })
+}
+
+/// Largest absolute column sum.
+fn norm1<T: FloatingPoint, const N: usize>(matrix: &[[T; N]; N]) -> T {
+    (0..N)
+        .map(|j| matrix.iter().fold(T::zero(), |sum, row| sum + row[j].abs()))
+        .fold(T::zero(), |max, sum| if sum > max { sum } else { max })
+}
+
+/// Largest over smallest diagonal magnitude; infinite if the smallest is at
+/// or below `tolerance`.
+fn diagonal_ratio<T: FloatingPoint, const N: usize>(matrix: &[[T; N]; N], tolerance: T) -> T {
+    let diagonal: [T; N] = core::array::from_fn(|i| matrix[i][i]);
+    let ratio = magnitude_ratio(&diagonal);
+    let smallest = diagonal.iter().fold(infinity::<T>(), |min, &x| if x.abs() < min { x.abs() } else { min });
+    if smallest <= tolerance { infinity() } else { ratio }
+}
+
+fn magnitude_ratio<T: FloatingPoint, const N: usize>(values: &[T; N]) -> T {
+    let (smallest, largest) = values.iter().fold((infinity::<T>(), T::zero()), |(min, max), &x| {
+        let x = x.abs();
+        (if x < min { x } else { min }, if x > max { x } else { max })
+    });
+    if smallest == T::zero() { infinity() } else { largest / smallest }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numerics::types::quaternion::Quaternion;
+    use crate::numerics::types::vector::Vector3;
+
+    fn multiply<const N: usize>(a: &[[f64; N]; N], b: &[[f64; N]; N]) -> [[f64; N]; N] {
+        core::array::from_fn(|i| core::array::from_fn(|j| (0..N).map(|k| a[i][k] * b[k][j]).sum()))
+    }
+
+    fn assert_matrix_close<const N: usize>(a: &[[f64; N]; N], b: &[[f64; N]; N], tolerance: f64) {
+        for i in 0..N {
+            for j in 0..N {
+                assert!((a[i][j] - b[i][j]).abs() < tolerance, "{:?} != {:?}", a, b);
+            }
+        }
+    }
+
+    fn assert_vector_close<const N: usize>(a: &[f64; N], b: &[f64; N], tolerance: f64) {
+        assert!(a.iter().zip(b).all(|(x, y)| (x - y).abs() < tolerance), "{:?} != {:?}", a, b);
+    }
+
+    const GENERAL: [[f64; 4]; 4] = [
+        [0.0, 2.0, -1.0, 3.0],
+        [4.0, 1.0, 0.5, -2.0],
+        [1.0, -3.0, 2.0, 0.0],
+        [2.0, 0.0, 1.0, 1.0],
+    ];
+
+    #[test]
+    fn test_lu_with_pivoting() {
+        // A zero in the top-left corner needs a row swap
+        let lu = Lu::new(GENERAL);
+        assert_ne!(lu.permutation()[0], 0);
+        let permuted: [[f64; 4]; 4] = core::array::from_fn(|i| GENERAL[lu.permutation()[i]]);
+        assert_matrix_close(&multiply(&lu.lower(), &lu.upper()), &permuted, 1e-12);
+
+        let inverse = lu.inverse().unwrap();
+        assert_matrix_close(&multiply(&GENERAL, &inverse), &identity(), 1e-12);
+        assert!((lu.determinant() - Matrix4x4::new(GENERAL).determinant()).abs() < 1e-9);
+
+        let x = lu.solve([1.0, 2.0, 3.0, 4.0]).unwrap();
+        let b: [f64; 4] = core::array::from_fn(|i| (0..4).map(|k| GENERAL[i][k] * x[k]).sum());
+        assert_vector_close(&b, &[1.0, 2.0, 3.0, 4.0], 1e-12);
+        assert!(lu.condition_number() > 1.0 && lu.condition_number() < 1e3);
+    }
+
+    #[test]
+    fn test_lu_detects_near_singular() {
+        // Third row is the sum of the first two, up to rounding
+        let m = [[1.0, 2.0, 3.0], [0.1, 0.2, 0.7], [1.1, 2.2, 3.7]];
+        let lu = Lu::new(m);
+        assert!(lu.is_singular());
+        assert_eq!(lu.solve([1.0, 1.0, 1.0]), None);
+        assert_eq!(lu.condition_number(), f64::INFINITY);
+        assert!(Matrix3x3::new(m).inverse().is_none());
+    }
+
+    #[test]
+    fn test_lu_small_scale_large_translation() {
+        // The pivots of the scale columns are far below epsilon times the
+        // translation, but the matrix is perfectly invertible
+        let m = Matrix4x4::<f32>::from_trs(
+            Vector3::new(1000.0, -1000.0, 500.0),
+            Quaternion::identity(),
+            Vector3::new(1e-4, 1e-4, 1e-4),
+        );
+        let inverse = m.inverse().expect("affine matrix with tiny scale is invertible");
+        let product = m.clone() * inverse.clone();
+        for (entry, expected) in product.data.iter().flatten().zip(Matrix4x4::<f32>::identity().data.iter().flatten()) {
+            assert!((entry - expected).abs() < 1e-3, "{:?}", product);
+        }
+        let origin = inverse.transform_point(Vector3::new(1000.0, -1000.0, 500.0));
+        assert!(origin.length() < 1e-2, "{:?}", origin);
+        assert!((inverse.data[0][0] - 1e4).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_qr() {
+        let qr = Qr::new(GENERAL);
+        let q = qr.q();
+        assert_matrix_close(&multiply(&q, &transpose(&q)), &identity(), 1e-12);
+        assert_matrix_close(&multiply(&q, &qr.r()), &GENERAL, 1e-12);
+        for i in 0..4 {
+            for j in 0..i {
+                assert_eq!(qr.r()[i][j], 0.0);
+            }
+        }
+        assert_eq!(qr.rank(), 4);
+        let x = qr.solve([1.0, 2.0, 3.0, 4.0]).unwrap();
+        let expected = Lu::new(GENERAL).solve([1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_vector_close(&x, &expected, 1e-12);
+
+        let rank_two = Qr::new([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 1.0]]);
+        assert_eq!(rank_two.rank(), 2);
+        assert_eq!(rank_two.condition_estimate(), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let covariance = [[4.0, 2.0, 0.4], [2.0, 5.0, 1.0], [0.4, 1.0, 3.0]];
+        let cholesky = Matrix3x3::new(covariance).cholesky().unwrap();
+        let l = cholesky.lower();
+        assert_matrix_close(&multiply(&l, &transpose(&l)), &covariance, 1e-12);
+        assert!((cholesky.determinant() - Lu::new(covariance).determinant()).abs() < 1e-9);
+        assert_matrix_close(&multiply(&covariance, &cholesky.inverse()), &identity(), 1e-12);
+
+        let eigen = SymmetricEigen::new(covariance);
+        assert!(cholesky.condition_estimate() <= eigen.condition_number() + 1e-12);
+
+        assert!(Cholesky::new([[1.0, 2.0], [2.0, 1.0]]).is_err());
+        assert!(Cholesky::new([[1.0, 1.0], [1.0, 1.0]]).is_err());
+        assert!(Cholesky::new([[f64::NAN, 0.0], [0.0, 1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let m = [[2.0, -1.0, 0.0], [-1.0, 2.0, -1.0], [0.0, -1.0, 2.0]];
+        let eigen = Matrix3x3::new(m).symmetric_eigen();
+        let sqrt2 = 2f64.sqrt();
+        assert_vector_close(&eigen.eigenvalues, &[2.0 + sqrt2, 2.0, 2.0 - sqrt2], 1e-12);
+        let v = eigen.eigenvectors;
+        assert_matrix_close(&multiply(&transpose(&v), &v), &identity(), 1e-12);
+        assert_matrix_close(&eigen.reconstruct(), &m, 1e-12);
+        assert!((eigen.condition_number() - (2.0 + sqrt2) / (2.0 - sqrt2)).abs() < 1e-9);
+
+        // Tiny eigenvalues of a flat covariance keep their relative accuracy
+        let flat = [[1.0, 0.0, 0.0], [0.0, 1e-12, 0.0], [0.0, 0.0, 1e-6]];
+        let eigen = SymmetricEigen::new(flat);
+        assert_eq!(eigen.eigenvalues, [1.0, 1e-6, 1e-12]);
+        assert!((eigen.condition_number() - 1e12).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_svd() {
+        let svd = Matrix4x4::new(GENERAL).svd();
+        assert_matrix_close(&svd.reconstruct(), &GENERAL, 1e-12);
+        assert_matrix_close(&multiply(&transpose(&svd.u), &svd.u), &identity(), 1e-12);
+        assert_matrix_close(&multiply(&transpose(&svd.v), &svd.v), &identity(), 1e-12);
+        assert!(svd.singular_values.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert_eq!(svd.rank(), 4);
+        assert_matrix_close(&svd.pseudo_inverse(), &Lu::new(GENERAL).inverse().unwrap(), 1e-12);
+        assert!(svd.condition_number() > 1.0);
+
+        // Rank-deficient input still gets an orthogonal U
+        let singular = [[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 0.0, 0.0]];
+        let svd = Svd::new(singular);
+        assert_eq!(svd.rank(), 1);
+        assert_eq!(svd.condition_number(), f64::INFINITY);
+        assert_matrix_close(&multiply(&transpose(&svd.u), &svd.u), &identity(), 1e-12);
+        assert_matrix_close(&svd.reconstruct(), &singular, 1e-12);
+        assert!((svd.singular_values[0] - 70f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_f32_decompositions() {
+        let m = Matrix3x3::new([[4.0f32, 1.0, 0.0], [1.0, 3.0, 0.5], [0.0, 0.5, 2.0]]);
+        let product = m * m.inverse().unwrap();
+        for (entry, expected) in product.data.iter().flatten().zip(Matrix3x3::<f32>::identity().data.iter().flatten()) {
+            assert!((entry - expected).abs() < 1e-5);
+        }
+        let values = m.symmetric_eigen().eigenvalues;
+        let singular = m.svd().singular_values;
+        for (value, singular) in values.iter().zip(singular) {
+            assert!((value - singular).abs() < 1e-5);
+        }
+        assert!(m.cholesky().is_ok());
+    }
+}
@@ -0,0 +1,403 @@
+// src/numerics/types/half.rs
+// 16-bit floating point scalars: IEEE 754 binary16 (`F16`) and bfloat16 (`BF16`).
+// Values are stored as raw bits; arithmetic widens to f32 and rounds back.
+
+#![allow(dead_code)]
+
+use super::traits::FloatingPoint;
+use bincode::{Decode, Encode};
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use serde::{Deserialize, Serialize};
+
+/// How a value that is not exactly representable is rounded.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to the nearest value, ties to even (IEEE 754 default)
+    #[default]
+    NearestEven,
+
+    /// Round toward zero (truncate)
+    TowardZero,
+
+    /// Round toward positive infinity
+    TowardPositive,
+
+    /// Round toward negative infinity
+    TowardNegative,
+}
+
+/// Bit layout of a 16-bit binary floating point format.
+struct Format {
+    exponent_bits: u32,
+    mantissa_bits: u32,
+}
+
+const BINARY16: Format = Format { exponent_bits: 5, mantissa_bits: 10 };
+const BFLOAT16: Format = Format { exponent_bits: 8, mantissa_bits: 7 };
+
+impl Format {
+    const fn exponent_mask(&self) -> u16 {
+        (((1u32 << self.exponent_bits) - 1) << self.mantissa_bits) as u16
+    }
+
+    const fn sign_mask(&self) -> u16 {
+        1 << (self.exponent_bits + self.mantissa_bits)
+    }
+
+    /// Encode an f64 with a single rounding step.
+    ///
+    /// Going through f64 (rather than f32) avoids double rounding, and f32
+    /// inputs widen to f64 exactly.
+    fn encode(&self, value: f64, mode: RoundingMode) -> u16 {
+        let bits = value.to_bits();
+        let negative = bits >> 63 != 0;
+        let sign = if negative { self.sign_mask() } else { 0 };
+        let exponent = ((bits >> 52) & 0x7FF) as i64;
+        let mantissa = bits & ((1 << 52) - 1);
+
+        if exponent == 0x7FF {
+            return if mantissa == 0 {
+                sign | self.exponent_mask()
+            } else {
+                // Quiet NaN, keeping the top payload bits
+                let payload = (mantissa >> (52 - self.mantissa_bits)) as u16;
+                sign | self.exponent_mask() | (1 << (self.mantissa_bits - 1)) | payload
+            };
+        }
+        if exponent == 0 && mantissa == 0 {
+            return sign;
+        }
+
+        // value = significand * 2^(unbiased - 52)
+        let (significand, unbiased) = if exponent == 0 {
+            (mantissa, -1022)
+        } else {
+            (mantissa | (1 << 52), exponent - 1023)
+        };
+        let bias = (1i64 << (self.exponent_bits - 1)) - 1;
+        let max_exponent = (1i64 << self.exponent_bits) - 1;
+        let target_exponent = unbiased + bias;
+        if target_exponent >= max_exponent {
+            return self.overflow(sign, negative, mode);
+        }
+
+        // Subnormal results lose one more bit per step below the normal range
+        let shift = (52 - self.mantissa_bits as i64 + (1 - target_exponent).max(0)) as u32;
+        let (truncated, remainder, half) = if shift >= 64 {
+            (0, significand, u64::MAX)
+        } else {
+            (significand >> shift, significand & ((1 << shift) - 1), 1u64 << (shift - 1))
+        };
+        let round_up = match mode {
+            RoundingMode::NearestEven => remainder > half || (remainder == half && truncated & 1 == 1),
+            RoundingMode::TowardZero => false,
+            RoundingMode::TowardPositive => remainder != 0 && !negative,
+            RoundingMode::TowardNegative => remainder != 0 && negative,
+        };
+        let rounded = truncated + round_up as u64;
+
+        // Adding the implicit bit into the exponent field carries correctly on mantissa overflow
+        let magnitude = if target_exponent >= 1 {
+            (((target_exponent - 1) as u64) << self.mantissa_bits) + rounded
+        } else {
+            rounded
+        };
+        if magnitude >= self.exponent_mask() as u64 {
+            return self.overflow(sign, negative, mode);
+        }
+        sign | magnitude as u16
+    }
+
+    /// Result of a value too large for the format: infinity, or the largest
+    /// finite value when rounding away from infinity.
+    fn overflow(&self, sign: u16, negative: bool, mode: RoundingMode) -> u16 {
+        let to_infinity = match mode {
+            RoundingMode::NearestEven => true,
+            RoundingMode::TowardZero => false,
+            RoundingMode::TowardPositive => !negative,
+            RoundingMode::TowardNegative => negative,
+        };
+        if to_infinity {
+            sign | self.exponent_mask()
+        } else {
+            sign | (self.exponent_mask() - 1)
+        }
+    }
+}
+
+macro_rules! half_type {
+    // f32 carries at least 2p + 2 significand bits for both formats (p = 11 and 8),
+    // so rounding the f32 result again equals rounding the exact result once
+    (@binary $name:ident, $trait:ident, $method:ident, $op:tt) => {
+        impl $trait for $name {
+            type Output = Self;
+            fn $method(self, rhs: Self) -> Self {
+                Self::from_f32(self.to_f32() $op rhs.to_f32())
+            }
+        }
+    };
+
+    ($name:ident, $format:expr, $to_f32:expr, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Copy, Clone, Default, Serialize, Deserialize, Encode, Decode)]
+        #[repr(transparent)]
+        pub struct $name(u16);
+
+        impl $name {
+            /// Positive zero.
+            pub const ZERO: Self = Self(0);
+
+            /// Positive infinity.
+            pub const INFINITY: Self = Self($format.exponent_mask());
+
+            /// Negative infinity.
+            pub const NEG_INFINITY: Self = Self($format.exponent_mask() | $format.sign_mask());
+
+            /// A quiet NaN.
+            pub const NAN: Self = Self($format.exponent_mask() | (1 << ($format.mantissa_bits - 1)));
+
+            /// Largest finite value.
+            pub const MAX: Self = Self($format.exponent_mask() - 1);
+
+            /// Smallest positive normal value.
+            pub const MIN_POSITIVE: Self = Self(1 << $format.mantissa_bits);
+
+            /// Create a value from its raw bits.
+            pub const fn from_bits(bits: u16) -> Self {
+                Self(bits)
+            }
+
+            /// The raw bits of this value.
+            pub const fn to_bits(self) -> u16 {
+                self.0
+            }
+
+            /// Convert from f32, rounding to nearest even.
+            pub fn from_f32(value: f32) -> Self {
+                Self::from_f64_rounded(value as f64, RoundingMode::NearestEven)
+            }
+
+            /// Convert from f32 with an explicit rounding mode.
+            pub fn from_f32_rounded(value: f32, mode: RoundingMode) -> Self {
+                Self::from_f64_rounded(value as f64, mode)
+            }
+
+            /// Convert from f64 with an explicit rounding mode.
+            pub fn from_f64_rounded(value: f64, mode: RoundingMode) -> Self {
+                Self($format.encode(value, mode))
+            }
+
+            /// Widen to f32. Every value is exactly representable.
+            pub fn to_f32(self) -> f32 {
+                ($to_f32)(self.0)
+            }
+
+            /// Whether this value is NaN.
+            pub fn is_nan(self) -> bool {
+                (self.0 & !$format.sign_mask()) > $format.exponent_mask()
+            }
+
+            /// Whether this value is neither infinite nor NaN.
+            pub fn is_finite(self) -> bool {
+                (self.0 & $format.exponent_mask()) != $format.exponent_mask()
+            }
+
+            /// Whether the sign bit is set, including for -0.0 and NaN.
+            pub fn is_sign_negative(self) -> bool {
+                (self.0 & $format.sign_mask()) != 0
+            }
+        }
+
+        impl From<$name> for f32 {
+            fn from(value: $name) -> f32 {
+                value.to_f32()
+            }
+        }
+
+        impl From<$name> for f64 {
+            fn from(value: $name) -> f64 {
+                value.to_f32() as f64
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.to_f32() == other.to_f32()
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.to_f32().partial_cmp(&other.to_f32())
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(self.0 ^ $format.sign_mask())
+            }
+        }
+
+        half_type!(@binary $name, Add, add, +);
+        half_type!(@binary $name, Sub, sub, -);
+        half_type!(@binary $name, Mul, mul, *);
+        half_type!(@binary $name, Div, div, /);
+
+        impl FloatingPoint for $name {
+            fn zero() -> Self { Self::ZERO }
+            fn one() -> Self { Self::from_f32(1.0) }
+            fn abs(self) -> Self { Self(self.0 & !$format.sign_mask()) }
+            fn sqrt(self) -> Self { Self::from_f32(self.to_f32().sqrt()) }
+            fn from_f64(value: f64) -> Self { Self::from_f64_rounded(value, RoundingMode::NearestEven) }
+            fn to_f64(self) -> f64 { self.to_f32() as f64 }
+            fn epsilon() -> Self { Self::from_f64(2f64.powi(-($format.mantissa_bits as i32))) }
+        }
+    };
+}
+
+half_type!(
+    F16,
+    BINARY16,
+    f16_bits_to_f32,
+    "IEEE 754 half precision (binary16): 1 sign, 5 exponent and 10 mantissa bits."
+);
+
+half_type!(
+    BF16,
+    BFLOAT16,
+    |bits: u16| f32::from_bits((bits as u32) << 16),
+    "Brain floating point (bfloat16): the upper half of an f32, with 8 exponent and 7 mantissa bits."
+);
+
+fn f16_bits_to_f32(bits: u16) -> f32 {
+    let sign = ((bits & 0x8000) as u32) << 16;
+    let exponent = ((bits >> 10) & 0x1F) as u32;
+    let mantissa = (bits & 0x3FF) as u32;
+
+    let magnitude = match exponent {
+        // Subnormal: mantissa * 2^-24, exact in f32
+        0 => (mantissa as f32 * f32::from_bits(0x3380_0000)).to_bits(),
+        0x1F => 0x7F80_0000 | (mantissa << 13),
+        _ => ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(sign | magnitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f16_conversion() {
+        assert_eq!(F16::from_f32(1.0).to_bits(), 0x3C00);
+        assert_eq!(F16::from_f32(-2.0).to_bits(), 0xC000);
+        assert_eq!(F16::from_f32(65504.0), F16::MAX);
+        assert_eq!(F16::from_f32(0.5).to_f32(), 0.5);
+        assert_eq!(F16::from_f32(2f32.powi(-14)), F16::MIN_POSITIVE);
+
+        // Subnormals and underflow
+        let smallest = F16::from_bits(1);
+        assert_eq!(smallest.to_f32(), 2f32.powi(-24));
+        assert_eq!(F16::from_f32(2f32.powi(-24)), smallest);
+        assert_eq!(F16::from_f32(2f32.powi(-26)).to_bits(), 0);
+        assert_eq!(F16::from_f32(-1e-10).to_bits(), 0x8000);
+
+        // Overflow and special values
+        assert_eq!(F16::from_f32(70000.0), F16::INFINITY);
+        assert_eq!(F16::from_f32(f32::NEG_INFINITY), F16::NEG_INFINITY);
+        assert!(F16::from_f32(f32::NAN).is_nan());
+        assert!(!F16::MAX.is_nan() && F16::MAX.is_finite());
+        assert!(!F16::INFINITY.is_finite());
+
+        // Every finite value round trips through f32
+        for bits in 0..=u16::MAX {
+            let value = F16::from_bits(bits);
+            if !value.is_nan() {
+                assert_eq!(F16::from_f32(value.to_f32()).to_bits(), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bf16_conversion() {
+        assert_eq!(BF16::from_f32(1.0).to_bits(), 0x3F80);
+        assert_eq!(BF16::from_f32(1.0 + 2f32.powi(-8)).to_bits(), 0x3F80);
+        assert_eq!(BF16::from_f32(1.0 + 3.0 * 2f32.powi(-8)).to_bits(), 0x3F82);
+        assert_eq!(BF16::from_f32(f32::MAX), BF16::INFINITY);
+        assert!(BF16::from_f32(f32::NAN).is_nan());
+
+        for bits in 0..=u16::MAX {
+            let value = BF16::from_bits(bits);
+            if !value.is_nan() {
+                assert_eq!(BF16::from_f32(value.to_f32()).to_bits(), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        // 1 + 2^-11 lies exactly between 1 and the next F16 value
+        let tie = 1.0 + 2f32.powi(-11);
+        assert_eq!(F16::from_f32(tie).to_bits(), 0x3C00);
+        // 1 + 3 * 2^-11 ties toward the even neighbour above
+        assert_eq!(F16::from_f32(1.0 + 3.0 * 2f32.powi(-11)).to_bits(), 0x3C02);
+
+        let value = 1.0 + 2f32.powi(-12);
+        assert_eq!(F16::from_f32_rounded(value, RoundingMode::TowardZero).to_bits(), 0x3C00);
+        assert_eq!(F16::from_f32_rounded(value, RoundingMode::TowardPositive).to_bits(), 0x3C01);
+        assert_eq!(F16::from_f32_rounded(-value, RoundingMode::TowardNegative).to_bits(), 0xBC01);
+        assert_eq!(F16::from_f32_rounded(-value, RoundingMode::TowardPositive).to_bits(), 0xBC00);
+
+        // Directed rounding away from infinity saturates at the largest value
+        assert_eq!(F16::from_f32_rounded(1e6, RoundingMode::TowardZero), F16::MAX);
+        assert_eq!(BF16::from_f32_rounded(f32::MAX, RoundingMode::TowardZero), BF16::MAX);
+        assert_eq!(F16::from_f32_rounded(-1e6, RoundingMode::TowardPositive), -F16::MAX);
+
+        // Converting from f64 rounds once: this value would tie after rounding to f32 first
+        let just_above_tie = 1.0 + 2f64.powi(-11) + 2f64.powi(-40);
+        assert_eq!(F16::from_f64(just_above_tie).to_bits(), 0x3C01);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = F16::from_f32(1.5);
+        let b = F16::from_f32(0.25);
+        assert_eq!((a + b).to_f32(), 1.75);
+        assert_eq!((a - b).to_f32(), 1.25);
+        assert_eq!((a * b).to_f32(), 0.375);
+        assert_eq!((a / b).to_f32(), 6.0);
+        assert_eq!((-a).to_f32(), -1.5);
+        assert!(b < a);
+
+        // Results are rounded to the format
+        let third = F16::one() / F16::from_f32(3.0);
+        assert_eq!(third, F16::from_f32(1.0 / 3.0));
+        assert_ne!(third.to_f64(), 1.0 / 3.0);
+
+        let x = BF16::from_f64(9.0);
+        assert_eq!(x.sqrt().to_f64(), 3.0);
+        assert_eq!((-x).abs(), x);
+        assert_eq!(BF16::zero() + BF16::one(), BF16::one());
+        assert_eq!((F16::one() + F16::epsilon()).to_f64(), 1.0 + 2f64.powi(-10));
+        assert_eq!((BF16::one() + BF16::epsilon()).to_f64(), 1.0 + 2f64.powi(-7));
+
+        // -0 equals +0, NaN equals nothing
+        assert_eq!(F16::from_f32(-0.0), F16::ZERO);
+        assert_ne!(F16::NAN, F16::NAN);
+    }
+}
@@ -99,6 +99,25 @@ impl<T: FloatingPoint> Matrix3x3<T> {
             self.data[target][j] = self.data[target][j] + self.data[source][j] * scalar;
         }
     }
+
+    /// Transpose
+    pub fn transpose(&self) -> Self {
+        Self { data: std::array::from_fn(|i| std::array::from_fn(|j| self.data[j][i])) }
+    }
+
+    /// Determinant
+    pub fn determinant(&self) -> T {
+        let m = &self.data;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// Compute the inverse. Returns None if the matrix is singular or too close
+    /// to singular for the result to be meaningful; see `Lu::is_singular`.
+    pub fn inverse(&self) -> Option<Self> {
+        self.lu().inverse().map(|data| Self { data })
+    }
 }
 
 // Generic serde implementations for Matrix3x3
@@ -756,65 +775,15 @@ impl<T: FloatingPoint> IntoIterator for Matrix4x4<T> {
     }
 }
 
-/// --- inverse() via adjugate / determinant
+/// --- inverse() via LU decomposition
 impl<T> Matrix4x4<T>
 where
     T: FloatingPoint + Neg<Output = T> + Div<Output = T> + AddAssign + SubAssign + MulAssign,
 {
-    /// Compute the inverse. Returns None if matrix is singular (determinant == 0).
-    /// Note: numeric stability is basic here; for production consider LU decomposition.
+    /// Compute the inverse. Returns None if the matrix is singular or too close
+    /// to singular for the result to be meaningful; see `Lu::is_singular`.
     pub fn inverse(&self) -> Option<Self> {
-        // compute determinant
-        let det = self.determinant();
-        if det == T::zero() {
-            return None;
-        }
-
-        // compute matrix of cofactors (3x3 minors with sign)
-        let mut cof = [[T::zero(); 4]; 4];
-        for r in 0..4 {
-            for c in 0..4 {
-                // build 3x3 minor for element (r,c)
-                let mut minor = [[T::zero(); 3]; 3];
-                let mut rr = 0;
-                for i in 0..4 {
-                    if i == r { continue; }
-                    let mut cc = 0;
-                    for j in 0..4 {
-                        if j == c { continue; }
-                        minor[rr][cc] = self.data[i][j];
-                        cc += 1;
-                    }
-                    rr += 1;
-                }
-                // determinant of 3x3 minor
-                let m = &minor;
-                let det3 = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
-                    - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
-                    + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
-                // cofactor sign
-                let sign = if ((r + c) % 2) == 0 { T::one() } else { -T::one() };
-                cof[r][c] = sign * det3;
-            }
-        }
-
-        // adjugate = transpose(cofactor matrix)
-        let mut adj = [[T::zero(); 4]; 4];
-        for r in 0..4 {
-            for c in 0..4 {
-                adj[c][r] = cof[r][c];
-            }
-        }
-
-        // divide adjugate by determinant
-        let mut inv = [[T::zero(); 4]; 4];
-        for r in 0..4 {
-            for c in 0..4 {
-                inv[r][c] = adj[r][c] / det;
-            }
-        }
-
-        Some(Matrix4x4::new(inv))
+        self.lu().inverse().map(|data| Matrix4x4 { data })
     }
 }
 
@@ -0,0 +1,501 @@
+// src/numerics/types/quaternion.rs
+// Rotation quaternion generic over the FloatingPoint trait.
+// Components are ordered [w, x, y, z], matching splat and transform rotations.
+
+#![allow(dead_code)]
+
+use core::ops::{Add, Mul, Neg, Sub};
+use serde::{Serialize, Deserialize};
+use bincode::{Encode, Decode};
+use super::matrix::{Matrix3x3, Matrix4x4};
+use super::traits::FloatingPoint;
+use super::vector::Vector3;
+
+/// Dot product above which slerp falls back to nlerp to avoid dividing by a
+/// vanishing sine.
+const SLERP_NLERP_THRESHOLD: f64 = 0.9995;
+
+/// Sine of the pitch beyond which `to_euler` treats the rotation as gimbal
+/// locked and folds all remaining rotation into yaw.
+const GIMBAL_LOCK_THRESHOLD: f64 = 1.0 - 1e-6;
+
+/// Quaternion `w + xi + yj + zk`, used to represent rotations.
+///
+/// Rotation methods expect a unit quaternion; constructors that build
+/// rotations return one, and `normalize` restores unit length after
+/// accumulated rounding. Composition follows the Hamilton product, so
+/// `a * b` applies `b` first and then `a`, like matrix multiplication.
+#[derive(Copy, Clone, Debug, PartialEq, Encode, Decode)]
+pub struct Quaternion<T: FloatingPoint> {
+    pub w: T,
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+pub type Quaternionf = Quaternion<f32>;
+pub type Quaterniond = Quaternion<f64>;
+
+// Conditional impls for serde
+impl<T> Serialize for Quaternion<T>
+where
+    T: FloatingPoint + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (&self.w, &self.x, &self.y, &self.z).serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Quaternion<T>
+where
+    T: FloatingPoint + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let (w, x, y, z) = <(T, T, T, T)>::deserialize(deserializer)?;
+        Ok(Quaternion { w, x, y, z })
+    }
+}
+
+impl<T: FloatingPoint> Quaternion<T> {
+    /// Construct a quaternion from its components.
+    pub fn new(w: T, x: T, y: T, z: T) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// The identity rotation.
+    pub fn identity() -> Self {
+        Self::new(T::one(), T::zero(), T::zero(), T::zero())
+    }
+
+    /// Rotation by `angle` radians around `axis`, counter-clockwise when
+    /// looking down the axis towards the origin.
+    ///
+    /// The axis does not need to be normalized. A zero axis gives the identity.
+    pub fn from_axis_angle(axis: Vector3<T>, angle: T) -> Self {
+        let length = axis.length();
+        if length == T::zero() {
+            return Self::identity();
+        }
+        let half = angle * T::from_f64(0.5);
+        let scale = half.sin() / length;
+        Self::new(half.cos(), axis.x * scale, axis.y * scale, axis.z * scale)
+    }
+
+    /// Rotation from Euler angles in radians.
+    ///
+    /// Rotates by `roll` around X, then `pitch` around Y, then `yaw` around Z,
+    /// all about the fixed axes (equivalently `Rz(yaw) * Ry(pitch) * Rx(roll)`).
+    pub fn from_euler(roll: T, pitch: T, yaw: T) -> Self {
+        let half = T::from_f64(0.5);
+        let (sr, cr) = ((roll * half).sin(), (roll * half).cos());
+        let (sp, cp) = ((pitch * half).sin(), (pitch * half).cos());
+        let (sy, cy) = ((yaw * half).sin(), (yaw * half).cos());
+        Self::new(
+            cr * cp * cy + sr * sp * sy,
+            sr * cp * cy - cr * sp * sy,
+            cr * sp * cy + sr * cp * sy,
+            cr * cp * sy - sr * sp * cy,
+        )
+    }
+
+    /// Euler angles `(roll, pitch, yaw)` in radians, inverting `from_euler`.
+    ///
+    /// Pitch is in `[-pi/2, pi/2]`. At those limits roll and yaw share one
+    /// degree of freedom; roll is then reported as zero.
+    pub fn to_euler(&self) -> (T, T, T) {
+        let (w, x, y, z) = (self.w, self.x, self.y, self.z);
+        let (zero, one, two) = (T::zero(), T::one(), T::from_f64(2.0));
+
+        let sin_pitch = two * (w * y - z * x);
+        let lock = T::from_f64(GIMBAL_LOCK_THRESHOLD);
+        if sin_pitch >= lock {
+            return (zero, T::from_f64(core::f64::consts::FRAC_PI_2), zero - two * x.atan2(w));
+        }
+        if sin_pitch <= zero - lock {
+            return (zero, T::from_f64(-core::f64::consts::FRAC_PI_2), two * x.atan2(w));
+        }
+
+        let roll = (two * (w * x + y * z)).atan2(one - two * (x * x + y * y));
+        let pitch = sin_pitch.atan2((one - sin_pitch * sin_pitch).sqrt());
+        let yaw = (two * (w * z + x * y)).atan2(one - two * (y * y + z * z));
+        (roll, pitch, yaw)
+    }
+
+    /// Rotation axis and angle in radians, with the angle in `[0, 2pi]`.
+    ///
+    /// The identity has no unique axis and reports the X axis.
+    pub fn to_axis_angle(&self) -> (Vector3<T>, T) {
+        let q = self.normalize().unwrap_or_else(Self::identity);
+        let sin_half = (q.x * q.x + q.y * q.y + q.z * q.z).sqrt();
+        let angle = T::from_f64(2.0) * sin_half.atan2(q.w);
+        if sin_half == T::zero() {
+            return (Vector3::new(T::one(), T::zero(), T::zero()), angle);
+        }
+        (Vector3::new(q.x / sin_half, q.y / sin_half, q.z / sin_half), angle)
+    }
+
+    /// Dot product of the components.
+    pub fn dot(&self, other: &Self) -> T {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Squared norm.
+    pub fn norm_squared(&self) -> T {
+        self.dot(self)
+    }
+
+    /// Norm (length).
+    pub fn norm(&self) -> T {
+        self.norm_squared().sqrt()
+    }
+
+    /// Unit quaternion in the same direction, or `None` for the zero quaternion.
+    pub fn normalize(&self) -> Option<Self> {
+        let norm = self.norm();
+        if norm == T::zero() {
+            return None;
+        }
+        Some(*self * (T::one() / norm))
+    }
+
+    /// Conjugate `w - xi - yj - zk`; the inverse rotation of a unit quaternion.
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, T::zero() - self.x, T::zero() - self.y, T::zero() - self.z)
+    }
+
+    /// Multiplicative inverse, or `None` for the zero quaternion.
+    pub fn inverse(&self) -> Option<Self> {
+        let norm_squared = self.norm_squared();
+        if norm_squared == T::zero() {
+            return None;
+        }
+        Some(self.conjugate() * (T::one() / norm_squared))
+    }
+
+    /// Rotate a vector by this unit quaternion.
+    pub fn rotate_vector(&self, v: Vector3<T>) -> Vector3<T> {
+        // v' = v + 2w (u x v) + 2 u x (u x v), with u the vector part
+        let u = Vector3::new(self.x, self.y, self.z);
+        let t = u.cross(&v) * T::from_f64(2.0);
+        v + t * self.w + u.cross(&t)
+    }
+
+    /// Normalized linear interpolation along the shorter arc.
+    ///
+    /// Cheaper than `slerp` but does not rotate at constant angular speed.
+    pub fn nlerp(&self, other: &Self, t: T) -> Self {
+        let other = if self.dot(other) < T::zero() { -*other } else { *other };
+        let blended = *self * (T::one() - t) + other * t;
+        blended.normalize().unwrap_or(*self)
+    }
+
+    /// Spherical linear interpolation along the shorter arc.
+    ///
+    /// `t = 0` gives `self` and `t = 1` gives `other` (or its negation,
+    /// which is the same rotation). Both inputs should be unit quaternions.
+    pub fn slerp(&self, other: &Self, t: T) -> Self {
+        let mut cos_theta = self.dot(other);
+        let mut other = *other;
+        if cos_theta < T::zero() {
+            other = -other;
+            cos_theta = T::zero() - cos_theta;
+        }
+        if cos_theta > T::from_f64(SLERP_NLERP_THRESHOLD) {
+            return self.nlerp(&other, t);
+        }
+
+        let sin_theta = (T::one() - cos_theta * cos_theta).sqrt();
+        let theta = sin_theta.atan2(cos_theta);
+        let a = ((T::one() - t) * theta).sin() / sin_theta;
+        let b = (t * theta).sin() / sin_theta;
+        *self * a + other * b
+    }
+
+    /// Rotation matrix.
+    ///
+    /// A non-unit quaternion gives the rotation of its normalized form.
+    pub fn to_matrix3(&self) -> Matrix3x3<T> {
+        let norm_squared = self.norm_squared();
+        if norm_squared == T::zero() {
+            return Matrix3x3::new([
+                [T::one(), T::zero(), T::zero()],
+                [T::zero(), T::one(), T::zero()],
+                [T::zero(), T::zero(), T::one()],
+            ]);
+        }
+        let s = T::from_f64(2.0) / norm_squared;
+        let (w, x, y, z) = (self.w, self.x, self.y, self.z);
+        let one = T::one();
+        Matrix3x3::new([
+            [one - s * (y * y + z * z), s * (x * y - w * z), s * (x * z + w * y)],
+            [s * (x * y + w * z), one - s * (x * x + z * z), s * (y * z - w * x)],
+            [s * (x * z - w * y), s * (y * z + w * x), one - s * (x * x + y * y)],
+        ])
+    }
+
+    /// Homogeneous rotation matrix with no translation.
+    pub fn to_matrix4(&self) -> Matrix4x4<T> {
+        let r = self.to_matrix3().data;
+        let (zero, one) = (T::zero(), T::one());
+        Matrix4x4 {
+            data: [
+                [r[0][0], r[0][1], r[0][2], zero],
+                [r[1][0], r[1][1], r[1][2], zero],
+                [r[2][0], r[2][1], r[2][2], zero],
+                [zero, zero, zero, one],
+            ],
+        }
+    }
+
+    /// Unit quaternion of a rotation matrix, with `w >= 0`.
+    ///
+    /// The matrix should be orthonormal with determinant +1; scale and shear
+    /// are not removed.
+    pub fn from_matrix3(matrix: &Matrix3x3<T>) -> Self {
+        let m = &matrix.data;
+        let (one, quarter, two) = (T::one(), T::from_f64(0.25), T::from_f64(2.0));
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        let q = if trace > T::zero() {
+            let s = (trace + one).sqrt() * two;
+            Self::new(quarter * s, (m[2][1] - m[1][2]) / s, (m[0][2] - m[2][0]) / s, (m[1][0] - m[0][1]) / s)
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = (one + m[0][0] - m[1][1] - m[2][2]).sqrt() * two;
+            Self::new((m[2][1] - m[1][2]) / s, quarter * s, (m[0][1] + m[1][0]) / s, (m[0][2] + m[2][0]) / s)
+        } else if m[1][1] > m[2][2] {
+            let s = (one + m[1][1] - m[0][0] - m[2][2]).sqrt() * two;
+            Self::new((m[0][2] - m[2][0]) / s, (m[0][1] + m[1][0]) / s, quarter * s, (m[1][2] + m[2][1]) / s)
+        } else {
+            let s = (one + m[2][2] - m[0][0] - m[1][1]).sqrt() * two;
+            Self::new((m[1][0] - m[0][1]) / s, (m[0][2] + m[2][0]) / s, (m[1][2] + m[2][1]) / s, quarter * s)
+        };
+        let q = q.normalize().unwrap_or_else(Self::identity);
+        if q.w < T::zero() { -q } else { q }
+    }
+
+    /// Unit quaternion of the upper-left 3x3 block of a homogeneous matrix.
+    pub fn from_matrix4(matrix: &Matrix4x4<T>) -> Self {
+        let m = &matrix.data;
+        Self::from_matrix3(&Matrix3x3::new([
+            [m[0][0], m[0][1], m[0][2]],
+            [m[1][0], m[1][1], m[1][2]],
+            [m[2][0], m[2][1], m[2][2]],
+        ]))
+    }
+}
+
+impl<T: FloatingPoint> Default for Quaternion<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+// Component-wise + and -, used for interpolation
+impl<T: FloatingPoint> Add for Quaternion<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.w + other.w, self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: FloatingPoint> Sub for Quaternion<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.w - other.w, self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: FloatingPoint> Neg for Quaternion<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(T::zero() - self.w, T::zero() - self.x, T::zero() - self.y, T::zero() - self.z)
+    }
+}
+
+impl<T: FloatingPoint> Mul<T> for Quaternion<T> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        Self::new(self.w * scalar, self.x * scalar, self.y * scalar, self.z * scalar)
+    }
+}
+
+// Hamilton product: composes rotations, applying `rhs` first
+impl<T: FloatingPoint> Mul for Quaternion<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
+
+impl<T: FloatingPoint> Mul<Vector3<T>> for Quaternion<T> {
+    type Output = Vector3<T>;
+
+    fn mul(self, rhs: Vector3<T>) -> Vector3<T> {
+        self.rotate_vector(rhs)
+    }
+}
+
+// Conversions between Quaternion<T> and [w, x, y, z] arrays
+
+impl<T: FloatingPoint> From<[T; 4]> for Quaternion<T> {
+    fn from(array: [T; 4]) -> Self {
+        Self::new(array[0], array[1], array[2], array[3])
+    }
+}
+
+impl<T: FloatingPoint> From<Quaternion<T>> for [T; 4] {
+    fn from(q: Quaternion<T>) -> Self {
+        [q.w, q.x, q.y, q.z]
+    }
+}
+
+impl<T: FloatingPoint> From<&Quaternion<T>> for [T; 4] {
+    fn from(q: &Quaternion<T>) -> Self {
+        [q.w, q.x, q.y, q.z]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bincode::config;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    fn assert_vector_close(a: Vector3<f64>, b: Vector3<f64>) {
+        assert!((a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9 && (a.z - b.z).abs() < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    fn assert_rotation_close(a: Quaterniond, b: Quaterniond) {
+        // q and -q are the same rotation
+        assert!((a.dot(&b).abs() - 1.0).abs() < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_axis_angle_rotation() {
+        let q = Quaterniond::from_axis_angle(Vector3::new(0.0, 0.0, 2.0), FRAC_PI_2);
+        assert!((q.norm() - 1.0).abs() < 1e-12);
+        assert_vector_close(q * Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+        assert_vector_close(q.conjugate() * Vector3::new(0.0, 1.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+
+        let (axis, angle) = q.to_axis_angle();
+        assert_vector_close(axis, Vector3::new(0.0, 0.0, 1.0));
+        assert!((angle - FRAC_PI_2).abs() < 1e-12);
+
+        assert_eq!(Quaterniond::from_axis_angle(Vector3::new(0.0, 0.0, 0.0), 1.0), Quaterniond::identity());
+    }
+
+    #[test]
+    fn test_composition_and_inverse() {
+        let a = Quaterniond::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 0.7);
+        let b = Quaterniond::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), -1.1);
+        let v = Vector3::new(0.3, -1.2, 2.5);
+
+        // a * b applies b first
+        assert_vector_close((a * b) * v, a * (b * v));
+        assert_vector_close((a * b).to_matrix3() * v, a.to_matrix3() * (b.to_matrix3() * v));
+        assert_rotation_close(a * a.inverse().unwrap(), Quaterniond::identity());
+        assert_eq!(Quaterniond::new(0.0, 0.0, 0.0, 0.0).inverse(), None);
+
+        let scaled = a * 3.0;
+        assert_rotation_close(scaled.normalize().unwrap(), a);
+        assert_vector_close(scaled.to_matrix3() * v, a * v);
+    }
+
+    #[test]
+    fn test_euler_roundtrip() {
+        let (roll, pitch, yaw) = (0.3, -0.6, 1.2);
+        let q = Quaterniond::from_euler(roll, pitch, yaw);
+        let expected = Quaterniond::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), yaw)
+            * Quaterniond::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), pitch)
+            * Quaterniond::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), roll);
+        assert_rotation_close(q, expected);
+
+        let (r, p, y) = q.to_euler();
+        assert!((r - roll).abs() < 1e-9 && (p - pitch).abs() < 1e-9 && (y - yaw).abs() < 1e-9);
+
+        // Gimbal lock still reproduces the rotation
+        for pitch in [FRAC_PI_2, -FRAC_PI_2] {
+            let locked = Quaterniond::from_euler(0.4, pitch, 0.1);
+            let (r, p, y) = locked.to_euler();
+            assert_eq!((r, p), (0.0, pitch));
+            assert_rotation_close(Quaterniond::from_euler(r, p, y), locked);
+        }
+    }
+
+    #[test]
+    fn test_matrix_roundtrip() {
+        let q = Quaterniond::new(0.1, 0.7, -0.5, 0.4).normalize().unwrap();
+        assert_rotation_close(Quaterniond::from_matrix3(&q.to_matrix3()), q);
+        assert_rotation_close(Quaterniond::from_matrix4(&q.to_matrix4()), q);
+        assert!(Quaterniond::from_matrix3(&(-q).to_matrix3()).w >= 0.0);
+
+        // A half turn exercises the non-positive trace branches
+        for axis in [Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0)] {
+            let half_turn = Quaterniond::from_axis_angle(axis, PI);
+            assert_rotation_close(Quaterniond::from_matrix3(&half_turn.to_matrix3()), half_turn);
+        }
+
+        let m = q.to_matrix4();
+        assert_eq!(m.data[3], [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(m.data[0][3], 0.0);
+    }
+
+    #[test]
+    fn test_slerp_and_nlerp() {
+        let axis = Vector3::new(0.0, 1.0, 0.0);
+        let a = Quaterniond::identity();
+        let b = Quaterniond::from_axis_angle(axis, FRAC_PI_2);
+
+        assert_rotation_close(a.slerp(&b, 0.0), a);
+        assert_rotation_close(a.slerp(&b, 1.0), b);
+        assert_rotation_close(a.slerp(&b, 0.5), Quaterniond::from_axis_angle(axis, FRAC_PI_4));
+
+        // Shorter arc even when the target is given with the opposite sign
+        assert_rotation_close(a.slerp(&-b, 0.5), Quaterniond::from_axis_angle(axis, FRAC_PI_4));
+        assert!(a.slerp(&-b, 0.5).w > 0.0);
+
+        let n = a.nlerp(&b, 0.5);
+        assert!((n.norm() - 1.0).abs() < 1e-12);
+        assert_rotation_close(n, Quaterniond::from_axis_angle(axis, FRAC_PI_4));
+
+        // Nearly identical inputs take the nlerp path
+        let c = Quaterniond::from_axis_angle(axis, 1e-4);
+        assert_rotation_close(a.slerp(&c, 0.5), Quaterniond::from_axis_angle(axis, 5e-5));
+    }
+
+    #[test]
+    fn test_f32_and_array_conversions() {
+        let q = Quaternionf::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), core::f32::consts::FRAC_PI_2);
+        let v = q * Vector3::new(0.0f32, 1.0, 0.0);
+        assert!((v.z - 1.0).abs() < 1e-6 && v.y.abs() < 1e-6);
+
+        let array: [f32; 4] = q.into();
+        assert_eq!(Quaternionf::from(array), q);
+        assert_eq!(Quaternionf::default(), Quaternionf::identity());
+    }
+
+    #[test]
+    fn test_bincode_roundtrip() {
+        let config = config::standard();
+        let q = Quaterniond::new(0.5, -0.5, 0.5, 0.5);
+
+        let encoded = bincode::encode_to_vec(q, config).unwrap();
+        let (decoded, _len): (Quaterniond, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, q);
+    }
+}
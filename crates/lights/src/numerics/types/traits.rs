@@ -23,6 +23,18 @@ Copy
     fn one() -> Self;
     fn abs(self) -> Self;
     fn sqrt(self) -> Self;
+    /// Convert from f64, rounding to the nearest representable value.
+    fn from_f64(value: f64) -> Self;
+    /// Convert to f64.
+    fn to_f64(self) -> f64;
+    /// Difference between 1.0 and the next larger representable value.
+    fn epsilon() -> Self;
+    /// Sine of an angle in radians. Computed in f64 unless overridden.
+    fn sin(self) -> Self { Self::from_f64(self.to_f64().sin()) }
+    /// Cosine of an angle in radians. Computed in f64 unless overridden.
+    fn cos(self) -> Self { Self::from_f64(self.to_f64().cos()) }
+    /// Four-quadrant arctangent of `self / other`. Computed in f64 unless overridden.
+    fn atan2(self, other: Self) -> Self { Self::from_f64(self.to_f64().atan2(other.to_f64())) }
 }
 
 impl FloatingPoint for f32 {
@@ -30,6 +42,12 @@ impl FloatingPoint for f32 {
     fn one() -> Self { 1.0 }
     fn abs(self) -> Self { f32::abs(self) }
     fn sqrt(self) -> Self { f32::sqrt(self) }
+    fn from_f64(value: f64) -> Self { value as f32 }
+    fn to_f64(self) -> f64 { self as f64 }
+    fn epsilon() -> Self { f32::EPSILON }
+    fn sin(self) -> Self { f32::sin(self) }
+    fn cos(self) -> Self { f32::cos(self) }
+    fn atan2(self, other: Self) -> Self { f32::atan2(self, other) }
 }
 
 impl FloatingPoint for f64 {
@@ -37,4 +55,10 @@ impl FloatingPoint for f64 {
     fn one() -> Self { 1.0 }
     fn abs(self) -> Self { f64::abs(self) }
     fn sqrt(self) -> Self { f64::sqrt(self) }
+    fn from_f64(value: f64) -> Self { value }
+    fn to_f64(self) -> f64 { self }
+    fn epsilon() -> Self { f64::EPSILON }
+    fn sin(self) -> Self { f64::sin(self) }
+    fn cos(self) -> Self { f64::cos(self) }
+    fn atan2(self, other: Self) -> Self { f64::atan2(self, other) }
 }
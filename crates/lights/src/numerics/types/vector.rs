@@ -4,7 +4,7 @@
 
 #![allow(dead_code)]
 
-use core::ops::{Add, Sub};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use serde::{Serialize, Deserialize};
 use bincode::{Encode, Decode};
 use super::traits::FloatingPoint;
@@ -62,10 +62,7 @@ impl<T: FloatingPoint> Vector3<T> {
     /// Kept minimal to avoid extra trait bounds.
     /// Vector of all zeros
     #[allow(dead_code)]
-    pub fn zero() -> Self
-    where
-        T: From<f32>,
-    {
+    pub fn zero() -> Self {
         Self {
             x: T::zero(),
             y: T::zero(),
@@ -74,10 +71,7 @@ impl<T: FloatingPoint> Vector3<T> {
     }
 
     /// Vector of all ones
-    pub fn one() -> Self
-    where
-        T: From<f32>,
-    {
+    pub fn one() -> Self {
         Self {
             x: T::one(),
             y: T::one(),
@@ -179,24 +173,6 @@ impl<T: FloatingPoint> From<&Vector3<T>> for [T; 3] {
     }
 }
 
-// Implement some f32-specific utilities (square root usage allowed only for f32)
-impl Vector3<f32> {
-    /// Return the squared length (avoids sqrt)
-    pub fn length_squared(&self) -> f32 {
-        self.x * self.x + self.y * self.y + self.z * self.z
-    }
-
-    /// Return the Euclidean length. Uses `f32::sqrt`.
-    pub fn length(&self) -> f32 {
-        self.length_squared().sqrt()
-    }
-
-    /// Dot product for f32 specialization
-    pub fn dot(&self, other: &Self) -> f32 {
-        self.x * other.x + self.y * other.y + self.z * other.z
-    }
-}
-
 #[derive(Copy, Clone, Debug, PartialEq, Encode, Decode)]
 pub struct Vector2<T: FloatingPoint> {
     pub x: T,
@@ -245,10 +221,7 @@ impl<T: FloatingPoint> Vector2<T> {
     /// Kept minimal to avoid extra trait bounds.
     /// Vector of all zeros
     #[allow(dead_code)]
-    pub fn zero() -> Self
-    where
-        T: From<f32>,
-    {
+    pub fn zero() -> Self {
         Self {
             x: T::zero(),
             y: T::zero(),
@@ -256,10 +229,7 @@ impl<T: FloatingPoint> Vector2<T> {
     }
 
     /// Vector of all ones
-    pub fn one() -> Self
-    where
-        T: From<f32>,
-    {
+    pub fn one() -> Self {
         Self {
             x: T::one(),
             y: T::one(),
@@ -356,24 +326,6 @@ impl<T: FloatingPoint> From<&Vector2<T>> for [T; 2] {
     }
 }
 
-// Implement some f32-specific utilities (square root usage allowed only for f32)
-impl Vector2<f32> {
-    /// Return the squared length (avoids sqrt)
-    pub fn length_squared(&self) -> f32 {
-        self.x * self.x + self.y * self.y
-    }
-
-    /// Return the Euclidean length. Uses `f32::sqrt`.
-    pub fn length(&self) -> f32 {
-        self.length_squared().sqrt()
-    }
-
-    /// Dot product for f32 specialization
-    pub fn dot(&self, other: &Self) -> f32 {
-        self.x * other.x + self.y * other.y
-    }
-}
-
 #[derive(Copy, Clone, Debug, PartialEq, Encode, Decode)]
 pub struct Vector4<T: FloatingPoint> {
     pub x: T,
@@ -424,10 +376,7 @@ impl<T: FloatingPoint> Vector4<T> {
     /// Kept minimal to avoid extra trait bounds.
     /// Vector of all zeros
     #[allow(dead_code)]
-    pub fn zero() -> Self
-    where
-        T: From<f32>,
-    {
+    pub fn zero() -> Self {
         Self {
             x: T::zero(),
             y: T::zero(),
@@ -437,10 +386,7 @@ impl<T: FloatingPoint> Vector4<T> {
     }
 
     /// Vector of all ones
-    pub fn one() -> Self
-    where
-        T: From<f32>,
-    {
+    pub fn one() -> Self {
         Self {
             x: T::one(),
             y: T::one(),
@@ -547,21 +493,178 @@ impl<T: FloatingPoint> From<&Vector4<T>> for [T; 4] {
     }
 }
 
-// Implement some f32-specific utilities (square root usage allowed only for f32)
-impl Vector4<f32> {
-    /// Return the squared length (avoids sqrt)
-    pub fn length_squared(&self) -> f32 {
-        self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w
-    }
+// Vector algebra shared by Vector2, Vector3 and Vector4, generic over every
+// FloatingPoint type.
+macro_rules! vector_algebra {
+    ($name:ident { $($field:ident),+ }) => {
+        impl<T: FloatingPoint> $name<T> {
+            /// Vector with every component set to `value`
+            pub fn splat(value: T) -> Self {
+                Self { $($field: value),+ }
+            }
+
+            /// Dot product
+            pub fn dot(&self, other: &Self) -> T {
+                T::zero() $(+ self.$field * other.$field)+
+            }
+
+            /// Return the squared length (avoids sqrt)
+            pub fn length_squared(&self) -> T {
+                self.dot(self)
+            }
+
+            /// Return the Euclidean length
+            pub fn length(&self) -> T {
+                self.length_squared().sqrt()
+            }
+
+            /// Squared distance to another point
+            pub fn distance_squared(&self, other: &Self) -> T {
+                (*self - *other).length_squared()
+            }
+
+            /// Distance to another point
+            pub fn distance(&self, other: &Self) -> T {
+                (*self - *other).length()
+            }
+
+            /// Unit vector in the same direction, or `None` for the zero vector
+            pub fn normalize(&self) -> Option<Self> {
+                let length = self.length();
+                if length == T::zero() {
+                    return None;
+                }
+                Some(*self / length)
+            }
+
+            /// Linear interpolation: `self` at `t = 0`, `other` at `t = 1`
+            pub fn lerp(&self, other: &Self, t: T) -> Self {
+                Self { $($field: self.$field + (other.$field - self.$field) * t),+ }
+            }
+
+            /// Component-wise minimum
+            pub fn min(&self, other: &Self) -> Self {
+                Self { $($field: if other.$field < self.$field { other.$field } else { self.$field }),+ }
+            }
+
+            /// Component-wise maximum
+            pub fn max(&self, other: &Self) -> Self {
+                Self { $($field: if other.$field > self.$field { other.$field } else { self.$field }),+ }
+            }
+
+            /// Component-wise absolute value
+            pub fn abs(&self) -> Self {
+                Self { $($field: self.$field.abs()),+ }
+            }
+
+            /// Component-wise product
+            pub fn component_mul(&self, other: &Self) -> Self {
+                Self { $($field: self.$field * other.$field),+ }
+            }
+
+            /// Component-wise quotient
+            pub fn component_div(&self, other: &Self) -> Self {
+                Self { $($field: self.$field / other.$field),+ }
+            }
+
+            /// Smallest component
+            pub fn min_component(&self) -> T {
+                [$(self.$field),+].into_iter().reduce(|a, b| if b < a { b } else { a }).unwrap()
+            }
+
+            /// Largest component
+            pub fn max_component(&self) -> T {
+                [$(self.$field),+].into_iter().reduce(|a, b| if b > a { b } else { a }).unwrap()
+            }
+        }
+
+        impl<T: FloatingPoint> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, scalar: T) -> Self {
+                Self { $($field: self.$field * scalar),+ }
+            }
+        }
+
+        impl<T: FloatingPoint> Div<T> for $name<T> {
+            type Output = Self;
 
-    /// Return the Euclidean length. Uses `f32::sqrt`.
-    pub fn length(&self) -> f32 {
-        self.length_squared().sqrt()
+            fn div(self, scalar: T) -> Self {
+                Self { $($field: self.$field / scalar),+ }
+            }
+        }
+
+        impl<T: FloatingPoint> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: T::zero() - self.$field),+ }
+            }
+        }
+
+        impl<T: FloatingPoint> AddAssign for $name<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: FloatingPoint> SubAssign for $name<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: FloatingPoint> MulAssign<T> for $name<T> {
+            fn mul_assign(&mut self, scalar: T) {
+                *self = *self * scalar;
+            }
+        }
+
+        impl<T: FloatingPoint> DivAssign<T> for $name<T> {
+            fn div_assign(&mut self, scalar: T) {
+                *self = *self / scalar;
+            }
+        }
+
+        // Scalar on the left, for the concrete precisions
+        impl Mul<$name<f32>> for f32 {
+            type Output = $name<f32>;
+
+            fn mul(self, vector: $name<f32>) -> $name<f32> {
+                vector * self
+            }
+        }
+
+        impl Mul<$name<f64>> for f64 {
+            type Output = $name<f64>;
+
+            fn mul(self, vector: $name<f64>) -> $name<f64> {
+                vector * self
+            }
+        }
+    };
+}
+
+vector_algebra!(Vector2 { x, y });
+vector_algebra!(Vector3 { x, y, z });
+vector_algebra!(Vector4 { x, y, z, w });
+
+impl<T: FloatingPoint> Vector3<T> {
+    /// Cross product, following the right-hand rule
+    pub fn cross(&self, other: &Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
     }
+}
 
-    /// Dot product for f32 specialization
-    pub fn dot(&self, other: &Self) -> f32 {
-        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+impl<T: FloatingPoint> Vector2<T> {
+    /// Z component of the 3D cross product of the two vectors; positive when
+    /// `other` is counter-clockwise from `self`
+    pub fn perp_dot(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
     }
 }
 
@@ -937,4 +1040,66 @@ mod tests {
         let o = Vector4::<f32>::one();
         assert_eq!(o, Vector4::new(1.0, 1.0, 1.0, 1.0));
     }
+
+    #[test]
+    fn test_generic_length_and_normalize() {
+        fn unit_length<T: FloatingPoint>(v: Vector3<T>) -> f64 {
+            v.normalize().unwrap().length().to_f64()
+        }
+
+        let v64 = Vector3::new(3.0f64, 4.0, 12.0);
+        assert_eq!(v64.length(), 13.0);
+        assert_eq!(v64.length_squared(), 169.0);
+        assert!((unit_length(v64) - 1.0).abs() < 1e-12);
+        assert!((unit_length(Vector3::new(3.0f32, 4.0, 12.0)) - 1.0).abs() < 1e-6);
+        assert_eq!(Vector3::<f64>::zero().normalize(), None);
+
+        assert_eq!(Vector2::new(3.0f64, 4.0).length(), 5.0);
+        assert_eq!(Vector4::new(1.0f64, 1.0, 1.0, 1.0).length(), 2.0);
+        assert_eq!(Vector2::new(0.0f64, 0.0).distance(&Vector2::new(3.0, 4.0)), 5.0);
+    }
+
+    #[test]
+    fn test_dot_and_cross() {
+        let x = Vector3::new(1.0f64, 0.0, 0.0);
+        let y = Vector3::new(0.0f64, 1.0, 0.0);
+        assert_eq!(x.cross(&y), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(y.cross(&x), Vector3::new(0.0, 0.0, -1.0));
+        assert_eq!(x.dot(&y), 0.0);
+
+        let a = Vector3::new(1.0f32, 2.0, 3.0);
+        let b = Vector3::new(-2.0f32, 0.5, 4.0);
+        assert_eq!(a.dot(&b), 11.0);
+        assert_eq!(a.cross(&b).dot(&a), 0.0);
+        assert_eq!(Vector2::new(1.0f64, 0.0).perp_dot(&Vector2::new(0.0, 1.0)), 1.0);
+        assert_eq!(Vector4::new(1.0f64, 2.0, 3.0, 4.0).dot(&Vector4::splat(1.0)), 10.0);
+    }
+
+    #[test]
+    fn test_scalar_and_component_ops() {
+        let mut v = Vector3::new(1.0f64, -2.0, 4.0);
+        assert_eq!(v * 2.0, Vector3::new(2.0, -4.0, 8.0));
+        assert_eq!(2.0 * v, v * 2.0);
+        assert_eq!(v / 2.0, Vector3::new(0.5, -1.0, 2.0));
+        assert_eq!(-v, Vector3::new(-1.0, 2.0, -4.0));
+        assert_eq!(v.abs(), Vector3::new(1.0, 2.0, 4.0));
+
+        let other = Vector3::new(2.0, 3.0, -1.0);
+        assert_eq!(v.min(&other), Vector3::new(1.0, -2.0, -1.0));
+        assert_eq!(v.max(&other), Vector3::new(2.0, 3.0, 4.0));
+        assert_eq!(v.component_mul(&other), Vector3::new(2.0, -6.0, -4.0));
+        assert_eq!(v.component_div(&other), Vector3::new(0.5, -2.0 / 3.0, -4.0));
+        assert_eq!((v.min_component(), v.max_component()), (-2.0, 4.0));
+        assert_eq!(v.lerp(&other, 0.5), Vector3::new(1.5, 0.5, 1.5));
+
+        v += other;
+        v -= Vector3::splat(1.0);
+        v *= 2.0;
+        v /= 4.0;
+        assert_eq!(v, Vector3::new(1.0, 0.0, 1.0));
+
+        let w = Vector4::new(1.0f32, 2.0, 3.0, 4.0);
+        assert_eq!(0.5f32 * w, Vector4::new(0.5, 1.0, 1.5, 2.0));
+        assert_eq!(-Vector2::new(1.0f32, -1.0), Vector2::new(-1.0, 1.0));
+    }
 }
@@ -5,7 +5,8 @@
 //! shaders, vertex buffer objects, and framebuffer objects to achieve
 //! high-performance real-time rendering.
 
-use crate::renderer::{Capability, ProcessingUnitCapability, Renderer, DataPrecision, RendererEvent, BufferedAsyncSender, generate_renderer_id};
+use crate::renderer::{Capability, ProcessingUnitCapability, Renderer, DataPrecision, RendererEvent, BufferedAsyncSender, generate_renderer_id, handle_scene_event};
+use crate::renderer::world::{check_splat_count, SceneUpdate, World};
 use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
@@ -321,6 +322,9 @@ pub struct OpenGL3Renderer {
     vertex_buffer_object: u32,
     shader_program: u32,
 
+    /// World whose splats are uploaded to the vertex buffer
+    world: Option<World>,
+
     sender: BufferedAsyncSender<RendererEvent>,
     receiver: UnboundedReceiver<RendererEvent>
 }
@@ -342,6 +346,7 @@ impl OpenGL3Renderer {
             vertex_array_object: 0,
             vertex_buffer_object: 0,
             shader_program: 0,
+            world: None,
             sender: buffered_sender,
             receiver: buffered_receiver
         }
@@ -383,6 +388,11 @@ impl OpenGL3Renderer {
         self.config.max_splat_count
     }
 
+    /// Get the world currently bound to this renderer, if any.
+    pub fn world(&self) -> Option<&World> {
+        self.world.as_ref()
+    }
+
     /// Check if OpenGL context is initialized.
     pub fn is_context_initialized(&self) -> bool {
         self.gl_context_initialized
@@ -415,6 +425,22 @@ impl OpenGL3Renderer {
         Ok(())
     }
 
+    /// Upload the bound world's splats to the vertex buffer (mock implementation).
+    fn upload_splat_data(&mut self) -> Result<(), String> {
+        let splat_count = self.world.as_ref().map_or(0, World::splat_count);
+
+        // In a real implementation, this would:
+        // 1. Convert splats to the current vertex precision
+        // 2. Resize the VBO if needed (glBufferData)
+        // 3. Upload the vertex data (glBufferSubData)
+
+        let _ = self.sender.send(RendererEvent::SplatDataUpdated {
+            renderer_id: self.id,
+            splat_count,
+        });
+        Ok(())
+    }
+
     /// Cleanup OpenGL resources (mock implementation).
     fn cleanup_gl_context(&mut self) {
         if !self.gl_context_initialized {
@@ -515,6 +541,22 @@ impl Renderer for OpenGL3Renderer {
         Ok(())
     }
 
+    fn set_world(&mut self, world: World) -> Result<(), String> {
+        check_splat_count(world.splat_count(), self.config.max_splat_count)?;
+        self.world = Some(world);
+        self.upload_splat_data()
+    }
+
+    fn submit_scene_update(&mut self, update: SceneUpdate) -> Result<(), String> {
+        let splat_count = self.world.as_ref().ok_or("No world bound to OpenGL3 renderer")?.splat_count();
+        check_splat_count(update.resulting_splat_count(splat_count), self.config.max_splat_count)?;
+
+        if let Some(world) = self.world.as_mut() {
+            world.apply_update(update)?;
+        }
+        self.upload_splat_data()
+    }
+
     fn set_data_precision(&mut self, precision: DataPrecision) -> Result<DataPrecision, String> {
         if !self.supports_precision(precision) {
             return Err(format!(
@@ -591,7 +633,9 @@ impl Renderer for OpenGL3Renderer {
                         println!("OpenGL3Renderer switched {:?}", active);
                     }
                     other => {
-                        println!("OpenGL3Renderer ignoring {:?}", other);
+                        if let Some(other) = handle_scene_event(self, other) {
+                            println!("OpenGL3Renderer ignoring {:?}", other);
+                        }
                     }
                 }
             }
@@ -879,6 +923,48 @@ mod tests {
         assert!(fragment_shader.contains("gaussian"));
     }
 
+    #[test]
+    fn test_scene_submission() {
+        use crate::renderer::world::{GaussianSplat, Point3D};
+
+        let mut renderer = OpenGL3RendererBuilder::new().max_splat_count(2).build();
+        assert!(renderer.world().is_none());
+
+        // Updates require a bound world
+        let result = renderer.submit_scene_update(SceneUpdate::new());
+        assert!(result.unwrap_err().contains("No world bound"));
+
+        let mut world = World::new();
+        let id = world.add_splat_at(Point3D::origin());
+        renderer.set_world(world).unwrap();
+        assert_eq!(renderer.world().unwrap().splat_count(), 1);
+
+        let update = SceneUpdate::new().with_added(GaussianSplat::new(0, Point3D::new(1.0, 0.0, 0.0)));
+        renderer.submit_scene_update(update).unwrap();
+        assert_eq!(renderer.world().unwrap().splat_count(), 2);
+
+        // Exceeding max_splat_count is rejected
+        let update = SceneUpdate::new().with_added(GaussianSplat::new(0, Point3D::new(2.0, 0.0, 0.0)));
+        assert!(renderer.submit_scene_update(update).unwrap_err().contains("max_splat_count"));
+        assert_eq!(renderer.world().unwrap().splat_count(), 2);
+
+        let update = SceneUpdate::new()
+            .with_removed(id)
+            .with_added(GaussianSplat::new(0, Point3D::new(2.0, 0.0, 0.0)));
+        renderer.submit_scene_update(update).unwrap();
+        assert_eq!(renderer.world().unwrap().splat_count(), 2);
+
+        // A repeated removed ID only frees one slot, as in ReferenceRenderer
+        let kept = renderer.world().unwrap().splats()[0].id;
+        let update = SceneUpdate::new()
+            .with_removed(kept)
+            .with_removed(kept)
+            .with_added(GaussianSplat::new(0, Point3D::new(3.0, 0.0, 0.0)))
+            .with_added(GaussianSplat::new(0, Point3D::new(4.0, 0.0, 0.0)));
+        assert!(renderer.submit_scene_update(update).unwrap_err().contains("max_splat_count"));
+        assert_eq!(renderer.world().unwrap().splat_count(), 2);
+    }
+
     #[test]
     fn test_opengl_requirements() {
         let mut renderer = OpenGL3RendererBuilder::new()
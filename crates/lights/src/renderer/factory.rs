@@ -416,6 +416,39 @@ impl Default for ReferenceRendererConfig {
 }
 
 impl ReferenceRendererConfig {
+    /// Factory parameters understood by the reference renderer, with descriptions.
+    pub const PARAMETERS: &'static [(&'static str, &'static str)] = &[
+        ("precision", "Data precision for rendering (f16, f32, f64, bfloat16)"),
+        ("threads", "Worker threads compositing screen tiles"),
+        ("quality", "Sampling and cutoff preset (low, medium, high, ultra)"),
+        ("debug", "Enable debug output (true, false)"),
+        ("max_splat_count", "Largest number of splats a scene may contain"),
+        ("viewport_size", "Initial viewport as widthxheight, e.g. 1920x1080"),
+    ];
+
+    /// Parse and validate factory parameters, rejecting unknown keys.
+    pub fn from_factory_parameters(precision: DataPrecision, parameters: &str) -> Result<Self, RendererError> {
+        for key in parse_parameters(parameters).keys() {
+            if !Self::PARAMETERS.iter().any(|(name, _)| name == key) {
+                return Err(RendererError::InvalidParameters(
+                    format!("Unknown parameter for ReferenceRenderer: {}", key)
+                ));
+            }
+        }
+
+        let config = Self::from_parameters(precision, parameters).map_err(RendererError::InvalidParameters)?;
+        config.validate().map_err(RendererError::InvalidParameters)?;
+        Ok(config)
+    }
+
+    /// Parameter descriptions for `RendererInfo`.
+    pub fn parameter_descriptions() -> HashMap<String, String> {
+        Self::PARAMETERS
+            .iter()
+            .map(|(name, description)| (name.to_string(), description.to_string()))
+            .collect()
+    }
+
     /// Create configuration from parameter string.
     pub fn from_parameters(precision: DataPrecision, parameters: &str) -> Result<Self, String> {
         let mut config = Self::default();
@@ -548,57 +581,22 @@ impl Default for ReferenceRendererFactory {
 
 impl RendererFactory for ReferenceRendererFactory {
     fn create(&self, precision: DataPrecision, parameters: &str) -> Result<Box<dyn crate::renderer::Renderer>, RendererError> {
-        // Parse parameters if any
-        if !parameters.is_empty() {
-            let params = parse_parameters(parameters);
-            for (key, _) in params {
-                match key.as_str() {
-                    "precision" => {}, // Handled by precision parameter
-                    _ => {
-                        return Err(RendererError::InvalidParameters(
-                            format!("Unknown parameter for ReferenceRenderer: {}", key)
-                        ));
-                    }
-                }
-            }
-        }
-
-        Ok(Box::new(crate::renderer::ReferenceRenderer::with_precision(precision)))
+        let config = ReferenceRendererConfig::from_factory_parameters(precision, parameters)?;
+        let renderer = crate::renderer::ReferenceRenderer::from_config(&config).map_err(RendererError::CreationFailed)?;
+        Ok(Box::new(renderer))
     }
 
     fn get_info(&self) -> RendererInfo {
-        let mut parameters = std::collections::HashMap::new();
-        parameters.insert(
-            "precision".to_string(),
-            "Data precision for rendering (f16, f32, f64, bfloat16)".to_string()
-        );
-
         RendererInfo::new(
             self.factory_name.clone(),
             "reference,cpu,basic_rendering,all_precisions".to_string(),
-            parameters,
+            ReferenceRendererConfig::parameter_descriptions(),
             1000, // 1ms timeout
         )
     }
 
-    fn validate_parameters(&self, _precision: DataPrecision, parameters: &str) -> Result<(), RendererError> {
-        if parameters.is_empty() {
-            return Ok(());
-        }
-
-        let params = parse_parameters(parameters);
-        for (key, _) in params {
-            match key.as_str() {
-                "precision" => {},
-                _ => {
-                    return Err(RendererError::InvalidParameters(
-                        format!("Unknown parameter for ReferenceRenderer: {}", key)
-                    ));
-                }
-            }
-        }
-
-        Ok(())
+    fn validate_parameters(&self, precision: DataPrecision, parameters: &str) -> Result<(), RendererError> {
+        ReferenceRendererConfig::from_factory_parameters(precision, parameters).map(|_| ())
     }
 }
 
@@ -0,0 +1,258 @@
+//! Framebuffer storage for CPU-side rendering.
+//!
+//! The framebuffer keeps RGBA pixels as `f32` values in row-major order,
+//! with row 0 at the top of the image. Colors are display-referred: splat and
+//! spherical-harmonic colors are already encoded for display, as in 3D
+//! Gaussian Splatting, and compositing them leaves them that way. Frames can
+//! be read back as RGBA8 or RGBA32F buffers and exported to PNG or OpenEXR
+//! without a display. 8-bit readback quantizes the stored values directly;
+//! `to_rgba8_srgb` applies the sRGB curve for buffers filled with linear values.
+
+use image::{DynamicImage, ImageFormat, ImageResult, Rgba32FImage, RgbaImage};
+use std::fmt;
+use std::path::Path;
+
+/// An RGBA framebuffer with display-referred `f32` color channels.
+#[derive(Clone, PartialEq)]
+pub struct Framebuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Framebuffer {
+    /// Create a new framebuffer cleared to transparent black.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0.0; 4]; width as usize * height as usize],
+        }
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Total number of pixels.
+    pub fn pixel_count(&self) -> usize {
+        self.pixels.len()
+    }
+
+    /// Resize the framebuffer. Contents are cleared to transparent black.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+        self.pixels.clear();
+        self.pixels.resize(width as usize * height as usize, [0.0; 4]);
+    }
+
+    /// Fill every pixel with the given color.
+    pub fn clear(&mut self, color: [f32; 4]) {
+        self.pixels.fill(color);
+    }
+
+    /// Get the pixel at `(x, y)`, or `None` if out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[f32; 4]> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[self.index(x, y)])
+        } else {
+            None
+        }
+    }
+
+    /// Set the pixel at `(x, y)`. Out-of-bounds writes are ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: [f32; 4]) {
+        if x < self.width && y < self.height {
+            let index = self.index(x, y);
+            self.pixels[index] = color;
+        }
+    }
+
+    /// All pixels in row-major order.
+    pub fn pixels(&self) -> &[[f32; 4]] {
+        &self.pixels
+    }
+
+    /// Mutable access to all pixels in row-major order.
+    pub fn pixels_mut(&mut self) -> &mut [[f32; 4]] {
+        &mut self.pixels
+    }
+
+    /// Read back as interleaved RGBA8 bytes.
+    ///
+    /// Channels are clamped to `[0, 1]` and rounded to the nearest 8-bit value.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| pixel.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8))
+            .collect()
+    }
+
+    /// Read back as interleaved RGBA8 bytes, treating the color channels as
+    /// linear and sRGB encoding them.
+    ///
+    /// Color channels are clamped to `[0, 1]` and sRGB encoded, alpha is left
+    /// as is; both are rounded to the nearest 8-bit value.
+    pub fn to_rgba8_srgb(&self) -> Vec<u8> {
+        let quantize = |value: f32| (value * 255.0).round() as u8;
+        self.pixels
+            .iter()
+            .flat_map(|&[r, g, b, a]| {
+                [
+                    quantize(linear_to_srgb(r)),
+                    quantize(linear_to_srgb(g)),
+                    quantize(linear_to_srgb(b)),
+                    quantize(a.clamp(0.0, 1.0)),
+                ]
+            })
+            .collect()
+    }
+
+    /// Read back as interleaved RGBA32F values, unclamped.
+    pub fn to_rgba32f(&self) -> Vec<f32> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    /// Convert to an 8-bit `image` buffer.
+    pub fn to_rgba8_image(&self) -> RgbaImage {
+        RgbaImage::from_raw(self.width, self.height, self.to_rgba8())
+            .expect("framebuffer size matches pixel count")
+    }
+
+    /// Convert to a 32-bit float `image` buffer.
+    pub fn to_rgba32f_image(&self) -> Rgba32FImage {
+        Rgba32FImage::from_raw(self.width, self.height, self.to_rgba32f())
+            .expect("framebuffer size matches pixel count")
+    }
+
+    /// Save as an 8-bit PNG.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> ImageResult<()> {
+        self.to_rgba8_image().save_with_format(path, ImageFormat::Png)
+    }
+
+    /// Save as a 32-bit float OpenEXR image, preserving high dynamic range values.
+    pub fn save_exr(&self, path: impl AsRef<Path>) -> ImageResult<()> {
+        DynamicImage::ImageRgba32F(self.to_rgba32f_image()).save_with_format(path, ImageFormat::OpenExr)
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+}
+
+/// Encode a linear channel value with the sRGB transfer curve, clamping to `[0, 1]`.
+pub fn linear_to_srgb(value: f32) -> f32 {
+    let value = value.clamp(0.0, 1.0);
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+impl fmt::Debug for Framebuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Keep debug concise: pixel data can be several megabytes
+        f.debug_struct("Framebuffer")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_framebuffer_creation_and_access() {
+        let mut framebuffer = Framebuffer::new(4, 3);
+        assert_eq!(framebuffer.width(), 4);
+        assert_eq!(framebuffer.height(), 3);
+        assert_eq!(framebuffer.pixel_count(), 12);
+        assert_eq!(framebuffer.pixel(0, 0), Some([0.0; 4]));
+        assert_eq!(framebuffer.pixel(4, 0), None);
+
+        framebuffer.set_pixel(3, 2, [1.0, 0.5, 0.25, 1.0]);
+        assert_eq!(framebuffer.pixel(3, 2), Some([1.0, 0.5, 0.25, 1.0]));
+        assert_eq!(framebuffer.pixels()[11], [1.0, 0.5, 0.25, 1.0]);
+
+        // Out of bounds writes are ignored
+        framebuffer.set_pixel(10, 10, [1.0; 4]);
+    }
+
+    #[test]
+    fn test_framebuffer_clear_and_resize() {
+        let mut framebuffer = Framebuffer::new(2, 2);
+        framebuffer.clear([0.1, 0.2, 0.3, 1.0]);
+        assert!(framebuffer.pixels().iter().all(|p| *p == [0.1, 0.2, 0.3, 1.0]));
+
+        framebuffer.resize(3, 1);
+        assert_eq!(framebuffer.pixel_count(), 3);
+        assert!(framebuffer.pixels().iter().all(|p| *p == [0.0; 4]));
+    }
+
+    #[test]
+    fn test_framebuffer_readback() {
+        let mut framebuffer = Framebuffer::new(2, 1);
+        framebuffer.set_pixel(0, 0, [1.0, 0.5, 0.0, 1.0]);
+        framebuffer.set_pixel(1, 0, [2.0, -1.0, 0.25, 0.0]);
+
+        assert_eq!(framebuffer.to_rgba8(), vec![255, 128, 0, 255, 255, 0, 64, 0]);
+        assert_eq!(
+            framebuffer.to_rgba32f(),
+            vec![1.0, 0.5, 0.0, 1.0, 2.0, -1.0, 0.25, 0.0]
+        );
+
+        let image = framebuffer.to_rgba8_image();
+        assert_eq!(image.dimensions(), (2, 1));
+        assert_eq!(image.get_pixel(0, 0).0, [255, 128, 0, 255]);
+
+        let hdr_image = framebuffer.to_rgba32f_image();
+        assert_eq!(hdr_image.get_pixel(1, 0).0, [2.0, -1.0, 0.25, 0.0]);
+    }
+
+    #[test]
+    fn test_srgb_encoding() {
+        // Linear 0.216 is perceptual mid-grey, sRGB 128
+        let mut framebuffer = Framebuffer::new(1, 1);
+        framebuffer.clear([0.216, 0.216, 0.216, 0.5]);
+        assert_eq!(framebuffer.to_rgba8_srgb(), vec![128, 128, 128, 128]);
+        assert_eq!(framebuffer.to_rgba8(), vec![55, 55, 55, 128]);
+
+        assert_eq!(linear_to_srgb(0.0), 0.0);
+        assert!((linear_to_srgb(1.0) - 1.0).abs() < 1e-6);
+        assert!((linear_to_srgb(0.002) - 0.002 * 12.92).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_framebuffer_export() {
+        let mut framebuffer = Framebuffer::new(3, 2);
+        framebuffer.clear([0.2, 0.4, 0.6, 1.0]);
+        framebuffer.set_pixel(1, 1, [4.0, 0.0, 0.0, 1.0]);
+
+        let directory = std::env::temp_dir().join(format!("lights_framebuffer_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let png_path = directory.join("frame.png");
+        framebuffer.save_png(&png_path).unwrap();
+        let png = image::open(&png_path).unwrap().to_rgba8();
+        assert_eq!(png.dimensions(), (3, 2));
+        assert_eq!(png.get_pixel(1, 1).0, [255, 0, 0, 255]);
+
+        let exr_path = directory.join("frame.exr");
+        framebuffer.save_exr(&exr_path).unwrap();
+        let exr = image::open(&exr_path).unwrap().to_rgba32f();
+        assert_eq!(exr.dimensions(), (3, 2));
+        assert_eq!(exr.get_pixel(1, 1).0, [4.0, 0.0, 0.0, 1.0]);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
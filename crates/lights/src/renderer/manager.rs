@@ -102,7 +102,10 @@ pub struct RendererManager {
     renderer_shutdowns: Mutex<Vec<(u64, mpsc::Sender<()>, mpsc::Receiver<()>, Duration)>>,
     factory_health: Arc<Mutex<HashMap<TypeId, FactoryHealthInfo>>>,
     factory_metrics: Arc<Mutex<HashMap<TypeId, FactoryMetrics>>>,
-    last_health_check: Arc<Mutex<Option<Instant>>>
+    last_health_check: Arc<Mutex<Option<Instant>>>,
+    /// Quality scores measured by `tools::precision_comparison`, keyed by
+    /// (factory name, precision): each precision of a factory is scored separately
+    measured_quality: Arc<Mutex<HashMap<(String, DataPrecision), f32>>>,
 }
 
 impl RendererManager {
@@ -113,6 +116,7 @@ impl RendererManager {
             factory_health: Arc::new(Mutex::new(Default::default())),
             factory_metrics: Arc::new(Mutex::new(Default::default())),
             last_health_check: Arc::new(Mutex::new(None)),
+            measured_quality: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -653,13 +657,21 @@ impl RendererManager {
                             .push(info.name.clone());
                     }
 
+                    // Measured quality scores replace the built-in estimates
+                    let mut precision_performance = self.get_default_precision_performance();
+                    for (precision, performance) in precision_performance.iter_mut() {
+                        if let Some(quality_score) = self.measured_quality_score(&info.name, *precision) {
+                            performance.quality_score = quality_score;
+                        }
+                    }
+
                     // Create factory precision info with safer methods
                     let precision_info = FactoryPrecisionInfo {
                         factory_name: info.name.clone(),
                         factory_id: *type_id,
                         supported_precisions: supported,
                         preferred_precision: Some(DataPrecision::F32), // Safe default
-                        precision_performance: self.get_default_precision_performance(),
+                        precision_performance,
                     };
 
                     factory_infos.push(precision_info);
@@ -679,6 +691,24 @@ impl RendererManager {
         }
     }
 
+    /// Record a measured quality score for a factory at a precision.
+    ///
+    /// Scores are clamped to `[0, 1]` and reported by `get_precision_matrix`
+    /// in place of the built-in estimates.
+    pub fn record_quality_score(&self, factory_name: &str, precision: DataPrecision, quality_score: f32) {
+        if let Ok(mut measured) = self.measured_quality.lock() {
+            measured.insert((factory_name.to_string(), precision), quality_score.clamp(0.0, 1.0));
+        }
+    }
+
+    /// Get the measured quality score for a factory at a precision, if one was recorded.
+    pub fn measured_quality_score(&self, factory_name: &str, precision: DataPrecision) -> Option<f32> {
+        self.measured_quality
+            .lock()
+            .ok()
+            .and_then(|measured| measured.get(&(factory_name.to_string(), precision)).copied())
+    }
+
     /// FIXED: Get supported precisions for a specific factory (non-blocking)
     pub fn get_factory_supported_precisions(&self, factory_id: TypeId) -> Vec<DataPrecision> {
         let test_precisions = [
@@ -2181,6 +2211,27 @@ mod tests {
         assert!(f32_factories.len() > 0);
     }
 
+    #[test]
+    fn test_precision_matrix_measured_quality() {
+        let manager = standard_test_manager!(cpu_gpu);
+        assert_eq!(manager.measured_quality_score("GpuRenderer", DataPrecision::F16), None);
+
+        manager.record_quality_score("GpuRenderer", DataPrecision::F16, 0.42);
+        manager.record_quality_score("GpuRenderer", DataPrecision::F64, 1.5);
+        assert_eq!(manager.measured_quality_score("GpuRenderer", DataPrecision::F64), Some(1.0));
+
+        let matrix = manager.get_precision_matrix();
+        for factory in &matrix.factories {
+            let f16 = factory.precision_performance[&DataPrecision::F16].quality_score;
+            if factory.factory_name == "GpuRenderer" {
+                assert_eq!(f16, 0.42);
+            } else {
+                assert_eq!(f16, 0.85);
+            }
+            assert_eq!(factory.precision_performance[&DataPrecision::F32].quality_score, 0.95);
+        }
+    }
+
     #[test]
     fn test_find_best_factory() {
         let manager = standard_test_manager!(cpu_gpu);
@@ -9,6 +9,11 @@ pub mod capabilities;
 pub mod custom;
 pub mod world;
 pub mod manager;
+pub mod framebuffer;
+pub mod rasterizer;
+pub mod visibility;
+pub mod picking;
+pub mod sorting;
 
 use std::any::TypeId;
 use std::fmt::{self, Debug};
@@ -19,7 +24,14 @@ use tokio::sync::mpsc::{UnboundedReceiver};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::pin::Pin;
 use std::future::Future;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use framebuffer::Framebuffer;
+use factory::ReferenceRendererConfig;
+use crate::numerics::types::traits::FloatingPoint;
+use rasterizer::{RasterQuality, RasterSplat, TileRasterizer};
+use visibility::LodHierarchy;
+use world::storage::{PrecisionSplatStorage, SplatStorage};
+use world::{check_splat_count, Camera, SceneUpdate, World};
 
 // Global counter for generating unique renderer IDs
 static NEXT_RENDERER_ID: AtomicU64 = AtomicU64::new(1);
@@ -29,6 +41,22 @@ pub fn generate_renderer_id() -> u64 {
     NEXT_RENDERER_ID.fetch_add(1, Ordering::SeqCst)
 }
 
+/// Apply a `WorldSubmitted` or `SceneUpdateSubmitted` event to `renderer`.
+///
+/// A failure is sent back as a `RendererError` from the renderer. Returns any
+/// other event unhandled, so `run` loops can fall through to their own cases.
+pub(crate) fn handle_scene_event<R: Renderer + ?Sized>(renderer: &mut R, event: RendererEvent) -> Option<RendererEvent> {
+    let result = match event {
+        RendererEvent::WorldSubmitted { world, .. } => renderer.set_world(Arc::unwrap_or_clone(world)),
+        RendererEvent::SceneUpdateSubmitted { update, .. } => renderer.submit_scene_update(Arc::unwrap_or_clone(update)),
+        other => return Some(other),
+    };
+    if let Err(message) = result {
+        let _ = renderer.sender().send(RendererEvent::RendererError { renderer_id: renderer.unique_id(), message });
+    }
+    None
+}
+
 /// Updated RendererEvent enum with renderer_id instead of RendererId
 #[derive(Debug, Clone)]
 pub enum RendererEvent {
@@ -69,6 +97,18 @@ pub enum RendererEvent {
         splat_count: usize
     },
 
+    /// A world has been submitted to a renderer, replacing its current world.
+    WorldSubmitted {
+        renderer_id: u64,
+        world: Arc<World>,
+    },
+
+    /// An incremental scene update has been submitted to a renderer.
+    SceneUpdateSubmitted {
+        renderer_id: u64,
+        update: Arc<SceneUpdate>,
+    },
+
     /// A frame has been rendered.
     FrameRendered {
         renderer_id: u64,  // ← This is the key change!
@@ -114,6 +154,30 @@ pub trait Renderer: Send + Sync + Debug {
     /// Render a single frame
     fn render_frame(&mut self) -> Result<(), String>;
 
+    /// Bind a world to this renderer, replacing any previously bound world.
+    ///
+    /// Renderers emit `RendererEvent::SplatDataUpdated` once the splat data
+    /// has been uploaded. Renderers that cannot consume scene data return an
+    /// error, which is the default behavior.
+    fn set_world(&mut self, _world: World) -> Result<(), String> {
+        Err(format!("{} does not support scene submission", self.name()))
+    }
+
+    /// Apply an incremental update to the bound world.
+    ///
+    /// Emits `RendererEvent::SplatDataUpdated` after the update has been uploaded.
+    fn submit_scene_update(&mut self, _update: SceneUpdate) -> Result<(), String> {
+        Err(format!("{} does not support scene submission", self.name()))
+    }
+
+    /// Read back the most recently rendered frame.
+    ///
+    /// Renderers that cannot provide pixel data return an error, which is the
+    /// default behavior.
+    fn read_framebuffer(&self) -> Result<Framebuffer, String> {
+        Err(format!("{} does not support framebuffer readback", self.name()))
+    }
+
     /// Get the sender for communicating with this renderer
     fn sender(&self) -> async_communication::BufferedAsyncSender<RendererEvent>;
 
@@ -185,6 +249,9 @@ pub trait ProcessingUnitCapability: Capability {
 /// testing, validation, and as a fallback when specialized renderers are
 /// not available. It supports all standard data precisions and provides
 /// CPU/GPU unified rendering capabilities.
+///
+/// Frames are produced by the tile-based CPU rasterizer in [`rasterizer`],
+/// which makes this renderer the ground truth other backends are compared against.
 #[derive(Debug)]
 pub struct ReferenceRenderer {
     /// Unique ID for this renderer instance - generated once, never changes
@@ -199,6 +266,33 @@ pub struct ReferenceRenderer {
     /// Total number of frames rendered
     frame_count: u64,
 
+    /// Camera of the bound world
+    camera: Camera,
+
+    /// Background color of the bound world
+    background_color: [f64; 4],
+
+    /// The bound world's splats in `precision`; frames are rendered from
+    /// these rows and scene updates are applied to them in place
+    splat_data: PrecisionSplatStorage,
+
+    /// Viewport dimensions (width, height)
+    viewport_size: (u32, u32),
+
+    /// Output of the most recent frame
+    framebuffer: Framebuffer,
+
+    rasterizer: TileRasterizer,
+
+    /// Largest world this renderer accepts
+    max_splat_count: usize,
+
+    /// Screen-space error threshold for level-of-detail selection, if enabled
+    lod_threshold: Option<f64>,
+
+    /// Cluster hierarchy over the rows of `splat_data`, built on the first frame that needs it
+    lod: Option<LodHierarchy>,
+
     sender: BufferedAsyncSender<RendererEvent>,
     receiver: UnboundedReceiver<RendererEvent>
 }
@@ -209,11 +303,22 @@ impl ReferenceRenderer {
         let id = generate_renderer_id();
         let (sender, receiver) = BufferedAsyncSender::<RendererEvent>::new_unbounded(None);
 
+        let precision = DataPrecision::F32;
+
         Self {
             id,  // ← Unique ID generated here
-            precision: DataPrecision::F32,
+            precision,
             is_running: false,
             frame_count: 0,
+            camera: Camera::new(),
+            background_color: [0.0, 0.0, 0.0, 1.0],
+            splat_data: PrecisionSplatStorage::new(precision),
+            viewport_size: (1920, 1080), // Default to 1080p
+            framebuffer: Framebuffer::new(0, 0),
+            rasterizer: TileRasterizer::new().with_threads(num_cpus::get()),
+            max_splat_count: ReferenceRendererConfig::default().max_splat_count,
+            lod_threshold: None,
+            lod: None,
             sender,
             receiver,
         }
@@ -228,10 +333,124 @@ impl ReferenceRenderer {
             precision,
             is_running: false,
             frame_count: 0,
+            camera: Camera::new(),
+            background_color: [0.0, 0.0, 0.0, 1.0],
+            splat_data: PrecisionSplatStorage::new(precision),
+            viewport_size: (1920, 1080), // Default to 1080p
+            framebuffer: Framebuffer::new(0, 0),
+            rasterizer: TileRasterizer::new().with_threads(num_cpus::get()),
+            max_splat_count: ReferenceRendererConfig::default().max_splat_count,
+            lod_threshold: None,
+            lod: None,
             sender: buffered_sender,
             receiver: buffered_receiver
         }
     }
+
+    /// Create a reference renderer from a validated configuration.
+    ///
+    /// Tiles are composited on `config.threads` worker threads, `config.quality`
+    /// selects the [`RasterQuality`] preset, and worlds larger than
+    /// `config.max_splat_count` are rejected.
+    pub fn from_config(config: &ReferenceRendererConfig) -> Result<Self, String> {
+        config.validate()?;
+        let quality = RasterQuality::from_name(&config.quality)?;
+
+        let mut renderer = Self::with_precision(config.precision);
+        renderer.viewport_size = config.viewport_size;
+        renderer.rasterizer = TileRasterizer::new().with_threads(config.threads).with_quality(quality);
+        renderer.max_splat_count = config.max_splat_count;
+        Ok(renderer)
+    }
+
+    /// Number of threads compositing tiles.
+    pub fn threads(&self) -> usize {
+        self.rasterizer.threads()
+    }
+
+    /// Largest number of splats this renderer accepts.
+    pub fn max_splat_count(&self) -> usize {
+        self.max_splat_count
+    }
+
+    /// Splat data in the current precision, as used for rendering.
+    pub fn splat_data(&self) -> &PrecisionSplatStorage {
+        &self.splat_data
+    }
+
+    /// Camera frames are rendered from.
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    /// Notify listeners that the splat data has been uploaded.
+    fn emit_splat_data_updated(&self) {
+        let _ = self.sender.send(RendererEvent::SplatDataUpdated {
+            renderer_id: self.id,
+            splat_count: self.splat_data.len(),
+        });
+    }
+
+    /// Update the viewport size. Takes effect on the next frame.
+    pub fn set_viewport_size(&mut self, width: u32, height: u32) {
+        self.viewport_size = (width, height);
+    }
+
+    /// Get the current viewport size.
+    pub fn viewport_size(&self) -> (u32, u32) {
+        self.viewport_size
+    }
+
+    /// Enable level-of-detail selection with a screen-space error threshold
+    /// in pixels, or disable it with `None`.
+    ///
+    /// Frustum culling and LOD run through the shared visibility pass in
+    /// [`visibility`].
+    pub fn set_lod_threshold(&mut self, threshold: Option<f64>) {
+        self.lod_threshold = threshold;
+    }
+
+    /// Get the level-of-detail threshold, if enabled.
+    pub fn lod_threshold(&self) -> Option<f64> {
+        self.lod_threshold
+    }
+
+    /// Get the framebuffer produced by the most recent frame.
+    ///
+    /// The framebuffer is empty until the first frame has been rendered.
+    pub fn framebuffer(&self) -> &Framebuffer {
+        &self.framebuffer
+    }
+}
+
+/// Borrowed renderer state for rasterizing one frame from typed splat storage.
+struct Frame<'a> {
+    rasterizer: &'a TileRasterizer,
+    camera: &'a Camera,
+    background_color: [f64; 4],
+    lod_threshold: Option<f64>,
+    lod: &'a mut Option<LodHierarchy>,
+    framebuffer: &'a mut Framebuffer,
+}
+
+impl Frame<'_> {
+    /// Rasterize the storage rows, or the level-of-detail selection over them.
+    fn render<T: FloatingPoint>(&mut self, storage: &SplatStorage<T>) {
+        let Some(threshold) = self.lod_threshold else {
+            self.rasterizer.render_splats(storage.iter(), self.camera, self.background_color, self.framebuffer);
+            return;
+        };
+        let lod = self
+            .lod
+            .get_or_insert_with(|| LodHierarchy::from_splats(&storage.iter().collect::<Vec<_>>()));
+        let visible = lod.select(self.camera, self.framebuffer.width(), self.framebuffer.height(), threshold);
+        let detailed: Vec<_> = visible.detailed.iter().filter_map(|&index| storage.get(index)).collect();
+        let splats = detailed
+            .iter()
+            .map(|splat| splat as &dyn RasterSplat)
+            .chain(visible.merged.iter().map(|splat| splat as &dyn RasterSplat));
+        self.rasterizer.render_splats(splats, self.camera, self.background_color, self.framebuffer);
+    }
 }
 
 impl Capability for ReferenceRenderer {
@@ -291,15 +510,36 @@ impl Renderer for ReferenceRenderer {
             return Err("Renderer is not running".to_string());
         }
 
-        // Do rendering work...
+        let frame_start = Instant::now();
+
+        let (width, height) = self.viewport_size;
+        if self.framebuffer.width() != width || self.framebuffer.height() != height {
+            self.framebuffer.resize(width, height);
+        }
+        let mut frame = Frame {
+            rasterizer: &self.rasterizer,
+            camera: &self.camera,
+            background_color: self.background_color,
+            lod_threshold: self.lod_threshold,
+            lod: &mut self.lod,
+            framebuffer: &mut self.framebuffer,
+        };
+        match &self.splat_data {
+            PrecisionSplatStorage::F16(storage) => frame.render(storage),
+            PrecisionSplatStorage::F32(storage) => frame.render(storage),
+            PrecisionSplatStorage::F64(storage) => frame.render(storage),
+            PrecisionSplatStorage::BFloat16(storage) => frame.render(storage),
+        }
+
+        let frame_time = frame_start.elapsed();
         self.frame_count += 1;
 
         // Emit event with renderer_id instead of RendererId
         let event = RendererEvent::FrameRendered {
             renderer_id: self.unique_id(),  // ← Use unique_id()
             frame_number: self.frame_count,
-            frame_time_microseconds: 16666, // ~60fps
-            render_time_ns: 1_000_000,
+            frame_time_microseconds: frame_time.as_micros() as u64,
+            render_time_ns: frame_time.as_nanos() as u64,
         };
 
         // Send event (ignore if no subscribers)
@@ -308,15 +548,48 @@ impl Renderer for ReferenceRenderer {
         Ok(())
     }
 
+    fn set_world(&mut self, world: World) -> Result<(), String> {
+        check_splat_count(world.splat_count(), self.max_splat_count)?;
+        self.splat_data = PrecisionSplatStorage::from_world(&world, self.precision);
+        self.camera = world.camera;
+        self.background_color = world.background_color;
+        self.lod = None;
+        self.emit_splat_data_updated();
+        Ok(())
+    }
+
+    fn submit_scene_update(&mut self, update: SceneUpdate) -> Result<(), String> {
+        check_splat_count(update.resulting_splat_count(self.splat_data.len()), self.max_splat_count)?;
+        self.splat_data.apply_update(&update)?;
+        if let Some(camera) = update.camera {
+            self.camera = camera;
+        }
+        if !(update.added.is_empty() && update.modified.is_empty() && update.removed.is_empty()) {
+            self.lod = None;
+        }
+        self.emit_splat_data_updated();
+        Ok(())
+    }
+
+    fn read_framebuffer(&self) -> Result<Framebuffer, String> {
+        if self.frame_count == 0 {
+            return Err("No frame has been rendered yet".to_string());
+        }
+        Ok(self.framebuffer.clone())
+    }
+
     fn set_data_precision(&mut self, precision: DataPrecision) -> Result<DataPrecision, String> {
         if !self.supports_precision(precision) {
             return Err(format!("Unsupported precision: {}", precision));
         }
 
-        self.precision = precision;
-
-        // In a real implementation, this would trigger a DataPrecisionChanged event
-        // through some event system
+        if precision != self.precision {
+            self.precision = precision;
+            self.splat_data = self.splat_data.to_precision(precision);
+            // The hierarchy was clustered from the rows at the old precision
+            self.lod = None;
+            self.emit_splat_data_updated();
+        }
 
         Ok(precision)
     }
@@ -354,7 +627,9 @@ impl Renderer for ReferenceRenderer {
                         println!("ReferenceRenderer switched {:?}", active);
                     }
                     other => {
-                        println!("ReferenceRenderer ignoring {:?}", other);
+                        if let Some(other) = handle_scene_event(self, other) {
+                            println!("ReferenceRenderer ignoring {:?}", other);
+                        }
                     }
                 }
             }
@@ -383,57 +658,22 @@ impl ReferenceRendererFactory {
 
 impl RendererFactory for ReferenceRendererFactory {
     fn create(&self, precision: DataPrecision, parameters: &str) -> Result<Box<dyn Renderer>, RendererError> {
-        // Parse parameters if any (reference renderer accepts minimal parameters)
-        if !parameters.is_empty() {
-            let params = factory::parse_parameters(parameters);
-            for (key, _) in params {
-                match key.as_str() {
-                    "precision" => {}, // Handled by precision parameter
-                    _ => {
-                        return Err(RendererError::InvalidParameters(
-                            format!("Unknown parameter for ReferenceRenderer: {}", key)
-                        ));
-                    }
-                }
-            }
-        }
-
-        Ok(Box::new(ReferenceRenderer::with_precision(precision)))
+        let config = ReferenceRendererConfig::from_factory_parameters(precision, parameters)?;
+        let renderer = ReferenceRenderer::from_config(&config).map_err(RendererError::CreationFailed)?;
+        Ok(Box::new(renderer))
     }
 
     fn get_info(&self) -> RendererInfo {
-        let mut parameters = std::collections::HashMap::new();
-        parameters.insert(
-            "precision".to_string(),
-            "Data precision for rendering (f16, f32, f64, bfloat16)".to_string()
-        );
-
         RendererInfo::new(
             self.factory_name.clone(),
             "reference,cpu,basic_rendering,all_precisions".to_string(),
-            parameters,
+            ReferenceRendererConfig::parameter_descriptions(),
             1000, // 1ms timeout
         )
     }
 
-    fn validate_parameters(&self, _precision: DataPrecision, parameters: &str) -> Result<(), RendererError> {
-        if parameters.is_empty() {
-            return Ok(());
-        }
-
-        let params = factory::parse_parameters(parameters);
-        for (key, _) in params {
-            match key.as_str() {
-                "precision" => {},
-                _ => {
-                    return Err(RendererError::InvalidParameters(
-                        format!("Unknown parameter for ReferenceRenderer: {}", key)
-                    ));
-                }
-            }
-        }
-
-        Ok(())
+    fn validate_parameters(&self, precision: DataPrecision, parameters: &str) -> Result<(), RendererError> {
+        ReferenceRendererConfig::from_factory_parameters(precision, parameters).map(|_| ())
     }
 }
 
@@ -643,6 +883,210 @@ mod tests {
         assert!(result.unwrap_err().contains("not running"));
     }
 
+    #[test]
+    fn test_reference_renderer_rasterizes_world() {
+        use crate::renderer::world::{Camera, GaussianSplat, Point3D};
+
+        let mut world = World::new();
+        world.set_camera(Camera::new().with_aspect_ratio(1.0));
+        world.add_splat(
+            GaussianSplat::new(0, Point3D::origin())
+                .with_color(0.0, 1.0, 0.0, 1.0)
+                .with_covariance([0.05, 0.0, 0.0, 0.05, 0.0, 0.05]),
+        );
+
+        let mut renderer = ReferenceRenderer::new();
+        renderer.set_world(world).unwrap();
+        renderer.set_viewport_size(32, 32);
+        assert_eq!(renderer.framebuffer().pixel_count(), 0);
+
+        renderer.start().unwrap();
+        renderer.render_frame().unwrap();
+
+        let framebuffer = renderer.framebuffer();
+        assert_eq!((framebuffer.width(), framebuffer.height()), (32, 32));
+        let center = framebuffer.pixel(16, 16).unwrap();
+        assert!(center[1] > 0.9);
+        assert_eq!(framebuffer.pixel(0, 0), Some([0.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_reference_renderer_level_of_detail() {
+        use crate::renderer::world::{Camera, GaussianSplat, Point3D};
+
+        // A dense cluster far from the camera
+        let mut world = World::new();
+        world.set_camera(Camera::new().with_aspect_ratio(1.0));
+        for i in 0..64 {
+            let offset = (i as f64 / 64.0) * 0.2;
+            world.add_splat(
+                GaussianSplat::new(0, Point3D::new(offset, offset, -200.0))
+                    .with_color(1.0, 0.0, 0.0, 1.0)
+                    .with_covariance([0.01, 0.0, 0.0, 0.01, 0.0, 0.01]),
+            );
+        }
+
+        let mut renderer = ReferenceRenderer::new();
+        renderer.set_world(world).unwrap();
+        renderer.set_viewport_size(32, 32);
+        renderer.start().unwrap();
+        renderer.render_frame().unwrap();
+        let detailed = renderer.framebuffer().clone();
+
+        renderer.set_lod_threshold(Some(2.0));
+        assert_eq!(renderer.lod_threshold(), Some(2.0));
+        renderer.render_frame().unwrap();
+
+        // The merged cluster covers the same spot on screen. A single splat
+        // saturates below the stacked detail, but stays clearly visible.
+        let center = renderer.framebuffer().pixel(16, 16).unwrap();
+        assert!(detailed.pixel(16, 16).unwrap()[0] > 0.9);
+        assert!(center[0] > 0.3);
+        assert_eq!(center[1], 0.0);
+        assert_eq!(renderer.framebuffer().pixel(0, 0), detailed.pixel(0, 0));
+
+        // Switching precision drops the hierarchy so it is rebuilt from the recast rows
+        assert!(renderer.lod.is_some());
+        renderer.set_data_precision(DataPrecision::F16).unwrap();
+        assert!(renderer.lod.is_none());
+        renderer.render_frame().unwrap();
+        assert!(renderer.lod.is_some());
+    }
+
+    #[test]
+    fn test_reference_renderer_from_config() {
+        use crate::renderer::world::{GaussianSplat, Point3D};
+
+        let config = ReferenceRendererConfig::from_parameters(
+            DataPrecision::F64,
+            "threads=3,quality=high,max_splat_count=2,viewport_size=64x48",
+        )
+        .unwrap();
+        let mut renderer = ReferenceRenderer::from_config(&config).unwrap();
+        assert_eq!(renderer.threads(), 3);
+        assert_eq!(renderer.max_splat_count(), 2);
+        assert_eq!(renderer.viewport_size(), (64, 48));
+        assert_eq!(renderer.get_data_precision(), DataPrecision::F64);
+
+        // Scenes over the limit are rejected and leave the world untouched
+        let mut world = World::new();
+        for _ in 0..3 {
+            world.add_splat_at(Point3D::origin());
+        }
+        let error = renderer.set_world(world).unwrap_err();
+        assert!(error.contains("exceeds max_splat_count 2"));
+        assert_eq!(renderer.splat_data().len(), 0);
+
+        let update = SceneUpdate::new()
+            .with_added(GaussianSplat::new(0, Point3D::origin()))
+            .with_added(GaussianSplat::new(0, Point3D::origin()));
+        renderer.submit_scene_update(update).unwrap();
+        let update = SceneUpdate::new().with_added(GaussianSplat::new(0, Point3D::origin()));
+        assert!(renderer.submit_scene_update(update).is_err());
+        assert_eq!(renderer.splat_data().len(), 2);
+
+        renderer.start().unwrap();
+        renderer.render_frame().unwrap();
+        assert_eq!(renderer.framebuffer().width(), 64);
+
+        let invalid = ReferenceRendererConfig { threads: 0, ..ReferenceRendererConfig::default() };
+        assert!(ReferenceRenderer::from_config(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_reference_renderer_factory_parameters() {
+        let factory = ReferenceRendererFactory::new();
+        let renderer = factory.create(DataPrecision::F32, "threads=2,quality=ultra,debug").unwrap();
+        assert_eq!(renderer.get_data_precision(), DataPrecision::F32);
+        assert!(factory.get_info().get_parameter_description("max_splat_count").is_some());
+
+        match factory.create(DataPrecision::F32, "threads=0") {
+            Err(RendererError::InvalidParameters(message)) => assert!(message.contains("threads must be greater than 0")),
+            other => panic!("Expected InvalidParameters, got {:?}", other.map(|_| ())),
+        }
+        assert!(factory.validate_parameters(DataPrecision::F32, "quality=extreme").is_err());
+        assert!(factory.validate_parameters(DataPrecision::F32, "frobnicate=1").is_err());
+    }
+
+    #[test]
+    fn test_framebuffer_readback_through_trait_object() {
+        let mut renderer: Box<dyn Renderer> = Box::new(ReferenceRenderer::new());
+        assert!(renderer.read_framebuffer().is_err());
+
+        renderer.start().unwrap();
+        renderer.render_frame().unwrap();
+
+        let framebuffer = renderer.read_framebuffer().unwrap();
+        assert_eq!((framebuffer.width(), framebuffer.height()), (1920, 1080));
+        assert_eq!(framebuffer.to_rgba8().len(), 1920 * 1080 * 4);
+
+        // Renderers without pixel output report an error
+        let mock: Box<dyn Renderer> = Box::new(MockRenderer::new("Mock", DataPrecision::F32));
+        let result = mock.read_framebuffer();
+        assert!(result.unwrap_err().contains("does not support framebuffer readback"));
+    }
+
+    #[test]
+    fn test_reference_renderer_scene_submission() {
+        use crate::renderer::world::{Camera, GaussianSplat, Point3D};
+
+        let mut renderer = ReferenceRenderer::new();
+        let mut world = World::new();
+        let id = world.add_splat_at(Point3D::origin());
+        let kept = world.add_splat_at(Point3D::new(1.0, 0.0, 0.0));
+
+        renderer.set_world(world).unwrap();
+        assert_eq!(renderer.splat_data().len(), 2);
+
+        let update = SceneUpdate::new()
+            .with_removed(id)
+            .with_modified(GaussianSplat::new(kept, Point3D::new(5.0, 0.0, 0.0)))
+            .with_added(GaussianSplat::new(0, Point3D::new(0.0, 1.0, 0.0)))
+            .with_added(GaussianSplat::new(kept, Point3D::new(0.0, 2.0, 0.0)))
+            .with_camera(Camera::new().with_aspect_ratio(2.0));
+        renderer.submit_scene_update(update).unwrap();
+        assert_eq!(renderer.splat_data().len(), 3);
+        assert_eq!(renderer.camera().aspect_ratio, 2.0);
+
+        // Deltas land in place: the modified row keeps its ID and added
+        // splats with a missing or taken ID get fresh ones
+        let PrecisionSplatStorage::F32(storage) = renderer.splat_data() else { panic!("Expected f32 storage") };
+        let row = storage.position_of(kept).unwrap();
+        assert_eq!(storage.positions()[row], [5.0, 0.0, 0.0]);
+        let mut ids = storage.ids().to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 3);
+
+        // Invalid updates are rejected without modifying the bound world
+        let result = renderer.submit_scene_update(SceneUpdate::new().with_removed(id));
+        assert!(result.is_err());
+        assert_eq!(renderer.splat_data().len(), 3);
+
+        // Renderers without scene support report an error
+        let mut mock: Box<dyn Renderer> = Box::new(MockRenderer::new("Mock", DataPrecision::F32));
+        assert!(mock.set_world(World::new()).unwrap_err().contains("does not support scene submission"));
+    }
+
+    #[tokio::test]
+    async fn test_reference_renderer_reacts_to_scene_events() {
+        use crate::renderer::world::{GaussianSplat, Point3D};
+
+        let mut renderer = ReferenceRenderer::new();
+        let sender = renderer.sender();
+
+        let mut world = World::new();
+        world.add_splat_at(Point3D::origin());
+        sender.send(RendererEvent::WorldSubmitted { renderer_id: renderer.unique_id(), world: Arc::new(world) }).unwrap();
+
+        let update = SceneUpdate::new().with_added(GaussianSplat::new(0, Point3D::new(1.0, 0.0, 0.0)));
+        sender.send(RendererEvent::SceneUpdateSubmitted { renderer_id: renderer.unique_id(), update: Arc::new(update) }).unwrap();
+        sender.send(RendererEvent::Shutdown(renderer.unique_id())).unwrap();
+
+        renderer.run().await;
+        assert_eq!(renderer.splat_data().len(), 2);
+    }
+
     #[test]
     fn test_reference_renderer_precision_support() {
         let renderer = ReferenceRenderer::new();
@@ -682,6 +1126,48 @@ mod tests {
         assert_eq!(result.unwrap(), DataPrecision::F64);
     }
 
+    #[test]
+    fn test_reference_renderer_stores_selected_precision() {
+        use crate::renderer::world::{Camera, GaussianSplat, Point3D};
+
+        let mut world = World::new();
+        world.set_camera(Camera::new().with_aspect_ratio(1.0));
+        for i in 0..16 {
+            world.add_splat(
+                GaussianSplat::new(0, Point3D::new(i as f64 * 0.013 - 0.1, 0.0, 0.0))
+                    .with_color(0.3, 0.6, 0.9, 1.0)
+                    .with_opacity(0.4)
+                    .with_covariance([0.011, 0.0, 0.0, 0.011, 0.0, 0.011]),
+            );
+        }
+
+        let mut renderer = ReferenceRenderer::new();
+        renderer.set_world(world).unwrap();
+        renderer.set_viewport_size(32, 32);
+        renderer.start().unwrap();
+        renderer.render_frame().unwrap();
+        let reference = renderer.framebuffer().clone();
+        let f32_bytes = renderer.splat_data().memory_bytes();
+        assert_eq!(renderer.splat_data().precision(), DataPrecision::F32);
+        assert_eq!(renderer.splat_data().len(), 16);
+
+        renderer.set_data_precision(DataPrecision::F16).unwrap();
+        assert_eq!(renderer.splat_data().precision(), DataPrecision::F16);
+        assert!(renderer.splat_data().memory_bytes() < f32_bytes);
+
+        // Half precision shifts the image only by rounding error
+        renderer.render_frame().unwrap();
+        let center = renderer.framebuffer().pixel(16, 16).unwrap();
+        let expected = reference.pixel(16, 16).unwrap();
+        for channel in 0..3 {
+            assert!((center[channel] - expected[channel]).abs() < 1e-2);
+        }
+
+        renderer.set_data_precision(DataPrecision::F64).unwrap();
+        assert!(renderer.splat_data().memory_bytes() > f32_bytes);
+        assert_eq!(renderer.splat_data().len(), 16);
+    }
+
     #[test]
     fn test_capability_trait() {
         let renderer = ReferenceRenderer::new();
@@ -19,5 +19,12 @@ pub use super::async_communication::sender::BufferedAsyncSender;
 // Capability system
 pub use super::capabilities;
 
+// CPU rasterization
+pub use super::framebuffer::Framebuffer;
+pub use super::rasterizer::TileRasterizer;
+
 // World and scene management
-pub use super::world::{World, Camera, GaussianSplat, Point3D, PrecisionPoint3D};
\ No newline at end of file
+pub use super::world::{World, Camera, GaussianSplat, Point3D, PrecisionPoint3D, Ray, SceneUpdate};
+pub use super::world::storage::SplatStorage;
+pub use crate::scene::primitive::Splat;
+pub use crate::scene::spherical_harmonics::SphericalHarmonics;
@@ -0,0 +1,942 @@
+//! Tile-based CPU rasterizer for 3D Gaussian splats.
+//!
+//! The rasterizer follows the standard 3D Gaussian Splatting pipeline:
+//!
+//! 1. Project each splat's 3D covariance to a 2D screen-space ellipse
+//!    (EWA splatting with a small low-pass filter).
+//! 2. Bin the projected ellipses into fixed-size screen tiles.
+//! 3. Depth-sort the splats once, so every tile's list is already in order.
+//! 4. Alpha-composite front to back until the pixel is saturated.
+//!
+//! Tiles are independent once binned, so step 4 runs on a pool of worker
+//! threads that take tiles from a shared counter. `RasterQuality` trades
+//! speed for fidelity through supersampling and the compositing cutoffs.
+//!
+//! Screen space uses pixel coordinates with the origin at the top-left
+//! corner and pixel centers at half-integer positions. View space is
+//! right-handed with the camera looking down the negative Z axis.
+
+use crate::numerics::types::traits::FloatingPoint;
+use crate::renderer::framebuffer::Framebuffer;
+use crate::renderer::sorting::{quantize_depths, radix_sort, SortOrder};
+use crate::renderer::world::storage::SplatRef;
+use crate::renderer::world::{Camera, GaussianSplat, Point3D, World};
+use crate::scene::spherical_harmonics::evaluate_coefficients;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Default edge length of a screen tile in pixels.
+pub const DEFAULT_TILE_SIZE: u32 = 16;
+
+/// Variance added to the 2D covariance diagonal so every splat covers at least one pixel.
+const LOW_PASS_FILTER: f64 = 0.3;
+
+/// Contributions below this alpha are skipped.
+pub(crate) const MIN_ALPHA: f64 = 1.0 / 255.0;
+
+/// Upper bound on a single splat's alpha, keeps transmittance from collapsing to zero.
+pub(crate) const MAX_ALPHA: f64 = 0.99;
+
+/// Compositing stops once the remaining transmittance falls below this value.
+const TRANSMITTANCE_CUTOFF: f64 = 1e-4;
+
+/// Screen-space extent of a splat in standard deviations.
+const SIGMA_EXTENT: f64 = 3.0;
+
+/// Camera parameters resolved for a specific viewport.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewParameters {
+    /// World-to-view rotation (rows are the camera right, up and backward axes)
+    pub rotation: [[f64; 3]; 3],
+
+    /// World-to-view translation
+    pub translation: [f64; 3],
+
+    /// Focal lengths in pixels (x, y)
+    pub focal: [f64; 2],
+
+    /// Principal point in pixels (x, y)
+    pub principal_point: [f64; 2],
+
+    /// Tangent of half the field of view (x, y)
+    pub tan_half_fov: [f64; 2],
+
+    /// Near clipping plane distance
+    pub near: f64,
+
+    /// Far clipping plane distance
+    pub far: f64,
+
+    /// Viewport dimensions (width, height)
+    pub viewport_size: (u32, u32),
+
+    /// Camera position in world space, used for view-dependent color
+    pub camera_position: Point3D,
+}
+
+impl ViewParameters {
+    /// Resolve the camera for a viewport of the given size.
+    pub fn new(camera: &Camera, width: u32, height: u32) -> Self {
+        let view = camera.view_matrix().data;
+        let rotation = core::array::from_fn(|row| [view[row][0], view[row][1], view[row][2]]);
+        let translation = [view[0][3], view[1][3], view[2][3]];
+
+        let tan_half_fov_y = (camera.fov.to_radians() * 0.5).tan();
+        let tan_half_fov_x = tan_half_fov_y * camera.aspect_ratio;
+
+        Self {
+            rotation,
+            translation,
+            focal: [
+                width as f64 / (2.0 * tan_half_fov_x),
+                height as f64 / (2.0 * tan_half_fov_y),
+            ],
+            principal_point: [width as f64 * 0.5, height as f64 * 0.5],
+            tan_half_fov: [tan_half_fov_x, tan_half_fov_y],
+            near: camera.near,
+            far: camera.far,
+            viewport_size: (width, height),
+            camera_position: camera.position.clone(),
+        }
+    }
+
+    /// Transform a world-space point into view space.
+    pub fn world_to_view(&self, point: [f64; 3]) -> [f64; 3] {
+        let r = &self.rotation;
+        let t = &self.translation;
+        [
+            r[0][0] * point[0] + r[0][1] * point[1] + r[0][2] * point[2] + t[0],
+            r[1][0] * point[0] + r[1][1] * point[1] + r[1][2] * point[2] + t[1],
+            r[2][0] * point[0] + r[2][1] * point[1] + r[2][2] * point[2] + t[2],
+        ]
+    }
+}
+
+/// A splat projected to screen space, ready for compositing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectedSplat {
+    /// Identifier of the source splat
+    pub splat_id: u64,
+
+    /// Center in pixel coordinates
+    pub center: [f64; 2],
+
+    /// Distance from the camera along the viewing direction
+    pub depth: f64,
+
+    /// Inverse 2D covariance stored as (xx, xy, yy)
+    pub conic: [f64; 3],
+
+    /// Conservative screen-space radius in pixels
+    pub radius: f64,
+
+    /// RGB color (0.0 to 1.0)
+    pub color: [f64; 3],
+
+    /// Peak opacity at the splat center
+    pub opacity: f64,
+}
+
+impl ProjectedSplat {
+    /// Evaluate the splat's alpha contribution at a pixel position.
+    pub fn alpha_at(&self, x: f64, y: f64) -> f64 {
+        let dx = x - self.center[0];
+        let dy = y - self.center[1];
+        let power = -0.5 * (self.conic[0] * dx * dx + self.conic[2] * dy * dy) - self.conic[1] * dx * dy;
+        if power > 0.0 {
+            return 0.0;
+        }
+        (self.opacity * power.exp()).min(MAX_ALPHA)
+    }
+}
+
+/// The splat attributes the rasterizer reads, widened to `f64`.
+///
+/// Implemented by `GaussianSplat` and by borrowed rows of typed splat
+/// storage, so renderers can rasterize either without converting.
+pub trait RasterSplat {
+    /// Identifier of the splat
+    fn id(&self) -> u64;
+
+    /// Position [x, y, z]
+    fn position(&self) -> [f64; 3];
+
+    /// Packed covariance `[xx, xy, xz, yy, yz, zz]`
+    fn covariance(&self) -> [f64; 6];
+
+    /// RGBA color
+    fn color(&self) -> [f64; 4];
+
+    /// Opacity
+    fn opacity(&self) -> f64;
+
+    /// RGB color seen from a camera at `viewpoint`.
+    fn view_color(&self, viewpoint: &Point3D) -> [f64; 3];
+}
+
+impl RasterSplat for GaussianSplat {
+    fn id(&self) -> u64 {
+        self.id
+    }
+
+    fn position(&self) -> [f64; 3] {
+        [self.position.x, self.position.y, self.position.z]
+    }
+
+    fn covariance(&self) -> [f64; 6] {
+        self.covariance
+    }
+
+    fn color(&self) -> [f64; 4] {
+        self.color
+    }
+
+    fn opacity(&self) -> f64 {
+        self.opacity
+    }
+
+    fn view_color(&self, viewpoint: &Point3D) -> [f64; 3] {
+        self.color_from(viewpoint)
+    }
+}
+
+impl<T: FloatingPoint> RasterSplat for SplatRef<'_, T> {
+    fn id(&self) -> u64 {
+        self.id
+    }
+
+    fn position(&self) -> [f64; 3] {
+        self.position.map(|c| c.to_f64())
+    }
+
+    fn covariance(&self) -> [f64; 6] {
+        self.covariance.map(|c| c.to_f64())
+    }
+
+    fn color(&self) -> [f64; 4] {
+        self.color.map(|c| c.to_f64())
+    }
+
+    fn opacity(&self) -> f64 {
+        self.opacity.to_f64()
+    }
+
+    fn view_color(&self, viewpoint: &Point3D) -> [f64; 3] {
+        if self.sh_coefficients.is_empty() {
+            let [r, g, b, _] = self.color();
+            return [r, g, b];
+        }
+        let [x, y, z] = self.position();
+        evaluate_coefficients(self.sh_coefficients, [x - viewpoint.x, y - viewpoint.y, z - viewpoint.z])
+    }
+}
+
+impl<S: RasterSplat + ?Sized> RasterSplat for &S {
+    fn id(&self) -> u64 {
+        (**self).id()
+    }
+
+    fn position(&self) -> [f64; 3] {
+        (**self).position()
+    }
+
+    fn covariance(&self) -> [f64; 6] {
+        (**self).covariance()
+    }
+
+    fn color(&self) -> [f64; 4] {
+        (**self).color()
+    }
+
+    fn opacity(&self) -> f64 {
+        (**self).opacity()
+    }
+
+    fn view_color(&self, viewpoint: &Point3D) -> [f64; 3] {
+        (**self).view_color(viewpoint)
+    }
+}
+
+/// Project a single splat to screen space.
+///
+/// Returns `None` if the splat lies outside the near/far range, is entirely
+/// off screen, is fully transparent, or has a degenerate covariance.
+pub fn project_splat(splat: &(impl RasterSplat + ?Sized), view: &ViewParameters) -> Option<ProjectedSplat> {
+    let opacity = splat.opacity() * splat.color()[3];
+    if opacity < MIN_ALPHA {
+        return None;
+    }
+
+    let view_position = view.world_to_view(splat.position());
+    let depth = -view_position[2];
+    if depth <= view.near || depth >= view.far {
+        return None;
+    }
+
+    // Clamp the point used for the Jacobian to avoid extreme distortion near the frustum edge
+    let limit_x = 1.3 * view.tan_half_fov[0];
+    let limit_y = 1.3 * view.tan_half_fov[1];
+    let tx = (view_position[0] / depth).clamp(-limit_x, limit_x) * depth;
+    let ty = (view_position[1] / depth).clamp(-limit_y, limit_y) * depth;
+
+    // Jacobian of the perspective projection evaluated at the splat center
+    let [fx, fy] = view.focal;
+    let jacobian = [
+        [fx / depth, 0.0, fx * tx / (depth * depth)],
+        [0.0, -fy / depth, -fy * ty / (depth * depth)],
+    ];
+
+    // T = J * W, where W is the world-to-view rotation
+    let mut t = [[0.0; 3]; 2];
+    for (row, jacobian_row) in t.iter_mut().zip(jacobian.iter()) {
+        for (column, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| jacobian_row[k] * view.rotation[k][column]).sum();
+        }
+    }
+
+    let c = splat.covariance();
+    let sigma = [[c[0], c[1], c[2]], [c[1], c[3], c[4]], [c[2], c[4], c[5]]];
+
+    // cov2d = T * Sigma * T^T
+    let mut t_sigma = [[0.0; 3]; 2];
+    for (row, t_row) in t_sigma.iter_mut().zip(t.iter()) {
+        for (column, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| t_row[k] * sigma[k][column]).sum();
+        }
+    }
+    let dot = |a: &[f64; 3], b: &[f64; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+    let a = dot(&t_sigma[0], &t[0]) + LOW_PASS_FILTER;
+    let b = dot(&t_sigma[0], &t[1]);
+    let d = dot(&t_sigma[1], &t[1]) + LOW_PASS_FILTER;
+
+    let determinant = a * d - b * b;
+    if determinant <= 0.0 || !determinant.is_finite() {
+        return None;
+    }
+
+    let conic = [d / determinant, -b / determinant, a / determinant];
+
+    let mid = 0.5 * (a + d);
+    let largest_eigenvalue = mid + (mid * mid - determinant).max(0.1).sqrt();
+    let radius = (SIGMA_EXTENT * largest_eigenvalue.sqrt()).ceil();
+
+    let center = [
+        view.principal_point[0] + fx * view_position[0] / depth,
+        view.principal_point[1] - fy * view_position[1] / depth,
+    ];
+
+    let (width, height) = view.viewport_size;
+    if center[0] + radius < 0.0
+        || center[1] + radius < 0.0
+        || center[0] - radius > width as f64
+        || center[1] - radius > height as f64
+    {
+        return None;
+    }
+
+    Some(ProjectedSplat {
+        splat_id: splat.id(),
+        center,
+        depth,
+        conic,
+        radius,
+        color: splat.view_color(&view.camera_position),
+        opacity,
+    })
+}
+
+/// Sampling and cutoff settings for compositing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RasterQuality {
+    /// Samples per pixel along each axis; the pixel color is their average
+    pub supersampling: u32,
+
+    /// Contributions below this alpha are skipped
+    pub min_alpha: f64,
+
+    /// Compositing stops once the remaining transmittance falls below this value
+    pub transmittance_cutoff: f64,
+}
+
+impl RasterQuality {
+    /// One sample per pixel and early termination.
+    pub fn low() -> Self {
+        Self {
+            supersampling: 1,
+            min_alpha: 2.0 / 255.0,
+            transmittance_cutoff: 1e-2,
+        }
+    }
+
+    /// One sample per pixel with the standard cutoffs.
+    pub fn medium() -> Self {
+        Self {
+            supersampling: 1,
+            min_alpha: MIN_ALPHA,
+            transmittance_cutoff: TRANSMITTANCE_CUTOFF,
+        }
+    }
+
+    /// 2x2 supersampling with the standard cutoffs.
+    pub fn high() -> Self {
+        Self {
+            supersampling: 2,
+            ..Self::medium()
+        }
+    }
+
+    /// 4x4 supersampling and tighter cutoffs.
+    pub fn ultra() -> Self {
+        Self {
+            supersampling: 4,
+            min_alpha: 0.5 / 255.0,
+            transmittance_cutoff: 1e-5,
+        }
+    }
+
+    /// Look up a preset by the names accepted by `ReferenceRendererConfig`.
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "low" => Ok(Self::low()),
+            "medium" => Ok(Self::medium()),
+            "high" => Ok(Self::high()),
+            "ultra" => Ok(Self::ultra()),
+            _ => Err(format!(
+                "Invalid quality: {}. Must be one of: low, medium, high, ultra",
+                name
+            )),
+        }
+    }
+}
+
+impl Default for RasterQuality {
+    fn default() -> Self {
+        Self::medium()
+    }
+}
+
+/// Statistics collected while rasterizing a frame.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RasterStatistics {
+    /// Number of splats that survived projection and culling
+    pub projected_splats: usize,
+
+    /// Number of splats rejected during projection
+    pub culled_splats: usize,
+
+    /// Number of tiles covering the viewport
+    pub tile_count: usize,
+
+    /// Total number of (tile, splat) overlaps
+    pub tile_splat_pairs: usize,
+}
+
+/// Tile-based Gaussian splat rasterizer.
+#[derive(Debug, Clone)]
+pub struct TileRasterizer {
+    tile_size: u32,
+    threads: usize,
+    quality: RasterQuality,
+}
+
+impl TileRasterizer {
+    /// Create a single-threaded rasterizer with the default tile size and quality.
+    pub fn new() -> Self {
+        Self {
+            tile_size: DEFAULT_TILE_SIZE,
+            threads: 1,
+            quality: RasterQuality::default(),
+        }
+    }
+
+    /// Set the number of worker threads compositing tiles (minimum 1).
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Set the sampling and cutoff settings.
+    pub fn with_quality(mut self, quality: RasterQuality) -> Self {
+        self.quality = RasterQuality {
+            supersampling: quality.supersampling.max(1),
+            ..quality
+        };
+        self
+    }
+
+    /// Get the number of worker threads.
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Get the sampling and cutoff settings.
+    pub fn quality(&self) -> RasterQuality {
+        self.quality
+    }
+
+    /// Set the tile edge length in pixels (minimum 1).
+    pub fn with_tile_size(mut self, tile_size: u32) -> Self {
+        self.tile_size = tile_size.max(1);
+        self
+    }
+
+    /// Get the tile edge length in pixels.
+    pub fn tile_size(&self) -> u32 {
+        self.tile_size
+    }
+
+    /// Number of tiles along each axis for a viewport.
+    pub fn tile_grid(&self, width: u32, height: u32) -> (u32, u32) {
+        (width.div_ceil(self.tile_size), height.div_ceil(self.tile_size))
+    }
+
+    /// Rasterize the world's splats from its camera into the framebuffer.
+    ///
+    /// The framebuffer dimensions define the viewport.
+    pub fn render(&self, world: &World, framebuffer: &mut Framebuffer) -> RasterStatistics {
+        self.render_splats(world.splats().iter(), &world.camera, world.background_color, framebuffer)
+    }
+
+    /// Rasterize an explicit list of splats, borrowed or owned, such as the
+    /// output of the visibility pass in [`crate::renderer::visibility`] or
+    /// rows of typed splat storage.
+    pub fn render_splats(
+        &self,
+        splats: impl IntoIterator<Item = impl RasterSplat>,
+        camera: &Camera,
+        background: [f64; 4],
+        framebuffer: &mut Framebuffer,
+    ) -> RasterStatistics {
+        let width = framebuffer.width();
+        let height = framebuffer.height();
+        let view = ViewParameters::new(camera, width, height);
+
+        let mut splat_count = 0;
+        let projected: Vec<ProjectedSplat> = splats
+            .into_iter()
+            .inspect(|_| splat_count += 1)
+            .filter_map(|splat| project_splat(&splat, &view))
+            .collect();
+
+        let depths: Vec<f64> = projected.iter().map(|splat| splat.depth).collect();
+        let order = radix_sort(&quantize_depths(&depths, SortOrder::FrontToBack));
+        let bins = self.bin_splats(&projected, &order, width, height);
+
+        let statistics = RasterStatistics {
+            projected_splats: projected.len(),
+            culled_splats: splat_count - projected.len(),
+            tile_count: bins.len(),
+            tile_splat_pairs: bins.iter().map(Vec::len).sum(),
+        };
+
+        let (tiles_x, _) = self.tile_grid(width, height);
+        let composite = |tile_index: usize| {
+            let tile_x = tile_index as u32 % tiles_x;
+            let tile_y = tile_index as u32 / tiles_x;
+            self.composite_tile(&projected, &bins[tile_index], tile_x, tile_y, background, (width, height))
+        };
+
+        let workers = self.threads.min(bins.len());
+        if workers <= 1 {
+            for tile_index in 0..bins.len() {
+                let pixels = composite(tile_index);
+                self.write_tile(tile_index as u32 % tiles_x, tile_index as u32 / tiles_x, &pixels, framebuffer);
+            }
+            return statistics;
+        }
+
+        // Workers pull tiles from a shared counter so uneven tiles balance out
+        let next_tile = AtomicUsize::new(0);
+        let finished: Vec<Vec<(usize, Vec<[f32; 4]>)>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut tiles = Vec::new();
+                        loop {
+                            let tile_index = next_tile.fetch_add(1, Ordering::Relaxed);
+                            if tile_index >= bins.len() {
+                                break tiles;
+                            }
+                            tiles.push((tile_index, composite(tile_index)));
+                        }
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|handle| handle.join().expect("tile worker panicked")).collect()
+        });
+
+        for (tile_index, pixels) in finished.into_iter().flatten() {
+            self.write_tile(tile_index as u32 % tiles_x, tile_index as u32 / tiles_x, &pixels, framebuffer);
+        }
+
+        statistics
+    }
+
+    /// Assign each projected splat to every tile its screen-space extent overlaps.
+    ///
+    /// Splats are visited in depth `order`, so every bin comes out sorted.
+    fn bin_splats(&self, projected: &[ProjectedSplat], order: &[usize], width: u32, height: u32) -> Vec<Vec<usize>> {
+        let (tiles_x, tiles_y) = self.tile_grid(width, height);
+        let mut bins = vec![Vec::new(); (tiles_x * tiles_y) as usize];
+        if tiles_x == 0 || tiles_y == 0 {
+            return bins;
+        }
+
+        let tile_size = self.tile_size as f64;
+        for &index in order {
+            let splat = &projected[index];
+            let min_x = ((splat.center[0] - splat.radius) / tile_size).floor().max(0.0) as u32;
+            let min_y = ((splat.center[1] - splat.radius) / tile_size).floor().max(0.0) as u32;
+            let max_x = (((splat.center[0] + splat.radius) / tile_size).floor().max(0.0) as u32).min(tiles_x - 1);
+            let max_y = (((splat.center[1] + splat.radius) / tile_size).floor().max(0.0) as u32).min(tiles_y - 1);
+
+            for tile_y in min_y..=max_y {
+                for tile_x in min_x..=max_x {
+                    bins[(tile_y * tiles_x + tile_x) as usize].push(index);
+                }
+            }
+        }
+
+        bins
+    }
+
+    /// Composite a tile's depth-sorted splats front to back.
+    ///
+    /// Returns the tile's pixels in row-major order, clipped to the viewport.
+    fn composite_tile(
+        &self,
+        projected: &[ProjectedSplat],
+        bin: &[usize],
+        tile_x: u32,
+        tile_y: u32,
+        background: [f64; 4],
+        (width, height): (u32, u32),
+    ) -> Vec<[f32; 4]> {
+        let x_start = tile_x * self.tile_size;
+        let y_start = tile_y * self.tile_size;
+        let x_end = (x_start + self.tile_size).min(width);
+        let y_end = (y_start + self.tile_size).min(height);
+
+        let pixel_count = ((x_end - x_start) * (y_end - y_start)) as usize;
+        if bin.is_empty() {
+            return vec![background.map(|channel| channel as f32); pixel_count];
+        }
+
+        let samples = self.quality.supersampling;
+        let sample_weight = 1.0 / (samples * samples) as f64;
+        let mut pixels = Vec::with_capacity(pixel_count);
+
+        for y in y_start..y_end {
+            for x in x_start..x_end {
+                let mut pixel = [0.0; 4];
+                for sample_y in 0..samples {
+                    for sample_x in 0..samples {
+                        let sample = self.composite_sample(
+                            projected,
+                            bin,
+                            x as f64 + (sample_x as f64 + 0.5) / samples as f64,
+                            y as f64 + (sample_y as f64 + 0.5) / samples as f64,
+                            background,
+                        );
+                        for (value, sample_value) in pixel.iter_mut().zip(sample) {
+                            *value += sample_value * sample_weight;
+                        }
+                    }
+                }
+                pixels.push(pixel.map(|channel| channel as f32));
+            }
+        }
+
+        pixels
+    }
+
+    /// Composite the splats covering one sample position over the background.
+    fn composite_sample(
+        &self,
+        projected: &[ProjectedSplat],
+        bin: &[usize],
+        sample_x: f64,
+        sample_y: f64,
+        background: [f64; 4],
+    ) -> [f64; 4] {
+        let mut color = [0.0; 3];
+        let mut transmittance = 1.0;
+
+        for &index in bin {
+            let splat = &projected[index];
+            let alpha = splat.alpha_at(sample_x, sample_y);
+            if alpha < self.quality.min_alpha {
+                continue;
+            }
+
+            for (channel, value) in color.iter_mut().enumerate() {
+                *value += splat.color[channel] * alpha * transmittance;
+            }
+            transmittance *= 1.0 - alpha;
+
+            if transmittance < self.quality.transmittance_cutoff {
+                break;
+            }
+        }
+
+        [
+            color[0] + transmittance * background[0],
+            color[1] + transmittance * background[1],
+            color[2] + transmittance * background[2],
+            1.0 - transmittance + transmittance * background[3],
+        ]
+    }
+
+    /// Copy a composited tile into the framebuffer.
+    fn write_tile(&self, tile_x: u32, tile_y: u32, pixels: &[[f32; 4]], framebuffer: &mut Framebuffer) {
+        let width = framebuffer.width() as usize;
+        let x_start = (tile_x * self.tile_size) as usize;
+        let y_start = (tile_y * self.tile_size) as usize;
+        let tile_width = (x_start + self.tile_size as usize).min(width) - x_start;
+        if tile_width == 0 {
+            return;
+        }
+
+        let target = framebuffer.pixels_mut();
+        for (row, row_pixels) in pixels.chunks(tile_width).enumerate() {
+            let start = (y_start + row) * width + x_start;
+            target[start..start + tile_width].copy_from_slice(row_pixels);
+        }
+    }
+}
+
+impl Default for TileRasterizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::spherical_harmonics::SphericalHarmonics;
+
+    fn test_world() -> World {
+        let mut world = World::new();
+        world.set_camera(Camera::new().with_aspect_ratio(1.0));
+        world
+    }
+
+    fn small_splat(position: Point3D, color: [f64; 3]) -> GaussianSplat {
+        GaussianSplat::new(0, position)
+            .with_color(color[0], color[1], color[2], 1.0)
+            .with_covariance([0.05, 0.0, 0.0, 0.05, 0.0, 0.05])
+    }
+
+    #[test]
+    fn test_view_parameters_center_projection() {
+        let camera = Camera::new().with_aspect_ratio(1.0);
+        let view = ViewParameters::new(&camera, 64, 64);
+
+        // The camera target lands in front of the camera at the default distance
+        let view_position = view.world_to_view([0.0, 0.0, 0.0]);
+        assert!(view_position[0].abs() < 1e-12);
+        assert!(view_position[1].abs() < 1e-12);
+        assert!((view_position[2] + 5.0).abs() < 1e-12);
+
+        assert_eq!(view.principal_point, [32.0, 32.0]);
+        assert!((view.focal[0] - view.focal[1]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_project_splat() {
+        let view = ViewParameters::new(&Camera::new().with_aspect_ratio(1.0), 64, 64);
+
+        let splat = small_splat(Point3D::new(0.0, 0.0, 0.0), [1.0, 0.0, 0.0]);
+        let projected = project_splat(&splat, &view).unwrap();
+        assert!((projected.center[0] - 32.0).abs() < 1e-9);
+        assert!((projected.center[1] - 32.0).abs() < 1e-9);
+        assert!((projected.depth - 5.0).abs() < 1e-9);
+        assert!(projected.radius > 0.0);
+
+        // Points above the camera axis land in the upper half of the image
+        let above = small_splat(Point3D::new(0.0, 1.0, 0.0), [1.0, 0.0, 0.0]);
+        let projected = project_splat(&above, &view).unwrap();
+        assert!(projected.center[1] < 32.0);
+
+        // Splats behind the camera are culled
+        let behind = small_splat(Point3D::new(0.0, 0.0, 10.0), [1.0, 0.0, 0.0]);
+        assert!(project_splat(&behind, &view).is_none());
+
+        // Fully transparent splats are culled
+        let transparent = small_splat(Point3D::new(0.0, 0.0, 0.0), [1.0, 0.0, 0.0]).with_opacity(0.0);
+        assert!(project_splat(&transparent, &view).is_none());
+    }
+
+    #[test]
+    fn test_project_splat_view_dependent_color() {
+        let camera = Camera::new().with_aspect_ratio(1.0);
+        let view = ViewParameters::new(&camera, 64, 64);
+
+        // Band 1, coefficient 2 responds to +z; the default camera looks down -z
+        let mut harmonics = SphericalHarmonics::from_color([0.5, 0.5, 0.5], 1).unwrap();
+        harmonics.coefficients_mut()[2] = [0.5, 0.0, 0.0];
+        let splat = small_splat(Point3D::origin(), [0.5, 0.5, 0.5]).with_spherical_harmonics(harmonics);
+
+        let projected = project_splat(&splat, &view).unwrap();
+        let direction = [0.0, 0.0, -camera.position.z];
+        assert_eq!(projected.color, splat.color_for_direction(direction));
+        assert!(projected.color[0] < 0.5);
+        assert!((projected.color[1] - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_projection_matches_camera() {
+        let camera = Camera::new()
+            .with_position(Point3D::new(3.0, 2.0, 4.0))
+            .with_target(Point3D::new(0.0, 0.5, 0.0))
+            .with_aspect_ratio(1.5);
+        let view = ViewParameters::new(&camera, 96, 64);
+
+        let position = Point3D::new(0.4, 0.8, -0.3);
+        let projected = project_splat(&small_splat(position.clone(), [1.0, 1.0, 1.0]), &view).unwrap();
+        let expected = camera.world_to_screen(&position, 96, 64).unwrap();
+
+        assert!((projected.center[0] - expected[0]).abs() < 1e-9);
+        assert!((projected.center[1] - expected[1]).abs() < 1e-9);
+        assert!((projected.depth - expected[2]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_projected_alpha_falloff() {
+        let view = ViewParameters::new(&Camera::new().with_aspect_ratio(1.0), 64, 64);
+        let splat = small_splat(Point3D::new(0.0, 0.0, 0.0), [1.0, 1.0, 1.0]);
+        let projected = project_splat(&splat, &view).unwrap();
+
+        let center_alpha = projected.alpha_at(32.0, 32.0);
+        let offset_alpha = projected.alpha_at(34.0, 32.0);
+        let far_alpha = projected.alpha_at(60.0, 60.0);
+
+        assert!((center_alpha - MAX_ALPHA).abs() < 1e-9);
+        assert!(offset_alpha < center_alpha);
+        assert!(far_alpha < MIN_ALPHA);
+    }
+
+    #[test]
+    fn test_render_single_splat() {
+        let mut world = test_world();
+        world.add_splat(small_splat(Point3D::new(0.0, 0.0, 0.0), [1.0, 0.0, 0.0]));
+
+        let mut framebuffer = Framebuffer::new(64, 64);
+        let statistics = TileRasterizer::new().render(&world, &mut framebuffer);
+
+        assert_eq!(statistics.projected_splats, 1);
+        assert_eq!(statistics.culled_splats, 0);
+        assert_eq!(statistics.tile_count, 16);
+        assert!(statistics.tile_splat_pairs >= 1);
+
+        let center = framebuffer.pixel(32, 32).unwrap();
+        assert!(center[0] > 0.9);
+        assert!(center[1] < 0.05);
+        assert!(center[3] > 0.9);
+
+        // Corners show the opaque black background
+        assert_eq!(framebuffer.pixel(0, 0), Some([0.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_render_front_to_back_order() {
+        let mut world = test_world();
+        // Blue is added first but sits behind red
+        world.add_splat(small_splat(Point3D::new(0.0, 0.0, -1.0), [0.0, 0.0, 1.0]));
+        world.add_splat(small_splat(Point3D::new(0.0, 0.0, 1.0), [1.0, 0.0, 0.0]));
+
+        let mut framebuffer = Framebuffer::new(64, 64);
+        TileRasterizer::new().render(&world, &mut framebuffer);
+
+        let center = framebuffer.pixel(32, 32).unwrap();
+        assert!(center[0] > center[2]);
+        assert!(center[0] > 0.9);
+    }
+
+    #[test]
+    fn test_render_background_only() {
+        let mut world = test_world();
+        world.set_background_color(0.25, 0.5, 0.75, 1.0);
+
+        let mut framebuffer = Framebuffer::new(20, 10);
+        let statistics = TileRasterizer::new().with_tile_size(8).render(&world, &mut framebuffer);
+
+        assert_eq!(statistics.tile_count, 6);
+        assert_eq!(statistics.projected_splats, 0);
+        assert!(framebuffer.pixels().iter().all(|p| *p == [0.25, 0.5, 0.75, 1.0]));
+    }
+
+    #[test]
+    fn test_render_storage_rows_matches_world() {
+        use crate::renderer::world::storage::SplatStorage;
+
+        let mut world = test_world();
+        world.add_splat(small_splat(Point3D::new(0.3, 0.0, 0.0), [1.0, 0.0, 0.0]).with_opacity(0.7));
+        world.add_splat(
+            small_splat(Point3D::new(-0.3, 0.2, 0.5), [0.0, 0.0, 0.0])
+                .with_spherical_harmonics(SphericalHarmonics::from_color([0.2, 0.7, 0.4], 2).unwrap()),
+        );
+
+        // Borrowed f64 rows carry exactly the world's values, SH included
+        let storage = SplatStorage::<f64>::from(&world);
+        let mut expected = Framebuffer::new(32, 32);
+        let mut rows = Framebuffer::new(32, 32);
+        let rasterizer = TileRasterizer::new();
+        rasterizer.render(&world, &mut expected);
+        rasterizer.render_splats(storage.iter(), &world.camera, world.background_color, &mut rows);
+        assert_eq!(rows, expected);
+    }
+
+    #[test]
+    fn test_tile_grid() {
+        let rasterizer = TileRasterizer::new();
+        assert_eq!(rasterizer.tile_size(), DEFAULT_TILE_SIZE);
+        assert_eq!(rasterizer.tile_grid(64, 64), (4, 4));
+        assert_eq!(rasterizer.tile_grid(65, 17), (5, 2));
+        assert_eq!(TileRasterizer::new().with_tile_size(0).tile_size(), 1);
+    }
+
+    #[test]
+    fn test_multithreaded_render_matches_single_threaded() {
+        let mut world = test_world();
+        world.set_background_color(0.1, 0.1, 0.1, 1.0);
+        for i in 0..40 {
+            let t = i as f64 * 0.45;
+            world.add_splat(
+                small_splat(Point3D::new(t.sin() * 1.5, t.cos() * 1.5, -(i as f64) * 0.05), [0.2, 0.5, 0.8])
+                    .with_opacity(0.6),
+            );
+        }
+
+        // An uneven viewport exercises the clipped edge tiles
+        let mut single = Framebuffer::new(70, 45);
+        let mut parallel = Framebuffer::new(70, 45);
+        let rasterizer = TileRasterizer::new().with_tile_size(8);
+        rasterizer.render(&world, &mut single);
+        rasterizer.clone().with_threads(4).render(&world, &mut parallel);
+
+        assert_eq!(rasterizer.with_threads(0).threads(), 1);
+        assert_eq!(single, parallel);
+    }
+
+    #[test]
+    fn test_quality_presets() {
+        assert_eq!(RasterQuality::from_name("medium").unwrap(), RasterQuality::default());
+        assert_eq!(RasterQuality::from_name("ultra").unwrap().supersampling, 4);
+        assert!(RasterQuality::from_name("extreme").unwrap_err().contains("Invalid quality"));
+
+        let mut world = test_world();
+        world.add_splat(small_splat(Point3D::new(0.0, 0.0, 0.0), [1.0, 0.0, 0.0]));
+
+        let mut medium = Framebuffer::new(32, 32);
+        let mut high = Framebuffer::new(32, 32);
+        TileRasterizer::new().render(&world, &mut medium);
+        TileRasterizer::new().with_quality(RasterQuality::high()).render(&world, &mut high);
+
+        // Supersampling averages sub-pixel samples: close to, but not exactly, one sample per pixel
+        let (center, supersampled) = (medium.pixel(16, 16).unwrap(), high.pixel(16, 16).unwrap());
+        assert_ne!(center, supersampled);
+        assert!((center[0] - supersampled[0]).abs() < 0.1);
+        assert_eq!(high.pixel(0, 0), medium.pixel(0, 0));
+    }
+}
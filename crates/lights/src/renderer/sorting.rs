@@ -0,0 +1,493 @@
+//! Depth ordering of splats for alpha blending.
+//!
+//! Splats are ordered by view-space depth, quantized to 32-bit keys over the
+//! depth range of the frame and sorted with a stable LSD radix sort:
+//!
+//! - `radix_sort` sorts on a single thread.
+//! - `parallel_radix_sort` sorts one chunk per thread and merges the chunks
+//!   pairwise, also in parallel.
+//! - `DepthSorter` keeps the previous frame's order. When the camera moved
+//!   only slightly it repairs that order with an insertion pass, which is
+//!   linear for nearly sorted input, and falls back to a full sort when the
+//!   order changed too much.
+//!
+//! All variants produce the same order: ties keep their input order.
+
+use crate::numerics::batch::{BatchKernels, Points};
+use crate::numerics::types::matrix::Matrix4x4;
+use crate::renderer::factory::ReferenceRendererConfig;
+use crate::renderer::world::{Camera, GaussianSplat};
+
+/// Default camera translation, in world units, below which `DepthSorter`
+/// reuses the previous order.
+pub const DEFAULT_INCREMENTAL_TRANSLATION: f64 = 0.1;
+
+/// Default camera rotation, in radians, below which `DepthSorter` reuses the
+/// previous order.
+pub const DEFAULT_INCREMENTAL_ROTATION: f64 = 0.035;
+
+/// Element moves allowed per splat in an incremental pass before falling back to a full sort.
+const INCREMENTAL_MOVE_BUDGET: usize = 8;
+
+/// Inputs smaller than this are sorted on one thread.
+const MIN_PARALLEL_LEN: usize = 4096;
+
+/// Direction in which sorted splats are composited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    /// Nearest splat first, for front-to-back compositing
+    #[default]
+    FrontToBack,
+
+    /// Farthest splat first, for back-to-front (painter's) compositing
+    BackToFront,
+}
+
+/// How `DepthSorter` produced its most recent order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortMode {
+    /// Keys were radix sorted from scratch
+    Full,
+
+    /// The previous order was repaired in place
+    Incremental,
+}
+
+/// Quantize depths to keys preserving their order.
+///
+/// Finite depths are mapped linearly from the smallest to the largest onto
+/// `0..u32::MAX`; `u32::MAX` is reserved so non-finite depths sort last in
+/// either order.
+pub fn quantize_depths(depths: &[f64], order: SortOrder) -> Vec<u32> {
+    let (min, max) = depths
+        .iter()
+        .filter(|depth| depth.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &depth| (min.min(depth), max.max(depth)));
+    let scale = if max > min { (u32::MAX - 1) as f64 / (max - min) } else { 0.0 };
+
+    depths
+        .iter()
+        .map(|&depth| {
+            if !depth.is_finite() {
+                return u32::MAX;
+            }
+            let key = ((depth - min) * scale) as u32;
+            match order {
+                SortOrder::FrontToBack => key,
+                SortOrder::BackToFront => u32::MAX - 1 - key,
+            }
+        })
+        .collect()
+}
+
+/// Stable radix sort of `keys`, returning the sorted permutation of indices.
+pub fn radix_sort(keys: &[u32]) -> Vec<usize> {
+    let mut entries = pack(keys);
+    sort_entries(&mut entries);
+    unpack(&entries)
+}
+
+/// Stable radix sort of `keys` using up to `threads` threads.
+///
+/// The result is identical to `radix_sort`.
+pub fn parallel_radix_sort(keys: &[u32], threads: usize) -> Vec<usize> {
+    let threads = threads.max(1).min(keys.len().div_ceil(MIN_PARALLEL_LEN).max(1));
+    if threads == 1 {
+        return radix_sort(keys);
+    }
+
+    let mut entries = pack(keys);
+    let chunk_len = entries.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        for chunk in entries.chunks_mut(chunk_len) {
+            scope.spawn(|| sort_entries(chunk));
+        }
+    });
+
+    // Merge neighbouring runs until one remains; each round merges in parallel
+    let mut scratch = vec![0; entries.len()];
+    let mut run_len = chunk_len;
+    while run_len < entries.len() {
+        std::thread::scope(|scope| {
+            for (source, target) in entries.chunks(2 * run_len).zip(scratch.chunks_mut(2 * run_len)) {
+                scope.spawn(move || {
+                    let (left, right) = source.split_at(run_len.min(source.len()));
+                    merge(left, right, target);
+                });
+            }
+        });
+        std::mem::swap(&mut entries, &mut scratch);
+        run_len *= 2;
+    }
+
+    unpack(&entries)
+}
+
+/// View-space depth of every splat: the distance along the camera's viewing direction.
+///
+/// Positions are taken relative to the camera in `f64`, then the depths are
+/// computed in `f32` by the SIMD batch kernels, which is ample for ordering.
+pub fn view_depths(splats: &[GaussianSplat], camera: &Camera) -> Vec<f64> {
+    let (_, _, forward) = camera.basis();
+    let eye = &camera.position;
+    let x: Vec<f32> = splats.iter().map(|splat| (splat.position.x - eye.x) as f32).collect();
+    let y: Vec<f32> = splats.iter().map(|splat| (splat.position.y - eye.y) as f32).collect();
+    let z: Vec<f32> = splats.iter().map(|splat| (splat.position.z - eye.z) as f32).collect();
+
+    // The kernel negates the third row, which is the backward axis of a view matrix
+    let backward = forward.map(|c| -c as f32);
+    let mut view = Matrix4x4::<f32>::identity();
+    view.data[2] = [backward[0], backward[1], backward[2], 0.0];
+
+    let mut depths = vec![0.0; splats.len()];
+    BatchKernels::new()
+        .view_depths(&view, Points::new(&x, &y, &z), &mut depths)
+        .expect("depth buffers have one entry per splat");
+    depths.into_iter().map(f64::from).collect()
+}
+
+/// Camera pose the previous order was computed for.
+#[derive(Debug, Clone)]
+struct PreviousView {
+    position: [f64; 3],
+    forward: [f64; 3],
+    count: usize,
+}
+
+/// Reusable depth sorter that keeps the order between frames.
+#[derive(Debug, Clone)]
+pub struct DepthSorter {
+    order: SortOrder,
+    threads: usize,
+    max_translation: f64,
+    max_rotation: f64,
+    previous: Option<PreviousView>,
+    indices: Vec<usize>,
+    last_mode: SortMode,
+}
+
+impl DepthSorter {
+    /// Create a single-threaded front-to-back sorter.
+    pub fn new() -> Self {
+        Self {
+            order: SortOrder::FrontToBack,
+            threads: 1,
+            max_translation: DEFAULT_INCREMENTAL_TRANSLATION,
+            max_rotation: DEFAULT_INCREMENTAL_ROTATION,
+            previous: None,
+            indices: Vec::new(),
+            last_mode: SortMode::Full,
+        }
+    }
+
+    /// Create a sorter using the thread count of a reference renderer configuration.
+    pub fn from_config(config: &ReferenceRendererConfig) -> Self {
+        Self::new().with_threads(config.threads)
+    }
+
+    /// Set the compositing order.
+    pub fn with_order(mut self, order: SortOrder) -> Self {
+        self.order = order;
+        self.invalidate();
+        self
+    }
+
+    /// Set the number of threads used for full sorts (minimum 1).
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Set how far the camera may move and turn between frames for the
+    /// previous order to be reused. Zero disables incremental sorting.
+    pub fn with_incremental_limits(mut self, max_translation: f64, max_rotation: f64) -> Self {
+        self.max_translation = max_translation.max(0.0);
+        self.max_rotation = max_rotation.max(0.0);
+        self
+    }
+
+    /// Get the compositing order.
+    pub fn sort_order(&self) -> SortOrder {
+        self.order
+    }
+
+    /// Get the number of threads used for full sorts.
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// How the most recent order was produced.
+    pub fn last_mode(&self) -> SortMode {
+        self.last_mode
+    }
+
+    /// The most recent order as indices into the sorted input.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Forget the previous order so the next sort starts from scratch.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+
+    /// Sort arbitrary depths from scratch.
+    pub fn sort_depths(&mut self, depths: &[f64]) -> &[usize] {
+        self.previous = None;
+        let keys = quantize_depths(depths, self.order);
+        self.indices = parallel_radix_sort(&keys, self.threads);
+        self.last_mode = SortMode::Full;
+        &self.indices
+    }
+
+    /// Sort splats by their depth from `camera`.
+    ///
+    /// The previous order is reused when the splat count is unchanged and the
+    /// camera stayed within the incremental limits. Splats are identified by
+    /// position in the slice, so invalidate the sorter after reordering them.
+    pub fn sort_splats(&mut self, splats: &[GaussianSplat], camera: &Camera) -> &[usize] {
+        let (_, _, forward) = camera.basis();
+        let view = PreviousView {
+            position: [camera.position.x, camera.position.y, camera.position.z],
+            forward,
+            count: splats.len(),
+        };
+        let keys = quantize_depths(&view_depths(splats, camera), self.order);
+
+        let reusable = self.previous.as_ref().is_some_and(|previous| self.is_small_motion(previous, &view));
+        if !(reusable && insertion_repair(&mut self.indices, &keys)) {
+            self.indices = parallel_radix_sort(&keys, self.threads);
+            self.last_mode = SortMode::Full;
+        } else {
+            self.last_mode = SortMode::Incremental;
+        }
+
+        self.previous = Some(view);
+        &self.indices
+    }
+
+    fn is_small_motion(&self, previous: &PreviousView, current: &PreviousView) -> bool {
+        if previous.count != current.count || self.indices.len() != current.count {
+            return false;
+        }
+        let translation = previous
+            .position
+            .iter()
+            .zip(&current.position)
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f64>()
+            .sqrt();
+        let cosine: f64 = previous.forward.iter().zip(&current.forward).map(|(a, b)| a * b).sum();
+        translation <= self.max_translation && cosine.clamp(-1.0, 1.0).acos() <= self.max_rotation
+    }
+}
+
+impl Default for DepthSorter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pack each key above its index so entries compare by key only when shifted.
+fn pack(keys: &[u32]) -> Vec<u64> {
+    keys.iter()
+        .enumerate()
+        .map(|(index, &key)| ((key as u64) << 32) | index as u64)
+        .collect()
+}
+
+fn unpack(entries: &[u64]) -> Vec<usize> {
+    entries.iter().map(|entry| (entry & u32::MAX as u64) as usize).collect()
+}
+
+/// LSD radix sort on the key half of packed entries, one byte per pass.
+fn sort_entries(entries: &mut [u64]) {
+    let mut scratch = vec![0; entries.len()];
+    let mut source_is_entries = true;
+
+    for pass in 0..4 {
+        let shift = 32 + 8 * pass;
+        let (source, target): (&[u64], &mut [u64]) = if source_is_entries {
+            (entries, &mut scratch)
+        } else {
+            (&scratch, entries)
+        };
+
+        let mut counts = [0usize; 256];
+        for entry in source {
+            counts[((entry >> shift) & 0xFF) as usize] += 1;
+        }
+        // Every key shares this byte, so the pass would not move anything
+        if counts.contains(&source.len()) {
+            continue;
+        }
+
+        let mut offsets = [0usize; 256];
+        let mut total = 0;
+        for (offset, count) in offsets.iter_mut().zip(counts) {
+            *offset = total;
+            total += count;
+        }
+        for &entry in source {
+            let bucket = ((entry >> shift) & 0xFF) as usize;
+            target[offsets[bucket]] = entry;
+            offsets[bucket] += 1;
+        }
+        source_is_entries = !source_is_entries;
+    }
+
+    if !source_is_entries {
+        entries.copy_from_slice(&scratch);
+    }
+}
+
+/// Stable merge of two sorted runs, preferring `left` on equal keys.
+fn merge(left: &[u64], right: &[u64], target: &mut [u64]) {
+    let (mut i, mut j) = (0, 0);
+    for slot in target.iter_mut() {
+        let take_left = j >= right.len() || (i < left.len() && left[i] >> 32 <= right[j] >> 32);
+        if take_left {
+            *slot = left[i];
+            i += 1;
+        } else {
+            *slot = right[j];
+            j += 1;
+        }
+    }
+}
+
+/// Re-sort a previous order for new keys by insertion.
+///
+/// Returns `false`, leaving `indices` unspecified, once the number of moves
+/// exceeds the incremental budget.
+fn insertion_repair(indices: &mut [usize], keys: &[u32]) -> bool {
+    let mut budget = indices.len() * INCREMENTAL_MOVE_BUDGET;
+    let rank = |index: usize| ((keys[index] as u64) << 32) | index as u64;
+
+    for position in 1..indices.len() {
+        let current = indices[position];
+        let mut hole = position;
+        while hole > 0 && rank(indices[hole - 1]) > rank(current) {
+            if budget == 0 {
+                return false;
+            }
+            budget -= 1;
+            indices[hole] = indices[hole - 1];
+            hole -= 1;
+        }
+        indices[hole] = current;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::world::Point3D;
+
+    fn pseudo_random_keys(count: usize, seed: u64) -> Vec<u32> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                // Narrow range so ties are common
+                ((state >> 33) % 1000) as u32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_radix_sort_is_stable() {
+        let keys = pseudo_random_keys(5000, 7);
+        let mut expected: Vec<usize> = (0..keys.len()).collect();
+        expected.sort_by_key(|&index| keys[index]);
+
+        assert_eq!(radix_sort(&keys), expected);
+        assert_eq!(parallel_radix_sort(&keys, 1), expected);
+        assert_eq!(parallel_radix_sort(&keys, 3), expected);
+        assert!(radix_sort(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_parallel_radix_sort_large_input() {
+        let keys = pseudo_random_keys(50_000, 11);
+        assert_eq!(parallel_radix_sort(&keys, 4), radix_sort(&keys));
+        assert_eq!(parallel_radix_sort(&keys, 7), radix_sort(&keys));
+    }
+
+    #[test]
+    fn test_quantize_depths() {
+        let depths = [2.0, 1.0, f64::NAN, 3.0, 1.0];
+        let keys = quantize_depths(&depths, SortOrder::FrontToBack);
+        assert_eq!(keys[1], 0);
+        assert_eq!(keys[3], u32::MAX - 1);
+        assert_eq!(keys[2], u32::MAX);
+        assert_eq!(radix_sort(&keys), vec![1, 4, 0, 3, 2]);
+
+        let reversed = quantize_depths(&depths, SortOrder::BackToFront);
+        assert_eq!(radix_sort(&reversed), vec![3, 0, 1, 4, 2]);
+    }
+
+    #[test]
+    fn test_depth_sorter_incremental() {
+        let splats: Vec<GaussianSplat> = (0..200)
+            .map(|i| {
+                let t = i as f64 * 0.7;
+                GaussianSplat::new(i, Point3D::new(t.sin() * 3.0, t.cos() * 3.0, -(i as f64 % 17.0)))
+            })
+            .collect();
+        let camera = Camera::new();
+        let mut sorter = DepthSorter::new().with_threads(2);
+
+        let first = sorter.sort_splats(&splats, &camera).to_vec();
+        assert_eq!(sorter.last_mode(), SortMode::Full);
+        assert_eq!(first, radix_sort(&quantize_depths(&view_depths(&splats, &camera), SortOrder::FrontToBack)));
+
+        // A small step reuses and repairs the previous order
+        let moved = camera.clone().with_position(Point3D::new(0.05, 0.0, 5.0));
+        let repaired = sorter.sort_splats(&splats, &moved).to_vec();
+        assert_eq!(sorter.last_mode(), SortMode::Incremental);
+        assert_eq!(repaired, radix_sort(&quantize_depths(&view_depths(&splats, &moved), SortOrder::FrontToBack)));
+
+        // Turning around is too far
+        let turned = moved.clone().with_target(Point3D::new(0.0, 0.0, 10.0));
+        sorter.sort_splats(&splats, &turned);
+        assert_eq!(sorter.last_mode(), SortMode::Full);
+
+        // So is a different splat count
+        sorter.sort_splats(&splats[..100], &turned);
+        assert_eq!(sorter.last_mode(), SortMode::Full);
+    }
+
+    #[test]
+    fn test_view_depths_far_from_origin() {
+        // Depths are relative to the camera, so a distant scene keeps its precision
+        let offset = Point3D::new(1.0e5, -2.0e5, 3.0e5);
+        let camera = Camera::new()
+            .with_position(Point3D::new(offset.x, offset.y, offset.z + 5.0))
+            .with_target(offset.clone());
+        let splats: Vec<GaussianSplat> = (0..37)
+            .map(|i| {
+                let t = i as f64 * 0.3;
+                GaussianSplat::new(i, Point3D::new(offset.x + t.sin(), offset.y + t.cos(), offset.z - t))
+            })
+            .collect();
+        for (splat, depth) in splats.iter().zip(view_depths(&splats, &camera)) {
+            let expected = offset.z + 5.0 - splat.position.z;
+            assert!((depth - expected).abs() < 1e-5, "{} vs {}", depth, expected);
+        }
+    }
+
+    #[test]
+    fn test_back_to_front() {
+        let splats: Vec<GaussianSplat> = [-1.0, -3.0, -2.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &z)| GaussianSplat::new(i as u64, Point3D::new(0.0, 0.0, z)))
+            .collect();
+        let config = ReferenceRendererConfig { threads: 4, ..Default::default() };
+        let mut sorter = DepthSorter::from_config(&config).with_order(SortOrder::BackToFront);
+        assert_eq!(sorter.threads(), 4);
+        assert_eq!(sorter.sort_splats(&splats, &Camera::new()), &[1, 2, 0]);
+    }
+}
@@ -0,0 +1,470 @@
+//! Visibility pass shared by all renderer backends.
+//!
+//! The pass runs before projection and decides which splats a frame draws:
+//!
+//! 1. Frustum culling: splats whose 3-sigma box lies entirely outside one of
+//!    the six camera planes are dropped.
+//! 2. Level of detail: a `LodHierarchy` clusters splats into a binary tree.
+//!    Every interior node carries a representative splat that matches the
+//!    opacity-weighted mean and covariance of its members, with an opacity
+//!    that preserves their combined cross-section. Traversal stops at
+//!    the first node whose size projects below the screen-space error
+//!    threshold, drawing its representative instead of its members.
+//!
+//! Backends render the resulting `VisibleSplats` like any other splat list.
+
+use crate::numerics::types::matrix::Matrix4x4;
+use crate::renderer::rasterizer::{RasterSplat, ViewParameters};
+use crate::renderer::world::bvh::{Aabb, DEFAULT_SIGMA};
+use crate::renderer::world::{Camera, GaussianSplat, Point3D, World};
+
+/// Default screen-space error threshold in pixels.
+pub const DEFAULT_LOD_THRESHOLD: f64 = 1.0;
+
+/// Weight given to fully transparent splats so merging never divides by zero.
+const MIN_MERGE_WEIGHT: f64 = 1e-6;
+
+/// A plane `normal . p + distance = 0` with a unit normal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    /// Unit normal pointing into the frustum
+    pub normal: [f64; 3],
+
+    /// Signed offset from the origin
+    pub distance: f64,
+}
+
+impl Plane {
+    /// Normalize `a x + b y + c z + d = 0` into a plane.
+    fn from_coefficients(coefficients: [f64; 4]) -> Self {
+        let [a, b, c, d] = coefficients;
+        let length = (a * a + b * b + c * c).sqrt();
+        Self {
+            normal: [a / length, b / length, c / length],
+            distance: d / length,
+        }
+    }
+
+    /// Signed distance from `point` to the plane; positive on the inside.
+    pub fn signed_distance(&self, point: &[f64; 3]) -> f64 {
+        self.normal[0] * point[0] + self.normal[1] * point[1] + self.normal[2] * point[2] + self.distance
+    }
+}
+
+/// The six planes bounding a camera's view volume.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    /// Left, right, bottom, top, near and far planes, normals pointing inwards
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// The frustum of a camera's perspective projection.
+    pub fn from_camera(camera: &Camera) -> Self {
+        Self::from_matrix(&camera.view_projection_matrix())
+    }
+
+    /// Extract the frustum of an OpenGL-style view-projection matrix.
+    pub fn from_matrix(matrix: &Matrix4x4<f64>) -> Self {
+        let m = &matrix.data;
+        let row = |index: usize| m[index];
+        let combine = |a: [f64; 4], b: [f64; 4], sign: f64| -> [f64; 4] { core::array::from_fn(|i| a[i] + sign * b[i]) };
+        Self {
+            planes: [
+                Plane::from_coefficients(combine(row(3), row(0), 1.0)),
+                Plane::from_coefficients(combine(row(3), row(0), -1.0)),
+                Plane::from_coefficients(combine(row(3), row(1), 1.0)),
+                Plane::from_coefficients(combine(row(3), row(1), -1.0)),
+                Plane::from_coefficients(combine(row(3), row(2), 1.0)),
+                Plane::from_coefficients(combine(row(3), row(2), -1.0)),
+            ],
+        }
+    }
+
+    /// Whether `point` lies inside the frustum.
+    pub fn contains_point(&self, point: &Point3D) -> bool {
+        let point = [point.x, point.y, point.z];
+        self.planes.iter().all(|plane| plane.signed_distance(&point) >= 0.0)
+    }
+
+    /// Whether the sphere may overlap the frustum.
+    ///
+    /// Conservative: spheres near a frustum corner can pass without
+    /// actually touching the volume.
+    pub fn intersects_sphere(&self, center: &[f64; 3], radius: f64) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(center) >= -radius)
+    }
+
+    /// Whether the box may overlap the frustum.
+    ///
+    /// Tests the box corner furthest along each plane normal, which is
+    /// conservative in the same way as `intersects_sphere`.
+    pub fn intersects_aabb(&self, bounds: &Aabb) -> bool {
+        self.planes.iter().all(|plane| {
+            let corner = core::array::from_fn(|axis| {
+                if plane.normal[axis] >= 0.0 {
+                    bounds.max[axis]
+                } else {
+                    bounds.min[axis]
+                }
+            });
+            plane.signed_distance(&corner) >= 0.0
+        })
+    }
+}
+
+/// Indices of the splats in `world` whose 3-sigma boxes overlap the frustum.
+pub fn cull_splats(world: &World, frustum: &Frustum) -> Vec<usize> {
+    world
+        .splats()
+        .iter()
+        .enumerate()
+        .filter(|(_, splat)| frustum.intersects_aabb(&Aabb::of_splat(splat, DEFAULT_SIGMA)))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// The splats selected for one frame.
+#[derive(Debug, Clone, Default)]
+pub struct VisibleSplats {
+    /// Indices of original splats drawn at full detail
+    pub detailed: Vec<usize>,
+
+    /// Representative splats drawn in place of distant clusters
+    pub merged: Vec<GaussianSplat>,
+
+    /// Number of original splats dropped by frustum culling
+    pub culled: usize,
+
+    /// Number of original splats replaced by representatives
+    pub simplified: usize,
+}
+
+impl VisibleSplats {
+    /// Number of splats to draw.
+    pub fn len(&self) -> usize {
+        self.detailed.len() + self.merged.len()
+    }
+
+    /// Whether nothing is visible.
+    pub fn is_empty(&self) -> bool {
+        self.detailed.is_empty() && self.merged.is_empty()
+    }
+
+    /// All splats to draw: detailed splats from `world`, then representatives.
+    pub fn splats<'a>(&'a self, world: &'a World) -> impl Iterator<Item = &'a GaussianSplat> + 'a {
+        self.detailed
+            .iter()
+            .filter_map(|&index| world.splats().get(index))
+            .chain(self.merged.iter())
+    }
+}
+
+/// Running moments used to merge splats into a representative.
+#[derive(Debug, Clone, Copy)]
+struct Moments {
+    weight: f64,
+    mean: [f64; 3],
+    covariance: [f64; 6],
+    color: [f64; 3],
+
+    /// Sum of opacity times cross-section, with `det(Sigma)^(1/3)` standing in for area
+    coverage: f64,
+}
+
+impl Moments {
+    fn of_splat(splat: &impl RasterSplat) -> Self {
+        let [r, g, b, a] = splat.color();
+        let covariance = splat.covariance();
+        let opacity = splat.opacity() * a;
+        let weight = opacity.max(MIN_MERGE_WEIGHT);
+        Self {
+            weight,
+            mean: splat.position(),
+            covariance,
+            color: [r, g, b],
+            coverage: opacity * cross_section(&covariance),
+        }
+    }
+
+    /// Combine two clusters; the spread between their means is added to the
+    /// covariance so merging is associative.
+    fn merge(&self, other: &Self) -> Self {
+        let weight = self.weight + other.weight;
+        let (a, b) = (self.weight / weight, other.weight / weight);
+        let d: [f64; 3] = core::array::from_fn(|i| self.mean[i] - other.mean[i]);
+        let spread = [d[0] * d[0], d[0] * d[1], d[0] * d[2], d[1] * d[1], d[1] * d[2], d[2] * d[2]];
+        Self {
+            weight,
+            mean: core::array::from_fn(|i| a * self.mean[i] + b * other.mean[i]),
+            covariance: core::array::from_fn(|i| a * self.covariance[i] + b * other.covariance[i] + a * b * spread[i]),
+            color: core::array::from_fn(|i| a * self.color[i] + b * other.color[i]),
+            coverage: self.coverage + other.coverage,
+        }
+    }
+
+    /// The representative splat: weighted mean position and color, covariance
+    /// of the whole cluster, and the opacity that spreads the members'
+    /// coverage over the larger cross-section (saturating when they overlap).
+    fn to_splat(self, id: u64) -> GaussianSplat {
+        let [x, y, z] = self.mean;
+        GaussianSplat::new(id, Point3D::new(x, y, z))
+            .with_covariance(self.covariance)
+            .with_color(self.color[0], self.color[1], self.color[2], 1.0)
+            .with_opacity(self.coverage / cross_section(&self.covariance).max(f64::MIN_POSITIVE))
+    }
+}
+
+/// Area proxy of a Gaussian, `det(Sigma)^(1/3)`.
+fn cross_section(c: &[f64; 6]) -> f64 {
+    let determinant = c[0] * (c[3] * c[5] - c[4] * c[4]) - c[1] * (c[1] * c[5] - c[4] * c[2]) + c[2] * (c[1] * c[4] - c[3] * c[2]);
+    determinant.max(0.0).cbrt()
+}
+
+#[derive(Debug, Clone)]
+struct LodNode {
+    bounds: Aabb,
+
+    /// Radius of the sphere around the bounds center enclosing all member extents
+    radius: f64,
+
+    /// Index into the clustered splats for leaves
+    splat: Option<usize>,
+
+    /// Number of original splats under this node
+    members: usize,
+
+    /// Child nodes of an interior node
+    children: [usize; 2],
+
+    /// Representative splat of an interior node
+    representative: Option<GaussianSplat>,
+}
+
+/// Precomputed cluster hierarchy for level-of-detail selection.
+///
+/// The hierarchy refers to splats by their index in the list it was built
+/// from, such as `World::splats` or the rows of a splat storage, so it must
+/// be rebuilt whenever those splats change.
+#[derive(Debug, Clone)]
+pub struct LodHierarchy {
+    nodes: Vec<LodNode>,
+}
+
+impl LodHierarchy {
+    /// Cluster the splats of `world`.
+    ///
+    /// Representatives get IDs counting down from `u64::MAX` so they do not
+    /// collide with the world's own IDs.
+    pub fn build(world: &World) -> Self {
+        Self::from_splats(world.splats())
+    }
+
+    /// Cluster an explicit list of splats, such as borrowed storage rows.
+    pub fn from_splats(splats: &[impl RasterSplat]) -> Self {
+        let mut hierarchy = Self { nodes: Vec::new() };
+        if splats.is_empty() {
+            return hierarchy;
+        }
+        let mut order: Vec<usize> = (0..splats.len()).collect();
+        hierarchy.nodes.reserve(2 * order.len());
+        hierarchy.build_node(splats, &mut order);
+        hierarchy
+    }
+
+    /// Number of nodes, leaves included.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Select the splats to draw for `camera` and a viewport of the given size.
+    ///
+    /// A cluster is drawn as its representative once its bounding sphere
+    /// projects to at most `threshold` pixels; a threshold of zero draws every
+    /// visible splat at full detail.
+    pub fn select(&self, camera: &Camera, width: u32, height: u32, threshold: f64) -> VisibleSplats {
+        let mut visible = VisibleSplats::default();
+        if self.nodes.is_empty() {
+            return visible;
+        }
+        let frustum = Frustum::from_camera(camera);
+        let view = ViewParameters::new(camera, width, height);
+        let focal = view.focal[0].max(view.focal[1]);
+        let eye = [camera.position.x, camera.position.y, camera.position.z];
+
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !frustum.intersects_aabb(&node.bounds) {
+                visible.culled += node.members;
+                continue;
+            }
+            if let Some(splat) = node.splat {
+                visible.detailed.push(splat);
+                continue;
+            }
+
+            let center = node.bounds.center();
+            let distance = (0..3).map(|axis| (center[axis] - eye[axis]).powi(2)).sum::<f64>().sqrt();
+            let depth = distance - node.radius;
+            let projected_size = if depth > 0.0 { node.radius * focal / depth } else { f64::INFINITY };
+            match &node.representative {
+                Some(representative) if projected_size <= threshold => {
+                    visible.merged.push(representative.clone());
+                    visible.simplified += node.members;
+                }
+                _ => stack.extend(node.children),
+            }
+        }
+        visible
+    }
+
+    /// Build the subtree over `order`, returning its node index and moments.
+    fn build_node(&mut self, splats: &[impl RasterSplat], order: &mut [usize]) -> (usize, Moments) {
+        let index = self.nodes.len();
+        let bounds = order.iter().fold(Aabb::empty(), |bounds, &splat| {
+            let splat = &splats[splat];
+            bounds.union(&Aabb::of_ellipsoid(splat.position(), &splat.covariance(), DEFAULT_SIGMA))
+        });
+        let half_diagonal = (0..3)
+            .map(|axis| (bounds.max[axis] - bounds.min[axis]).powi(2))
+            .sum::<f64>()
+            .sqrt()
+            * 0.5;
+        self.nodes.push(LodNode {
+            bounds,
+            radius: half_diagonal,
+            splat: None,
+            members: order.len(),
+            children: [0, 0],
+            representative: None,
+        });
+
+        if let [splat] = order {
+            self.nodes[index].splat = Some(*splat);
+            return (index, Moments::of_splat(&splats[*splat]));
+        }
+
+        let centers = order.iter().fold(Aabb::empty(), |bounds, &splat| {
+            let p = splats[splat].position();
+            bounds.union(&Aabb::new(p, p))
+        });
+        let axis = (0..3)
+            .max_by(|&a, &b| (centers.max[a] - centers.min[a]).total_cmp(&(centers.max[b] - centers.min[b])))
+            .unwrap_or(0);
+        let coordinate = |splat: usize| splats[splat].position()[axis];
+        let middle = order.len() / 2;
+        order.select_nth_unstable_by(middle, |&a, &b| coordinate(a).total_cmp(&coordinate(b)));
+
+        let (left_order, right_order) = order.split_at_mut(middle);
+        let (left, left_moments) = self.build_node(splats, left_order);
+        let (right, right_moments) = self.build_node(splats, right_order);
+        let moments = left_moments.merge(&right_moments);
+
+        let node = &mut self.nodes[index];
+        node.children = [left, right];
+        node.representative = Some(moments.to_splat(u64::MAX - index as u64));
+        (index, moments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> Camera {
+        Camera::new().with_aspect_ratio(1.0)
+    }
+
+    /// A line of small splats running away from the default camera along -z.
+    fn corridor() -> World {
+        let mut world = World::new();
+        world.set_camera(camera());
+        for step in 0..256 {
+            for x in [-0.5, 0.5] {
+                world.add_splat(
+                    GaussianSplat::new(0, Point3D::new(x, 0.0, -(step as f64)))
+                        .with_covariance([0.01, 0.0, 0.0, 0.01, 0.0, 0.01])
+                        .with_color(1.0, 0.5, 0.25, 1.0),
+                );
+            }
+        }
+        world
+    }
+
+    #[test]
+    fn test_frustum_planes() {
+        let frustum = Frustum::from_camera(&camera());
+        assert!(frustum.contains_point(&Point3D::origin()));
+        // Behind the camera, beyond the far plane and far off to the side
+        assert!(!frustum.contains_point(&Point3D::new(0.0, 0.0, 10.0)));
+        assert!(!frustum.contains_point(&Point3D::new(0.0, 0.0, -2000.0)));
+        assert!(!frustum.contains_point(&Point3D::new(100.0, 0.0, 0.0)));
+
+        // Spheres behind the camera intersect once they reach the near plane
+        assert!(!frustum.intersects_sphere(&[0.0, 0.0, 10.0], 4.0));
+        assert!(frustum.intersects_sphere(&[0.0, 0.0, 10.0], 6.0));
+        assert!(frustum.intersects_aabb(&Aabb::new([-200.0, -1.0, -1.0], [0.0, 1.0, 1.0])));
+        assert!(!frustum.intersects_aabb(&Aabb::new([100.0, -1.0, -1.0], [101.0, 1.0, 1.0])));
+    }
+
+    #[test]
+    fn test_cull_splats() {
+        let mut world = World::new();
+        world.set_camera(camera());
+        world.add_splat_at(Point3D::origin());
+        world.add_splat_at(Point3D::new(0.0, 0.0, 20.0));
+        world.add_splat_at(Point3D::new(50.0, 0.0, 0.0));
+        assert_eq!(cull_splats(&world, &Frustum::from_camera(&world.camera)), vec![0]);
+    }
+
+    #[test]
+    fn test_merged_moments() {
+        let mut world = World::new();
+        world.add_splat(GaussianSplat::new(0, Point3D::new(-1.0, 0.0, 0.0)).with_covariance([0.1, 0.0, 0.0, 0.1, 0.0, 0.1]));
+        world.add_splat(GaussianSplat::new(0, Point3D::new(1.0, 0.0, 0.0)).with_covariance([0.1, 0.0, 0.0, 0.1, 0.0, 0.1]));
+        let hierarchy = LodHierarchy::build(&world);
+        assert_eq!(hierarchy.node_count(), 3);
+
+        // Two equal splats merge into one centered between them, stretched along x
+        let representative = hierarchy.nodes[0].representative.as_ref().unwrap();
+        assert_eq!(representative.position, Point3D::origin());
+        assert!((representative.covariance[0] - 1.1).abs() < 1e-12);
+        assert!((representative.covariance[3] - 0.1).abs() < 1e-12);
+        assert!(representative.validate_covariance().is_ok());
+        assert_eq!(representative.id, u64::MAX);
+        // Both members are opaque and barely overlap, so the merged splat is partly transparent
+        assert!(representative.opacity > 0.5 && representative.opacity < 1.0);
+    }
+
+    #[test]
+    fn test_lod_selection() {
+        let world = corridor();
+        let hierarchy = LodHierarchy::build(&world);
+
+        // Without simplification every splat in front of the camera is drawn
+        let full = hierarchy.select(&world.camera, 64, 64, 0.0);
+        assert!(full.merged.is_empty());
+        assert_eq!(full.len() + full.culled, world.splat_count());
+
+        let reduced = hierarchy.select(&world.camera, 64, 64, 4.0);
+        assert!(!reduced.merged.is_empty());
+        assert!(reduced.len() < full.len());
+        assert_eq!(reduced.detailed.len() + reduced.simplified + reduced.culled, world.splat_count());
+        // Nearby splats keep full detail
+        assert!(reduced.splats(&world).any(|splat| splat.position == Point3D::new(0.5, 0.0, 0.0)));
+
+        // A higher resolution needs more detail for the same threshold
+        let sharper = hierarchy.select(&world.camera, 1024, 1024, 4.0);
+        assert!(sharper.len() > reduced.len());
+    }
+
+    #[test]
+    fn test_lod_culls_hierarchically() {
+        let mut world = corridor();
+        world.set_camera(camera().with_position(Point3D::new(0.0, 0.0, 5.0)).with_target(Point3D::new(0.0, 0.0, 10.0)));
+        let visible = LodHierarchy::build(&world).select(&world.camera, 64, 64, 1.0);
+        assert!(visible.is_empty());
+        assert_eq!(visible.culled, world.splat_count());
+    }
+}
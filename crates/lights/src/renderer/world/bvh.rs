@@ -0,0 +1,697 @@
+//! Bounding volume hierarchy over splat extents.
+//!
+//! Each splat is bounded by the axis-aligned box of its `sigma` ellipsoid
+//! (3 standard deviations by default), `|x - mu|^2_Sigma^-1 <= sigma^2`,
+//! whose half extent along axis `i` is `sigma * sqrt(Sigma_ii)`. The tree is
+//! built top-down with median splits along the longest centroid axis and
+//! supports:
+//!
+//! - ray queries against the ellipsoids themselves, not just their boxes
+//! - AABB and sphere range queries
+//! - k-nearest-neighbour queries on splat centers
+//! - refitting in place when splats move, for the whole tree or one splat
+//!
+//! Queries return splat IDs, so results stay valid when `World` reorders
+//! its splats.
+
+use crate::numerics::covariance::{invert_covariance, mahalanobis_along_line};
+use crate::renderer::world::{GaussianSplat, Point3D, Ray, World};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Default ellipsoid size in standard deviations.
+pub const DEFAULT_SIGMA: f64 = 3.0;
+
+/// Maximum number of splats stored in a leaf.
+const MAX_LEAF_SIZE: usize = 4;
+
+/// Marks the root node in the parent table.
+const NO_PARENT: usize = usize::MAX;
+
+/// An axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    /// Minimum corner
+    pub min: [f64; 3],
+
+    /// Maximum corner
+    pub max: [f64; 3],
+}
+
+impl Aabb {
+    /// Create a box from its corners.
+    pub fn new(min: [f64; 3], max: [f64; 3]) -> Self {
+        Self { min, max }
+    }
+
+    /// Create a box from `Point3D` corners, as returned by `World::bounding_box`.
+    pub fn from_corners(min: &Point3D, max: &Point3D) -> Self {
+        Self::new([min.x, min.y, min.z], [max.x, max.y, max.z])
+    }
+
+    /// An empty box that contains nothing; the identity for `union`.
+    pub fn empty() -> Self {
+        Self::new([f64::INFINITY; 3], [f64::NEG_INFINITY; 3])
+    }
+
+    /// The box bounding the `sigma` ellipsoid of a splat.
+    pub fn of_splat(splat: &GaussianSplat, sigma: f64) -> Self {
+        Self::of_ellipsoid([splat.position.x, splat.position.y, splat.position.z], &splat.covariance, sigma)
+    }
+
+    /// The box bounding the `sigma` ellipsoid of a Gaussian with the given
+    /// center and packed covariance.
+    pub fn of_ellipsoid(center: [f64; 3], c: &[f64; 6], sigma: f64) -> Self {
+        let half = [c[0], c[3], c[5]].map(|variance| sigma * variance.max(0.0).sqrt());
+        Self::new(
+            core::array::from_fn(|axis| center[axis] - half[axis]),
+            core::array::from_fn(|axis| center[axis] + half[axis]),
+        )
+    }
+
+    /// Whether the box contains no points.
+    pub fn is_empty(&self) -> bool {
+        (0..3).any(|axis| self.min[axis] > self.max[axis])
+    }
+
+    /// The smallest box containing both boxes.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(
+            core::array::from_fn(|axis| self.min[axis].min(other.min[axis])),
+            core::array::from_fn(|axis| self.max[axis].max(other.max[axis])),
+        )
+    }
+
+    /// Center of the box.
+    pub fn center(&self) -> [f64; 3] {
+        core::array::from_fn(|axis| (self.min[axis] + self.max[axis]) * 0.5)
+    }
+
+    /// Whether `point` lies inside or on the box.
+    pub fn contains(&self, point: &Point3D) -> bool {
+        let point = [point.x, point.y, point.z];
+        (0..3).all(|axis| self.min[axis] <= point[axis] && point[axis] <= self.max[axis])
+    }
+
+    /// Whether the two boxes overlap.
+    pub fn intersects(&self, other: &Self) -> bool {
+        (0..3).all(|axis| self.min[axis] <= other.max[axis] && other.min[axis] <= self.max[axis])
+    }
+
+    /// Squared distance from `point` to the box; zero inside.
+    pub fn distance_squared(&self, point: &[f64; 3]) -> f64 {
+        (0..3)
+            .map(|axis| {
+                let outside = (self.min[axis] - point[axis]).max(point[axis] - self.max[axis]).max(0.0);
+                outside * outside
+            })
+            .sum()
+    }
+
+    /// Whether the box overlaps the sphere at `center` with `radius`.
+    pub fn intersects_sphere(&self, center: &Point3D, radius: f64) -> bool {
+        self.distance_squared(&[center.x, center.y, center.z]) <= radius * radius
+    }
+
+    /// Entry and exit distances of `ray` through the box, clipped to `t >= 0`.
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<(f64, f64)> {
+        let origin = [ray.origin.x, ray.origin.y, ray.origin.z];
+        let direction = [ray.direction.x, ray.direction.y, ray.direction.z];
+        let mut near = 0.0f64;
+        let mut far = f64::INFINITY;
+        for axis in 0..3 {
+            if direction[axis] == 0.0 {
+                if origin[axis] < self.min[axis] || origin[axis] > self.max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let inverse = 1.0 / direction[axis];
+            let t0 = (self.min[axis] - origin[axis]) * inverse;
+            let t1 = (self.max[axis] - origin[axis]) * inverse;
+            near = near.max(t0.min(t1));
+            far = far.min(t0.max(t1));
+            if near > far {
+                return None;
+            }
+        }
+        Some((near, far))
+    }
+}
+
+/// A splat hit by a ray query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// ID of the splat that was hit
+    pub id: u64,
+
+    /// Distance along the ray to where it enters the splat's ellipsoid
+    pub distance: f64,
+}
+
+/// A splat returned by a nearest-neighbour query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Neighbor {
+    /// ID of the splat
+    pub id: u64,
+
+    /// Distance from the query point to the splat center
+    pub distance: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Primitive {
+    id: u64,
+    center: [f64; 3],
+    inverse_covariance: Option<[f64; 6]>,
+    bounds: Aabb,
+}
+
+impl Primitive {
+    fn new(splat: &GaussianSplat, sigma: f64) -> Self {
+        Self {
+            id: splat.id,
+            center: [splat.position.x, splat.position.y, splat.position.z],
+            inverse_covariance: invert_covariance(&splat.covariance),
+            bounds: Aabb::of_splat(splat, sigma),
+        }
+    }
+
+    /// Entry distance of `ray` into the `sigma` ellipsoid.
+    ///
+    /// Splats with a singular covariance fall back to their bounding box.
+    fn intersect_ray(&self, ray: &Ray, sigma: f64) -> Option<f64> {
+        let Some(inverse) = &self.inverse_covariance else {
+            return self.bounds.intersect_ray(ray).map(|(near, _)| near);
+        };
+        let p = [
+            ray.origin.x - self.center[0],
+            ray.origin.y - self.center[1],
+            ray.origin.z - self.center[2],
+        ];
+        let d = [ray.direction.x, ray.direction.y, ray.direction.z];
+
+        // Solve |p + t d|^2_A = sigma^2 for t
+        let [dd, pd, pp] = mahalanobis_along_line(inverse, &p, &d);
+        let (qa, qb, qc) = (dd, 2.0 * pd, pp - sigma * sigma);
+        if qa <= 0.0 {
+            return None;
+        }
+        let discriminant = qb * qb - 4.0 * qa * qc;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let (near, far) = ((-qb - root) / (2.0 * qa), (-qb + root) / (2.0 * qa));
+        if far < 0.0 {
+            None
+        } else {
+            Some(near.max(0.0))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BvhNode {
+    bounds: Aabb,
+
+    /// First primitive of a leaf
+    first: usize,
+
+    /// Number of primitives; zero for interior nodes
+    count: usize,
+
+    /// Child nodes of an interior node
+    children: [usize; 2],
+}
+
+/// A bounding volume hierarchy over the splats of a `World`.
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    sigma: f64,
+    nodes: Vec<BvhNode>,
+    parents: Vec<usize>,
+    primitives: Vec<Primitive>,
+
+    /// Leaf node holding each primitive
+    leaves: Vec<usize>,
+
+    /// Primitive index of each splat ID
+    slots: HashMap<u64, usize>,
+}
+
+impl Bvh {
+    /// Build a hierarchy over the `DEFAULT_SIGMA` ellipsoids of the world's splats.
+    pub fn build(world: &World) -> Self {
+        Self::build_with_sigma(world, DEFAULT_SIGMA)
+    }
+
+    /// Build a hierarchy over the `sigma` ellipsoids of the world's splats.
+    pub fn build_with_sigma(world: &World, sigma: f64) -> Self {
+        let mut bvh = Self {
+            sigma,
+            nodes: Vec::new(),
+            parents: Vec::new(),
+            primitives: world.splats.iter().map(|splat| Primitive::new(splat, sigma)).collect(),
+            leaves: Vec::new(),
+            slots: HashMap::new(),
+        };
+        if !bvh.primitives.is_empty() {
+            bvh.nodes.reserve(2 * bvh.primitives.len() / MAX_LEAF_SIZE + 1);
+            bvh.build_node(0, bvh.primitives.len(), NO_PARENT);
+        }
+
+        bvh.leaves = vec![0; bvh.primitives.len()];
+        for (node_index, node) in bvh.nodes.iter().enumerate() {
+            for leaf in &mut bvh.leaves[node.first..node.first + node.count] {
+                *leaf = node_index;
+            }
+        }
+        for (slot, primitive) in bvh.primitives.iter().enumerate() {
+            bvh.slots.entry(primitive.id).or_insert(slot);
+        }
+        bvh
+    }
+
+    /// Number of splats in the hierarchy.
+    pub fn len(&self) -> usize {
+        self.primitives.len()
+    }
+
+    /// Whether the hierarchy holds no splats.
+    pub fn is_empty(&self) -> bool {
+        self.primitives.is_empty()
+    }
+
+    /// Ellipsoid size in standard deviations.
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Bounds of all splat extents, or `None` when empty.
+    pub fn bounds(&self) -> Option<Aabb> {
+        self.nodes.first().map(|root| root.bounds)
+    }
+
+    /// All splats whose ellipsoids the ray passes through, nearest first.
+    pub fn intersect_ray(&self, ray: &Ray) -> Vec<RayHit> {
+        let mut hits = Vec::new();
+        self.visit(
+            |bounds| bounds.intersect_ray(ray).is_some(),
+            |primitive| {
+                if let Some(distance) = primitive.intersect_ray(ray, self.sigma) {
+                    hits.push(RayHit { id: primitive.id, distance });
+                }
+            },
+        );
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// The nearest splat whose ellipsoid the ray passes through.
+    pub fn first_hit(&self, ray: &Ray) -> Option<RayHit> {
+        let mut best: Option<RayHit> = None;
+        let mut stack = Vec::new();
+        if let Some(root) = self.nodes.first() {
+            if let Some((near, _)) = root.bounds.intersect_ray(ray) {
+                stack.push((0, near));
+            }
+        }
+        while let Some((node_index, near)) = stack.pop() {
+            if best.is_some_and(|hit| hit.distance < near) {
+                continue;
+            }
+            let node = &self.nodes[node_index];
+            if node.count > 0 {
+                for primitive in &self.primitives[node.first..node.first + node.count] {
+                    if let Some(distance) = primitive.intersect_ray(ray, self.sigma) {
+                        if best.is_none_or(|hit| distance < hit.distance) {
+                            best = Some(RayHit { id: primitive.id, distance });
+                        }
+                    }
+                }
+                continue;
+            }
+            // Push the farther child first so the nearer one is visited next
+            let mut children: Vec<(usize, f64)> = node
+                .children
+                .iter()
+                .filter_map(|&child| self.nodes[child].bounds.intersect_ray(ray).map(|(near, _)| (child, near)))
+                .collect();
+            children.sort_by(|a, b| b.1.total_cmp(&a.1));
+            stack.extend(children);
+        }
+        best
+    }
+
+    /// IDs of splats whose extents overlap `bounds`.
+    pub fn query_aabb(&self, bounds: &Aabb) -> Vec<u64> {
+        let mut ids = Vec::new();
+        self.visit(
+            |node| node.intersects(bounds),
+            |primitive| {
+                if primitive.bounds.intersects(bounds) {
+                    ids.push(primitive.id);
+                }
+            },
+        );
+        ids
+    }
+
+    /// IDs of splats whose extents overlap the sphere at `center` with `radius`.
+    pub fn query_sphere(&self, center: &Point3D, radius: f64) -> Vec<u64> {
+        let mut ids = Vec::new();
+        self.visit(
+            |node| node.intersects_sphere(center, radius),
+            |primitive| {
+                if primitive.bounds.intersects_sphere(center, radius) {
+                    ids.push(primitive.id);
+                }
+            },
+        );
+        ids
+    }
+
+    /// The `k` splats whose centers are closest to `point`, nearest first.
+    pub fn nearest(&self, point: &Point3D, k: usize) -> Vec<Neighbor> {
+        let point = [point.x, point.y, point.z];
+        let mut found: Vec<Neighbor> = Vec::with_capacity(k + 1);
+        if k == 0 || self.nodes.is_empty() {
+            return found;
+        }
+
+        // Best-first search: node boxes contain their splat centers, so the
+        // distance to a box is a lower bound for everything inside it
+        let mut queue = BinaryHeap::new();
+        queue.push(Candidate {
+            distance_squared: self.nodes[0].bounds.distance_squared(&point),
+            node: 0,
+        });
+        while let Some(Candidate { distance_squared, node }) = queue.pop() {
+            if found.len() == k && distance_squared > found[k - 1].distance * found[k - 1].distance {
+                break;
+            }
+            let node = &self.nodes[node];
+            if node.count == 0 {
+                for &child in &node.children {
+                    queue.push(Candidate {
+                        distance_squared: self.nodes[child].bounds.distance_squared(&point),
+                        node: child,
+                    });
+                }
+                continue;
+            }
+            for primitive in &self.primitives[node.first..node.first + node.count] {
+                let distance = (0..3)
+                    .map(|axis| (primitive.center[axis] - point[axis]).powi(2))
+                    .sum::<f64>()
+                    .sqrt();
+                if found.len() < k || distance < found[k - 1].distance {
+                    let position = found.partition_point(|neighbor| neighbor.distance <= distance);
+                    found.insert(position, Neighbor { id: primitive.id, distance });
+                    found.truncate(k);
+                }
+            }
+        }
+        found
+    }
+
+    /// Refresh one splat after it moved or changed shape, refitting the
+    /// boxes on its path to the root.
+    ///
+    /// The tree topology is kept, so queries stay correct but may slow down
+    /// after large movements; rebuild in that case.
+    pub fn update_splat(&mut self, splat: &GaussianSplat) -> Result<(), String> {
+        let slot = *self
+            .slots
+            .get(&splat.id)
+            .ok_or_else(|| format!("Splat {} is not in the BVH", splat.id))?;
+        self.primitives[slot] = Primitive::new(splat, self.sigma);
+
+        let mut node = self.leaves[slot];
+        while node != NO_PARENT {
+            self.nodes[node].bounds = self.node_bounds(node);
+            node = self.parents[node];
+        }
+        Ok(())
+    }
+
+    /// Refresh every splat from `world` and refit all boxes without rebuilding.
+    ///
+    /// Fails if the world no longer contains the same splats.
+    pub fn refit(&mut self, world: &World) -> Result<(), String> {
+        if world.splat_count() != self.primitives.len() {
+            return Err(format!(
+                "World has {} splats but the BVH was built over {}; rebuild it",
+                world.splat_count(),
+                self.primitives.len()
+            ));
+        }
+        for primitive in &mut self.primitives {
+            let splat = world
+                .get_splat(primitive.id)
+                .ok_or_else(|| format!("Splat {} is not in the world; rebuild the BVH", primitive.id))?;
+            *primitive = Primitive::new(splat, self.sigma);
+        }
+        // Children always follow their parent, so a reverse pass sees them first
+        for node in (0..self.nodes.len()).rev() {
+            self.nodes[node].bounds = self.node_bounds(node);
+        }
+        Ok(())
+    }
+
+    fn build_node(&mut self, first: usize, end: usize, parent: usize) -> usize {
+        let index = self.nodes.len();
+        let bounds = self.primitives[first..end]
+            .iter()
+            .fold(Aabb::empty(), |bounds, primitive| bounds.union(&primitive.bounds));
+        self.nodes.push(BvhNode {
+            bounds,
+            first,
+            count: end - first,
+            children: [0, 0],
+        });
+        self.parents.push(parent);
+        if end - first <= MAX_LEAF_SIZE {
+            return index;
+        }
+
+        let centers = self.primitives[first..end].iter().fold(Aabb::empty(), |bounds, primitive| {
+            bounds.union(&Aabb::new(primitive.center, primitive.center))
+        });
+        let axis = (0..3)
+            .max_by(|&a, &b| {
+                (centers.max[a] - centers.min[a]).total_cmp(&(centers.max[b] - centers.min[b]))
+            })
+            .unwrap_or(0);
+        let middle = (end - first) / 2;
+        self.primitives[first..end]
+            .select_nth_unstable_by(middle, |a, b| a.center[axis].total_cmp(&b.center[axis]));
+
+        let left = self.build_node(first, first + middle, index);
+        let right = self.build_node(first + middle, end, index);
+        let node = &mut self.nodes[index];
+        node.count = 0;
+        node.children = [left, right];
+        index
+    }
+
+    fn node_bounds(&self, node: usize) -> Aabb {
+        let node = &self.nodes[node];
+        if node.count > 0 {
+            self.primitives[node.first..node.first + node.count]
+                .iter()
+                .fold(Aabb::empty(), |bounds, primitive| bounds.union(&primitive.bounds))
+        } else {
+            self.nodes[node.children[0]].bounds.union(&self.nodes[node.children[1]].bounds)
+        }
+    }
+
+    /// Depth-first traversal of nodes accepted by `enter`, calling `visit` on
+    /// every primitive in the accepted leaves.
+    fn visit(&self, enter: impl Fn(&Aabb) -> bool, mut visit: impl FnMut(&Primitive)) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if !enter(&node.bounds) {
+                continue;
+            }
+            if node.count > 0 {
+                self.primitives[node.first..node.first + node.count].iter().for_each(&mut visit);
+            } else {
+                stack.extend(node.children);
+            }
+        }
+    }
+}
+
+/// A node in the nearest-neighbour queue, ordered closest first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    distance_squared: f64,
+    node: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance_squared.total_cmp(&self.distance_squared)
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 10 x 10 x 10 grid of small isotropic splats with unit spacing.
+    fn grid_world() -> World {
+        let mut world = World::new();
+        for x in 0..10 {
+            for y in 0..10 {
+                for z in 0..10 {
+                    world.add_splat(
+                        GaussianSplat::new(0, Point3D::new(x as f64, y as f64, z as f64))
+                            .with_covariance([0.01, 0.0, 0.0, 0.01, 0.0, 0.01]),
+                    );
+                }
+            }
+        }
+        world
+    }
+
+    fn brute_force_nearest(world: &World, point: &Point3D, k: usize) -> Vec<f64> {
+        let mut distances: Vec<f64> = world
+            .splats
+            .iter()
+            .map(|splat| {
+                let p = &splat.position;
+                ((p.x - point.x).powi(2) + (p.y - point.y).powi(2) + (p.z - point.z).powi(2)).sqrt()
+            })
+            .collect();
+        distances.sort_by(f64::total_cmp);
+        distances.truncate(k);
+        distances
+    }
+
+    #[test]
+    fn test_aabb() {
+        let splat = GaussianSplat::new(1, Point3D::new(1.0, 2.0, 3.0)).with_covariance([4.0, 0.0, 0.0, 1.0, 0.0, 0.25]);
+        let bounds = Aabb::of_splat(&splat, 3.0);
+        assert_eq!(bounds, Aabb::new([-5.0, -1.0, 1.5], [7.0, 5.0, 4.5]));
+        assert!(bounds.contains(&Point3D::new(6.9, 0.0, 2.0)));
+        assert!(!bounds.intersects(&Aabb::new([8.0, 0.0, 0.0], [9.0, 1.0, 1.0])));
+        assert!(bounds.intersects_sphere(&Point3D::new(8.0, 2.0, 3.0), 1.0));
+        assert!(Aabb::empty().is_empty());
+
+        let ray = Ray::new(Point3D::new(-10.0, 2.0, 3.0), Point3D::new(1.0, 0.0, 0.0));
+        assert_eq!(bounds.intersect_ray(&ray), Some((5.0, 17.0)));
+        let miss = Ray::new(Point3D::new(-10.0, 10.0, 3.0), Point3D::new(1.0, 0.0, 0.0));
+        assert!(bounds.intersect_ray(&miss).is_none());
+    }
+
+    #[test]
+    fn test_ray_queries() {
+        let world = grid_world();
+        let bvh = Bvh::build(&world);
+        assert_eq!(bvh.len(), 1000);
+
+        // A ray along the x axis through the row y = 2, z = 5
+        let ray = Ray::new(Point3D::new(-5.0, 2.0, 5.0), Point3D::new(1.0, 0.0, 0.0));
+        let hits = bvh.intersect_ray(&ray);
+        assert_eq!(hits.len(), 10);
+        assert!((hits[0].distance - 4.7).abs() < 1e-9);
+        assert!(hits.windows(2).all(|pair| pair[0].distance <= pair[1].distance));
+        let first = world.get_splat(hits[0].id).unwrap();
+        assert_eq!(first.position, Point3D::new(0.0, 2.0, 5.0));
+        assert_eq!(bvh.first_hit(&ray), Some(hits[0]));
+
+        // A ray between two rows hits nothing
+        let between = Ray::new(Point3D::new(-5.0, 2.5, 5.0), Point3D::new(1.0, 0.0, 0.0));
+        assert!(bvh.intersect_ray(&between).is_empty());
+        assert!(bvh.first_hit(&between).is_none());
+    }
+
+    #[test]
+    fn test_ellipsoid_is_tighter_than_box() {
+        let mut world = World::new();
+        world.add_splat(GaussianSplat::new(0, Point3D::origin()).with_covariance([1.0, 0.0, 0.0, 1.0, 0.0, 1.0]));
+        let bvh = Bvh::build(&world);
+
+        // Through the corner of the 3-sigma box, outside the sphere
+        let corner = Ray::new(Point3D::new(-10.0, 2.5, 2.5), Point3D::new(1.0, 0.0, 0.0));
+        assert!(bvh.intersect_ray(&corner).is_empty());
+        // From inside the ellipsoid the hit distance is zero
+        let inside = Ray::new(Point3D::origin(), Point3D::new(0.0, 1.0, 0.0));
+        assert_eq!(bvh.first_hit(&inside).unwrap().distance, 0.0);
+    }
+
+    #[test]
+    fn test_range_queries() {
+        let world = grid_world();
+        let bvh = Bvh::build(&world);
+
+        let ids = bvh.query_aabb(&Aabb::new([1.5, 1.5, 1.5], [3.5, 3.5, 3.5]));
+        assert_eq!(ids.len(), 8);
+        for id in ids {
+            let p = &world.get_splat(id).unwrap().position;
+            assert!([p.x, p.y, p.z].iter().all(|&c| c == 2.0 || c == 3.0));
+        }
+
+        // The sphere reaches the center splat's six face neighbours
+        let ids = bvh.query_sphere(&Point3D::new(5.0, 5.0, 5.0), 0.8);
+        assert_eq!(ids.len(), 7);
+        assert!(bvh.query_sphere(&Point3D::new(50.0, 0.0, 0.0), 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_nearest_neighbors() {
+        let world = grid_world();
+        let bvh = Bvh::build(&world);
+
+        for point in [Point3D::new(4.2, 4.9, 0.1), Point3D::new(-3.0, 12.0, 5.5), Point3D::new(9.0, 9.0, 9.0)] {
+            let neighbors = bvh.nearest(&point, 12);
+            let distances: Vec<f64> = neighbors.iter().map(|neighbor| neighbor.distance).collect();
+            assert_eq!(distances, brute_force_nearest(&world, &point, 12));
+        }
+        assert!(bvh.nearest(&Point3D::origin(), 0).is_empty());
+        assert_eq!(bvh.nearest(&Point3D::origin(), 5000).len(), 1000);
+        assert!(Bvh::build(&World::new()).nearest(&Point3D::origin(), 3).is_empty());
+    }
+
+    #[test]
+    fn test_refit() {
+        let mut world = grid_world();
+        let mut bvh = Bvh::build(&world);
+        let target = Point3D::new(20.0, 20.0, 20.0);
+
+        // Move a single splat and refit incrementally
+        let id = world.splats[0].id;
+        world.get_splat_mut(id).unwrap().set_position(target.clone());
+        bvh.update_splat(world.get_splat(id).unwrap()).unwrap();
+        assert_eq!(bvh.nearest(&target, 1)[0].id, id);
+        assert!(bvh.bounds().unwrap().contains(&target));
+        assert!(bvh.update_splat(&GaussianSplat::new(99_999, Point3D::origin())).is_err());
+
+        // Shift everything and refit the whole tree
+        for splat in &mut world.splats {
+            splat.position.x += 100.0;
+        }
+        bvh.refit(&world).unwrap();
+        let hits = bvh.intersect_ray(&Ray::new(Point3D::new(0.0, 2.0, 5.0), Point3D::new(1.0, 0.0, 0.0)));
+        assert_eq!(hits.len(), 10);
+        assert!((hits[0].distance - 99.7).abs() < 1e-9);
+
+        world.add_splat_at(Point3D::origin());
+        assert!(bvh.refit(&world).is_err());
+    }
+}
@@ -4,11 +4,29 @@
 //! within them. It includes support for 3D Gaussian splats, camera management,
 //! and scene graph operations optimized for the tile-based rendering pipeline.
 
+pub mod bvh;
+pub mod storage;
+
+use crate::numerics::covariance;
+use crate::numerics::types::half::{BF16, F16};
+use crate::numerics::types::affine::DepthRange;
+use crate::numerics::types::matrix::Matrix4x4;
+use crate::numerics::types::quaternion::Quaternion;
+use crate::numerics::types::traits::FloatingPoint;
+use crate::numerics::types::vector::Vector3;
 use crate::renderer::DataPrecision;
-use std::collections::HashMap;
+use crate::scene::primitive::Splat;
+use crate::scene::spherical_harmonics::SphericalHarmonics;
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{BorrowDecode, Decode, Encode};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
 
 /// A 3D point with associated data precision.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
 pub struct Point3D {
     pub x: f64,
     pub y: f64,
@@ -27,21 +45,21 @@ impl Point3D {
     /// Convert to the specified precision for calculations.
     pub fn to_precision(&self, precision: DataPrecision) -> PrecisionPoint3D {
         match precision {
-            DataPrecision::F16 => PrecisionPoint3D::F16([self.x as f32, self.y as f32, self.z as f32]), // Note: Using f32 as proxy for f16
+            DataPrecision::F16 => PrecisionPoint3D::F16([self.x, self.y, self.z].map(F16::from_f64)),
             DataPrecision::F32 => PrecisionPoint3D::F32([self.x as f32, self.y as f32, self.z as f32]),
             DataPrecision::F64 => PrecisionPoint3D::F64([self.x, self.y, self.z]),
-            DataPrecision::BFloat16 => PrecisionPoint3D::BFloat16([self.x as f32, self.y as f32, self.z as f32]),
+            DataPrecision::BFloat16 => PrecisionPoint3D::BFloat16([self.x, self.y, self.z].map(BF16::from_f64)),
         }
     }
 }
 
 /// A 3D point with precision-specific storage.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
 pub enum PrecisionPoint3D {
-    F16([f32; 3]),      // Using f32 as proxy for f16
+    F16([F16; 3]),
     F32([f32; 3]),
     F64([f64; 3]),
-    BFloat16([f32; 3]), // Using f32 as proxy for bfloat16
+    BFloat16([BF16; 3]),
 }
 
 impl PrecisionPoint3D {
@@ -56,16 +74,16 @@ impl PrecisionPoint3D {
 
     pub fn to_f64_array(&self) -> [f64; 3] {
         match self {
-            PrecisionPoint3D::F16([x, y, z]) => [*x as f64, *y as f64, *z as f64],
+            PrecisionPoint3D::F16(point) => point.map(F16::to_f64),
             PrecisionPoint3D::F32([x, y, z]) => [*x as f64, *y as f64, *z as f64],
             PrecisionPoint3D::F64([x, y, z]) => [*x, *y, *z],
-            PrecisionPoint3D::BFloat16([x, y, z]) => [*x as f64, *y as f64, *z as f64],
+            PrecisionPoint3D::BFloat16(point) => point.map(BF16::to_f64),
         }
     }
 }
 
 /// A 3D Gaussian splat with position, covariance, and appearance data.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
 pub struct GaussianSplat {
     /// Unique identifier for this splat
     pub id: u64,
@@ -82,6 +100,10 @@ pub struct GaussianSplat {
     /// Opacity/alpha value (0.0 to 1.0)
     pub opacity: f64,
 
+    /// Optional view-dependent color. When present, its DC term matches the
+    /// RGB part of `color`.
+    pub spherical_harmonics: Option<SphericalHarmonics<f64>>,
+
     /// Optional metadata for this splat
     pub metadata: HashMap<String, String>,
 }
@@ -95,16 +117,52 @@ impl GaussianSplat {
             covariance: [1.0, 0.0, 0.0, 1.0, 0.0, 1.0], // Identity-like covariance
             color: [1.0, 1.0, 1.0, 1.0], // White
             opacity: 1.0,
+            spherical_harmonics: None,
             metadata: HashMap::new(),
         }
     }
 
     /// Set the color of this splat.
+    ///
+    /// If spherical harmonics are attached, their DC term is updated to match.
     pub fn with_color(mut self, r: f64, g: f64, b: f64, a: f64) -> Self {
         self.color = [r, g, b, a];
+        if let Some(harmonics) = &mut self.spherical_harmonics {
+            harmonics.set_base_color([r, g, b]);
+        }
+        self
+    }
+
+    /// Attach spherical harmonic coefficients for view-dependent color.
+    ///
+    /// The RGB part of `color` is updated to the coefficients' base color so
+    /// renderers without SH support still see a sensible flat color.
+    pub fn with_spherical_harmonics(mut self, harmonics: SphericalHarmonics<f64>) -> Self {
+        let [r, g, b] = harmonics.base_color();
+        self.color = [r, g, b, self.color[3]];
+        self.spherical_harmonics = Some(harmonics);
         self
     }
 
+    /// RGB color seen along `direction` (from the viewer towards the splat).
+    ///
+    /// Falls back to the flat color when no SH coefficients are present.
+    pub fn color_for_direction(&self, direction: [f64; 3]) -> [f64; 3] {
+        match &self.spherical_harmonics {
+            Some(harmonics) => harmonics.evaluate(direction),
+            None => [self.color[0], self.color[1], self.color[2]],
+        }
+    }
+
+    /// RGB color seen from a camera at `viewpoint`.
+    pub fn color_from(&self, viewpoint: &Point3D) -> [f64; 3] {
+        self.color_for_direction([
+            self.position.x - viewpoint.x,
+            self.position.y - viewpoint.y,
+            self.position.z - viewpoint.z,
+        ])
+    }
+
     /// Set the opacity of this splat.
     pub fn with_opacity(mut self, opacity: f64) -> Self {
         self.opacity = opacity.clamp(0.0, 1.0);
@@ -117,15 +175,146 @@ impl GaussianSplat {
         self
     }
 
+    /// Set the covariance, rejecting matrices that are not positive definite.
+    pub fn try_with_covariance(self, covariance: [f64; 6]) -> Result<Self, String> {
+        covariance::validate_covariance(&covariance)?;
+        Ok(self.with_covariance(covariance))
+    }
+
+    /// Set the covariance from a rotation quaternion and per-axis scale.
+    ///
+    /// This is how 3DGS trainers parameterise splats; the quaternion is
+    /// normalized before use.
+    pub fn with_rotation_scale(self, rotation: Quaternion<f64>, scale: [f64; 3]) -> Self {
+        self.with_covariance(covariance::covariance_from_rotation_scale(rotation, scale))
+    }
+
+    /// Decompose the covariance into a rotation quaternion (with `w >= 0`)
+    /// and per-axis scale, largest scale first.
+    pub fn rotation_scale(&self) -> Result<(Quaternion<f64>, [f64; 3]), String> {
+        covariance::rotation_scale_from_covariance(&self.covariance)
+            .map_err(|e| format!("Splat {}: {}", self.id, e))
+    }
+
+    /// Check that the covariance is a valid positive definite matrix.
+    pub fn validate_covariance(&self) -> Result<(), String> {
+        covariance::validate_covariance(&self.covariance).map_err(|e| format!("Splat {}: {}", self.id, e))
+    }
+
     /// Add metadata to this splat.
     pub fn with_metadata(mut self, key: String, value: String) -> Self {
         self.metadata.insert(key, value);
         self
     }
+
+    /// Create a splat from the precision-generic `Splat` model.
+    ///
+    /// Every field is copied as-is, so `from_splat(id, &splat).to_splat()`
+    /// returns `splat` unchanged for `f32` and `f64`. The splat gets `id`
+    /// and starts without metadata.
+    pub fn from_splat<T: FloatingPoint>(id: u64, splat: &Splat<T>) -> Self {
+        Self::from_parts(id, splat, HashMap::new())
+    }
+
+    /// Create a splat from its ID, the generic `Splat` model and its metadata.
+    ///
+    /// This is the inverse of `into_parts`.
+    pub fn from_parts<T: FloatingPoint>(id: u64, splat: &Splat<T>, metadata: HashMap<String, String>) -> Self {
+        let [x, y, z] = splat.position.map(|c| c.to_f64());
+        Self {
+            id,
+            position: Point3D::new(x, y, z),
+            covariance: splat.covariance.map(|c| c.to_f64()),
+            color: splat.color.map(|c| c.to_f64()),
+            opacity: splat.opacity.to_f64(),
+            spherical_harmonics: splat.spherical_harmonics.as_ref().map(|harmonics| harmonics.cast()),
+            metadata,
+        }
+    }
+
+    /// Split into the ID, the generic `Splat` model and the metadata.
+    ///
+    /// Nothing is dropped: `from_parts` rebuilds an equal splat from the
+    /// parts of `into_parts::<f64>()`.
+    pub fn into_parts<T: FloatingPoint>(self) -> (u64, Splat<T>, HashMap<String, String>) {
+        let splat = self.to_splat();
+        (self.id, splat, self.metadata)
+    }
+
+    /// Convert the geometry and appearance to the precision-generic `Splat`
+    /// model, exactly so for `Splat<f64>`.
+    ///
+    /// The ID and metadata are not part of `Splat`; use `into_parts` to keep
+    /// them.
+    pub fn to_splat<T: FloatingPoint>(&self) -> Splat<T> {
+        Splat::from_parts(
+            [self.position.x, self.position.y, self.position.z].map(T::from_f64),
+            self.covariance.map(T::from_f64),
+            self.color.map(T::from_f64),
+            T::from_f64(self.opacity),
+            self.spherical_harmonics.as_ref().map(|harmonics| harmonics.cast()),
+        )
+    }
+}
+
+impl<T: FloatingPoint> From<Splat<T>> for GaussianSplat {
+    fn from(splat: Splat<T>) -> Self {
+        Self::from_splat(0, &splat)
+    }
+}
+
+impl<T: FloatingPoint> From<(u64, Splat<T>, HashMap<String, String>)> for GaussianSplat {
+    fn from((id, splat, metadata): (u64, Splat<T>, HashMap<String, String>)) -> Self {
+        Self::from_parts(id, &splat, metadata)
+    }
+}
+
+impl<T: FloatingPoint> From<GaussianSplat> for (u64, Splat<T>, HashMap<String, String>) {
+    fn from(splat: GaussianSplat) -> Self {
+        splat.into_parts()
+    }
+}
+
+/// A ray with an origin and a normalized direction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ray {
+    /// Ray origin in world space
+    pub origin: Point3D,
+
+    /// Unit direction in world space
+    pub direction: Point3D,
+}
+
+impl Ray {
+    /// Create a new ray. The direction is normalized.
+    pub fn new(origin: Point3D, direction: Point3D) -> Self {
+        let length = (direction.x * direction.x + direction.y * direction.y + direction.z * direction.z).sqrt();
+        let direction = if length > 0.0 {
+            Point3D::new(direction.x / length, direction.y / length, direction.z / length)
+        } else {
+            direction
+        };
+        Self { origin, direction }
+    }
+
+    /// Get the point at parameter `t` along the ray.
+    pub fn at(&self, t: f64) -> Point3D {
+        Point3D::new(
+            self.origin.x + self.direction.x * t,
+            self.origin.y + self.direction.y * t,
+            self.origin.z + self.direction.z * t,
+        )
+    }
 }
 
 /// Camera configuration for rendering.
-#[derive(Debug, Clone)]
+///
+/// Matrices follow `MatrixConvention::NATIVE`, the OpenGL conventions used by
+/// the shaders: right-handed view space looking down -Z, column vectors
+/// (`M * v`) stored row-major in `Matrix4x4::data[row][column]`, and
+/// clip-space depth in `[-1, 1]`. Screen coordinates are in pixels with the
+/// origin at the top-left corner.
+#[derive(Debug, Clone, Encode, Decode)]
 pub struct Camera {
     /// Camera position in world space
     pub position: Point3D,
@@ -186,6 +375,120 @@ impl Camera {
         self.aspect_ratio = aspect_ratio.max(0.1);
         self
     }
+
+    /// Set the up vector.
+    pub fn with_up(mut self, up: Point3D) -> Self {
+        self.up = up;
+        self
+    }
+
+    /// Set the near and far clipping plane distances.
+    pub fn with_clip_planes(mut self, near: f64, far: f64) -> Self {
+        self.near = near.max(f64::EPSILON);
+        self.far = far.max(self.near + f64::EPSILON);
+        self
+    }
+
+    /// Orthonormal camera basis as (right, up, forward) in world space.
+    ///
+    /// Degenerate configurations (target at the camera position, or an up
+    /// vector parallel to the viewing direction) fall back to sensible axes.
+    pub fn basis(&self) -> ([f64; 3], [f64; 3], [f64; 3]) {
+        // The rows of the view matrix are the basis, with forward negated
+        let m = self.view_matrix().data;
+        let row = |index: usize| [m[index][0], m[index][1], m[index][2]];
+        (row(0), row(1), row(2).map(|c| -c))
+    }
+
+    /// Look-at view matrix transforming world space into view space.
+    pub fn view_matrix(&self) -> Matrix4x4<f64> {
+        let vector = |p: &Point3D| Vector3::new(p.x, p.y, p.z);
+        Matrix4x4::look_at(vector(&self.position), vector(&self.target), vector(&self.up))
+    }
+
+    /// Perspective projection matrix using the vertical field of view.
+    pub fn perspective_matrix(&self) -> Matrix4x4<f64> {
+        Matrix4x4::perspective(
+            self.fov.to_radians(),
+            self.aspect_ratio,
+            self.near,
+            self.far,
+            DepthRange::NegativeOneToOne,
+        )
+    }
+
+    /// Orthographic projection matrix covering `view_height` world units vertically.
+    ///
+    /// The horizontal extent follows from the aspect ratio.
+    pub fn orthographic_matrix(&self, view_height: f64) -> Matrix4x4<f64> {
+        let half_height = view_height * 0.5;
+        let half_width = half_height * self.aspect_ratio;
+
+        Matrix4x4::orthographic(
+            -half_width,
+            half_width,
+            -half_height,
+            half_height,
+            self.near,
+            self.far,
+            DepthRange::NegativeOneToOne,
+        )
+    }
+
+    /// Projection matrix used for rendering (perspective).
+    pub fn projection_matrix(&self) -> Matrix4x4<f64> {
+        self.perspective_matrix()
+    }
+
+    /// Combined view-projection matrix (`projection * view`).
+    pub fn view_projection_matrix(&self) -> Matrix4x4<f64> {
+        self.projection_matrix() * self.view_matrix()
+    }
+
+    /// Project a world-space point to screen space.
+    ///
+    /// # Returns
+    /// `Some([x, y, depth])` with pixel coordinates and the distance along the
+    /// viewing direction, or `None` if the point is not in front of the camera.
+    pub fn world_to_screen(&self, point: &Point3D, width: u32, height: u32) -> Option<[f64; 3]> {
+        let m = self.view_projection_matrix().data;
+        let p = [point.x, point.y, point.z, 1.0];
+        let clip: [f64; 4] = core::array::from_fn(|row| (0..4).map(|k| m[row][k] * p[k]).sum());
+
+        if clip[3] <= f64::EPSILON {
+            return None;
+        }
+
+        let ndc_x = clip[0] / clip[3];
+        let ndc_y = clip[1] / clip[3];
+        Some([
+            (ndc_x + 1.0) * 0.5 * width as f64,
+            (1.0 - ndc_y) * 0.5 * height as f64,
+            clip[3],
+        ])
+    }
+
+    /// Build the world-space ray passing through a pixel position.
+    ///
+    /// This is the inverse of `world_to_screen`: every point on the returned
+    /// ray projects back to `(x, y)`.
+    pub fn screen_to_ray(&self, x: f64, y: f64, width: u32, height: u32) -> Ray {
+        let ndc_x = 2.0 * x / width as f64 - 1.0;
+        let ndc_y = 1.0 - 2.0 * y / height as f64;
+
+        let tan_half_fov_y = (self.fov.to_radians() * 0.5).tan();
+        let view_x = ndc_x * tan_half_fov_y * self.aspect_ratio;
+        let view_y = ndc_y * tan_half_fov_y;
+
+        let (right, up, forward) = self.basis();
+        let direction = Point3D::new(
+            right[0] * view_x + up[0] * view_y + forward[0],
+            right[1] * view_x + up[1] * view_y + forward[1],
+            right[2] * view_x + up[2] * view_y + forward[2],
+        );
+
+        Ray::new(self.position.clone(), direction)
+    }
 }
 
 impl Default for Camera {
@@ -195,10 +498,16 @@ impl Default for Camera {
 }
 
 /// A 3D world containing Gaussian splats and rendering configuration.
+///
+/// Splats are looked up through an ID-to-index map, so `get_splat`,
+/// `get_splat_mut` and `remove_splat` run in constant time. The splat list is
+/// only reachable through these methods and the read-only `splats`, and
+/// `get_splat_mut` hands out a `SplatMut` with setters for everything but the
+/// ID, so every ID stays unique and indexed.
 #[derive(Debug, Clone)]
 pub struct World {
-    /// Collection of Gaussian splats in this world
-    pub splats: Vec<GaussianSplat>,
+    /// Collection of Gaussian splats in this world, with unique IDs
+    splats: Vec<GaussianSplat>,
 
     /// Camera configuration for rendering this world
     pub camera: Camera,
@@ -210,7 +519,10 @@ pub struct World {
     pub metadata: HashMap<String, String>,
 
     /// Next available splat ID
-    next_id: u64,
+    pub(crate) next_id: u64,
+
+    /// Index of each splat in `splats`, keyed by ID
+    index: HashMap<u64, usize>,
 }
 
 impl World {
@@ -222,44 +534,120 @@ impl World {
             background_color: [0.0, 0.0, 0.0, 1.0], // Black background
             metadata: HashMap::new(),
             next_id: 0,
+            index: HashMap::new(),
         }
     }
 
-    /// Add a Gaussian splat to the world.
-    pub fn add_splat(&mut self, mut splat: GaussianSplat) -> u64 {
-        if splat.id == 0 {
-            splat.id = self.next_id();
-        }
-        let id = splat.id;
-        self.splats.push(splat);
-        id
+    /// Add a Gaussian splat to the world, returning its ID.
+    ///
+    /// A splat with ID 0 is assigned the next free ID, and so is a splat whose
+    /// ID is already in use, as in `replace_splats` and `apply_update`. Any
+    /// other ID is kept. The returned ID is the one the splat is stored under.
+    pub fn add_splat(&mut self, splat: GaussianSplat) -> u64 {
+        self.insert_splat(splat)
     }
 
     /// Create and add a new splat at the specified position.
     pub fn add_splat_at(&mut self, position: Point3D) -> u64 {
         let id = self.next_id();
-        let splat = GaussianSplat::new(id, position);
-        self.splats.push(splat);
+        self.index.insert(id, self.splats.len());
+        self.splats.push(GaussianSplat::new(id, position));
         id
     }
 
+    /// Add generic `Splat`s to the world, assigning each a new ID.
+    pub fn add_splats_from<T: FloatingPoint>(&mut self, splats: impl IntoIterator<Item = Splat<T>>) -> Vec<u64> {
+        splats.into_iter().map(|splat| self.add_splat(splat.into())).collect()
+    }
+
+    /// All splats, in world order.
+    ///
+    /// Removal moves the last splat into the freed slot, so the order is not
+    /// stable across `remove_splat`.
+    pub fn splats(&self) -> &[GaussianSplat] {
+        &self.splats
+    }
+
+    /// Replace all splats, keeping their IDs.
+    ///
+    /// Splats that repeat the ID of an earlier one are assigned fresh IDs.
+    pub fn replace_splats(&mut self, splats: Vec<GaussianSplat>) {
+        self.splats = splats;
+        self.reindex();
+    }
+
+    /// Reserve room for `additional` more splats.
+    pub fn reserve(&mut self, additional: usize) {
+        self.splats.reserve(additional);
+        self.index.reserve(additional);
+    }
+
+    /// Copy the splats of this world into the generic `Splat` model, in world order.
+    pub fn to_splats<T: FloatingPoint>(&self) -> Vec<Splat<T>> {
+        self.splats.iter().map(GaussianSplat::to_splat).collect()
+    }
+
     /// Remove a splat by its ID.
+    ///
+    /// The last splat is moved into the freed slot, so removal does not
+    /// preserve the order of `splats`.
     pub fn remove_splat(&mut self, id: u64) -> Option<GaussianSplat> {
-        if let Some(pos) = self.splats.iter().position(|s| s.id == id) {
-            Some(self.splats.remove(pos))
-        } else {
-            None
+        let pos = self.position_of(id)?;
+        self.index.remove(&id);
+        let removed = self.splats.swap_remove(pos);
+        if let Some(moved) = self.splats.get(pos) {
+            self.index.insert(moved.id, pos);
         }
+        Some(removed)
     }
 
     /// Get a splat by its ID.
     pub fn get_splat(&self, id: u64) -> Option<&GaussianSplat> {
-        self.splats.iter().find(|s| s.id == id)
+        self.position_of(id).map(|pos| &self.splats[pos])
     }
 
-    /// Get a mutable reference to a splat by its ID.
-    pub fn get_splat_mut(&mut self, id: u64) -> Option<&mut GaussianSplat> {
-        self.splats.iter_mut().find(|s| s.id == id)
+    /// Get mutable access to a splat by its ID.
+    ///
+    /// The returned `SplatMut` reads like the splat and has a setter for each
+    /// field except the ID, which the world's index depends on.
+    pub fn get_splat_mut(&mut self, id: u64) -> Option<SplatMut<'_>> {
+        let pos = self.position_of(id)?;
+        Some(SplatMut { splat: &mut self.splats[pos] })
+    }
+
+    /// Push a splat, assigning the next free ID if its ID is 0 or taken.
+    fn insert_splat(&mut self, mut splat: GaussianSplat) -> u64 {
+        if splat.id == 0 || self.index.contains_key(&splat.id) {
+            splat.id = self.next_id();
+        }
+        let id = splat.id;
+        self.index.insert(id, self.splats.len());
+        self.splats.push(splat);
+        id
+    }
+
+    /// Rebuild the ID-to-index map, assigning fresh IDs to repeated ones.
+    fn reindex(&mut self) {
+        self.index.clear();
+        let mut duplicates = Vec::new();
+        for (pos, splat) in self.splats.iter().enumerate() {
+            match self.index.entry(splat.id) {
+                Entry::Occupied(_) => duplicates.push(pos),
+                Entry::Vacant(entry) => {
+                    entry.insert(pos);
+                }
+            }
+        }
+        for pos in duplicates {
+            let id = self.next_id();
+            self.splats[pos].id = id;
+            self.index.insert(id, pos);
+        }
+    }
+
+    /// Index of the splat with `id` in `splats`.
+    fn position_of(&self, id: u64) -> Option<usize> {
+        self.index.get(&id).copied()
     }
 
     /// Get the total number of splats in this world.
@@ -270,6 +658,7 @@ impl World {
     /// Clear all splats from the world.
     pub fn clear_splats(&mut self) {
         self.splats.clear();
+        self.index.clear();
     }
 
     /// Set the camera for this world.
@@ -294,6 +683,10 @@ impl World {
 
     /// Generate the next available splat ID.
     fn next_id(&mut self) -> u64 {
+        // Skip IDs taken by splats that were added with an explicit ID
+        while self.index.contains_key(&self.next_id) {
+            self.next_id += 1;
+        }
         let id = self.next_id;
         self.next_id += 1;
         id
@@ -322,12 +715,215 @@ impl World {
     }
 }
 
+// Manual impls: the ID index is derived data and is rebuilt on decode
+impl Encode for World {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.splats.encode(encoder)?;
+        self.camera.encode(encoder)?;
+        self.background_color.encode(encoder)?;
+        self.metadata.encode(encoder)?;
+        self.next_id.encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for World {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let mut world = Self {
+            splats: Decode::decode(decoder)?,
+            camera: Decode::decode(decoder)?,
+            background_color: Decode::decode(decoder)?,
+            metadata: Decode::decode(decoder)?,
+            next_id: Decode::decode(decoder)?,
+            index: HashMap::new(),
+        };
+        world.reindex();
+        Ok(world)
+    }
+}
+
+impl<'de, Context> BorrowDecode<'de, Context> for World {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Decode::decode(decoder)
+    }
+}
+
+/// Mutable access to a splat in a `World`, returned by `World::get_splat_mut`.
+///
+/// Dereferences to the splat for reading. The ID has no setter because the
+/// world indexes splats by ID; remove and re-add a splat to change it.
+#[derive(Debug)]
+pub struct SplatMut<'a> {
+    splat: &'a mut GaussianSplat,
+}
+
+impl SplatMut<'_> {
+    /// Move the splat.
+    pub fn set_position(&mut self, position: Point3D) {
+        self.splat.position = position;
+    }
+
+    /// Set the covariance, stored as `[xx, xy, xz, yy, yz, zz]`.
+    pub fn set_covariance(&mut self, covariance: [f64; 6]) {
+        self.splat.covariance = covariance;
+    }
+
+    /// Set the RGBA color.
+    pub fn set_color(&mut self, color: [f64; 4]) {
+        self.splat.color = color;
+    }
+
+    /// Set the opacity.
+    pub fn set_opacity(&mut self, opacity: f64) {
+        self.splat.opacity = opacity;
+    }
+
+    /// Set or clear the spherical harmonic coefficients.
+    pub fn set_spherical_harmonics(&mut self, harmonics: Option<SphericalHarmonics<f64>>) {
+        self.splat.spherical_harmonics = harmonics;
+    }
+
+    /// Mutable access to the splat's metadata.
+    pub fn metadata_mut(&mut self) -> &mut HashMap<String, String> {
+        &mut self.splat.metadata
+    }
+}
+
+impl Deref for SplatMut<'_> {
+    type Target = GaussianSplat;
+
+    fn deref(&self) -> &GaussianSplat {
+        self.splat
+    }
+}
+
 impl Default for World {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// An incremental change to the contents of a world.
+///
+/// Updates are applied in a fixed order: removals, then modifications,
+/// then additions. Modified splats replace the existing splat with the same ID.
+#[derive(Debug, Clone, Default)]
+pub struct SceneUpdate {
+    /// Splats to add (an ID of 0 requests automatic assignment)
+    pub added: Vec<GaussianSplat>,
+
+    /// Splats that replace existing splats with the same ID
+    pub modified: Vec<GaussianSplat>,
+
+    /// IDs of splats to remove
+    pub removed: Vec<u64>,
+
+    /// Optional replacement camera
+    pub camera: Option<Camera>,
+}
+
+impl SceneUpdate {
+    /// Create an empty update.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a splat.
+    pub fn with_added(mut self, splat: GaussianSplat) -> Self {
+        self.added.push(splat);
+        self
+    }
+
+    /// Replace an existing splat.
+    pub fn with_modified(mut self, splat: GaussianSplat) -> Self {
+        self.modified.push(splat);
+        self
+    }
+
+    /// Remove a splat by its ID.
+    pub fn with_removed(mut self, id: u64) -> Self {
+        self.removed.push(id);
+        self
+    }
+
+    /// Replace the camera.
+    pub fn with_camera(mut self, camera: Camera) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    /// Check if the update contains no changes.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty() && self.camera.is_none()
+    }
+
+    /// Total number of splats touched by this update.
+    pub fn splat_change_count(&self) -> usize {
+        self.added.len() + self.modified.len() + self.removed.len()
+    }
+
+    /// Number of splats left after applying this update to `current` splats,
+    /// counting each removed ID once.
+    pub fn resulting_splat_count(&self, current: usize) -> usize {
+        let removed = self.removed.iter().collect::<HashSet<_>>().len();
+        (current + self.added.len()).saturating_sub(removed)
+    }
+}
+
+/// Check a splat count against a renderer's `max_splat_count`.
+///
+/// Renderers call this before accepting a world, or with
+/// `SceneUpdate::resulting_splat_count` before applying an update.
+pub fn check_splat_count(splat_count: usize, max_splat_count: usize) -> Result<(), String> {
+    if splat_count > max_splat_count {
+        return Err(format!("Splat count {} exceeds max_splat_count {}", splat_count, max_splat_count));
+    }
+    Ok(())
+}
+
+impl World {
+    /// Apply an incremental update to this world.
+    ///
+    /// The update is validated before anything changes: if any modified or
+    /// removed ID does not exist, or an added or modified splat has a
+    /// covariance that is not positive definite, an error is returned and the
+    /// world is left untouched. Added splats with ID 0 or an ID already in use
+    /// are assigned fresh IDs.
+    ///
+    /// # Returns
+    /// The IDs of the added splats, in the order they were provided.
+    pub fn apply_update(&mut self, update: SceneUpdate) -> Result<Vec<u64>, String> {
+        for id in &update.removed {
+            if self.get_splat(*id).is_none() {
+                return Err(format!("Cannot remove splat {}: no such splat", id));
+            }
+        }
+        for splat in &update.modified {
+            if self.get_splat(splat.id).is_none() || update.removed.contains(&splat.id) {
+                return Err(format!("Cannot modify splat {}: no such splat", splat.id));
+            }
+        }
+        for splat in update.added.iter().chain(update.modified.iter()) {
+            splat.validate_covariance()?;
+        }
+
+        for id in update.removed {
+            self.remove_splat(id);
+        }
+        for splat in update.modified {
+            if let Some(pos) = self.position_of(splat.id) {
+                self.splats[pos] = splat;
+            }
+        }
+        let added_ids = update.added.into_iter().map(|splat| self.insert_splat(splat)).collect();
+
+        if let Some(camera) = update.camera {
+            self.camera = camera;
+        }
+
+        Ok(added_ids)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -363,6 +959,18 @@ mod tests {
             }
             _ => panic!("Expected F64 precision point"),
         }
+
+        // Half precisions really round: 0.1 is not representable in either
+        let point = Point3D::new(0.1, 1000.5, -2.0);
+        let half = point.to_precision(DataPrecision::F16);
+        assert!(matches!(half, PrecisionPoint3D::F16(_)));
+        let [x, y, z] = half.to_f64_array();
+        assert!(x != 0.1 && (x - 0.1).abs() < 1e-4);
+        assert_eq!((y, z), (1000.5, -2.0));
+
+        let [x, y, _] = point.to_precision(DataPrecision::BFloat16).to_f64_array();
+        assert!(x != 0.1 && (x - 0.1).abs() < 1e-3);
+        assert_eq!(y, 1000.0);
     }
 
     #[test]
@@ -380,6 +988,85 @@ mod tests {
         assert_eq!(splat.metadata.get("test"), Some(&"value".to_string()));
     }
 
+    #[test]
+    fn test_gaussian_splat_spherical_harmonics() {
+        let flat = GaussianSplat::new(1, Point3D::origin()).with_color(0.2, 0.4, 0.6, 1.0);
+        assert_eq!(flat.color_for_direction([1.0, 0.0, 0.0]), [0.2, 0.4, 0.6]);
+
+        // Band 1, coefficient 1 responds to -y
+        let mut harmonics = SphericalHarmonics::from_color([0.5, 0.5, 0.5], 1).unwrap();
+        harmonics.coefficients_mut()[1] = [0.0, 0.0, 1.0];
+        let splat = flat.with_spherical_harmonics(harmonics);
+        assert!((splat.color[0] - 0.5).abs() < 1e-12);
+
+        let from_above = splat.color_from(&Point3D::new(0.0, 5.0, 0.0));
+        let from_below = splat.color_from(&Point3D::new(0.0, -5.0, 0.0));
+        assert!(from_above[2] > from_below[2]);
+        assert!((from_above[0] - from_below[0]).abs() < 1e-12);
+
+        // Changing the flat color keeps the DC term in sync
+        let recolored = splat.with_color(0.9, 0.1, 0.1, 1.0);
+        let base = recolored.spherical_harmonics.as_ref().unwrap().base_color();
+        assert!((base[0] - 0.9).abs() < 1e-12);
+        assert_eq!(recolored.spherical_harmonics.unwrap().coefficients()[1], [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_gaussian_splat_rotation_scale() {
+        // 90 degrees about z maps the x scale onto the y axis
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let splat = GaussianSplat::new(1, Point3D::origin()).with_rotation_scale(Quaternion::new(half, 0.0, 0.0, half), [2.0, 1.0, 0.5]);
+        let expected = [1.0, 0.0, 0.0, 4.0, 0.0, 0.25];
+        for (actual, expected) in splat.covariance.iter().zip(expected.iter()) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert!(splat.validate_covariance().is_ok());
+
+        let (_, scale) = splat.rotation_scale().unwrap();
+        assert!((scale[0] - 2.0).abs() < 1e-9);
+        assert!((scale[1] - 1.0).abs() < 1e-9);
+        assert!((scale[2] - 0.5).abs() < 1e-9);
+
+        let invalid = GaussianSplat::new(7, Point3D::origin()).try_with_covariance([1.0, 0.0, 0.0, -1.0, 0.0, 1.0]);
+        assert!(invalid.unwrap_err().contains("not positive definite"));
+        assert!(GaussianSplat::new(7, Point3D::origin()).with_covariance([0.0; 6]).rotation_scale().unwrap_err().contains("Splat 7"));
+    }
+
+    #[test]
+    fn test_gaussian_splat_generic_conversion() {
+        let splat = GaussianSplat::new(3, Point3D::new(0.1, -2.5, 7.0))
+            .with_rotation_scale(Quaternion::new(0.9, 0.1, -0.3, 0.2), [0.5, 2.0, 0.1])
+            .with_spherical_harmonics(SphericalHarmonics::from_color([0.2, 0.4, 0.6], 1).unwrap())
+            .with_color(0.2, 0.4, 0.6, 0.75)
+            .with_opacity(0.3)
+            .with_metadata("source".to_string(), "scan".to_string());
+
+        // The ID and metadata travel alongside the generic model
+        let (id, generic, metadata): (u64, Splat<f64>, _) = splat.clone().into();
+        assert_eq!(id, 3);
+        assert_eq!(metadata.get("source").map(String::as_str), Some("scan"));
+        let back = GaussianSplat::from((id, generic.clone(), metadata));
+        assert_eq!(back, splat);
+
+        // Geometry alone converts as-is, and an ID of 0 is assigned on add
+        let geometry = GaussianSplat::from_splat(splat.id, &generic);
+        assert_eq!(geometry.position, splat.position);
+        assert_eq!(geometry.covariance, splat.covariance);
+        assert_eq!(geometry.color, splat.color);
+        assert_eq!(geometry.opacity, splat.opacity);
+        assert_eq!(geometry.spherical_harmonics, splat.spherical_harmonics);
+        assert!(geometry.metadata.is_empty());
+        assert_eq!(GaussianSplat::from(generic).id, 0);
+
+        // A single-precision splat survives the trip through World unchanged
+        let narrow = Splat::new([1.5f32, 0.25, -3.0], 0.1, [1.0, 0.5, 0.0, 1.0]).with_opacity(0.8);
+        let mut world = World::new();
+        let ids = world.add_splats_from([narrow.clone(), narrow.clone()]);
+        assert_eq!(ids.len(), 2);
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(world.to_splats::<f32>(), vec![narrow.clone(), narrow]);
+    }
+
     #[test]
     fn test_camera_creation() {
         let camera = Camera::new()
@@ -394,6 +1081,95 @@ mod tests {
         assert_eq!(camera.aspect_ratio, 1.5);
     }
 
+    fn transform(m: &Matrix4x4<f64>, p: [f64; 4]) -> [f64; 4] {
+        core::array::from_fn(|row| (0..4).map(|k| m.data[row][k] * p[k]).sum())
+    }
+
+    #[test]
+    fn test_camera_view_matrix() {
+        let camera = Camera::new()
+            .with_position(Point3D::new(1.0, 2.0, 3.0))
+            .with_target(Point3D::new(1.0, 2.0, -7.0));
+        let view = camera.view_matrix();
+
+        // Camera position maps to the view-space origin
+        let eye = transform(&view, [1.0, 2.0, 3.0, 1.0]);
+        assert!(eye[0].abs() < 1e-12 && eye[1].abs() < 1e-12 && eye[2].abs() < 1e-12);
+
+        // Target lies on the -Z axis at its distance from the camera
+        let target = transform(&view, [1.0, 2.0, -7.0, 1.0]);
+        assert!(target[0].abs() < 1e-12 && target[1].abs() < 1e-12);
+        assert!((target[2] + 10.0).abs() < 1e-12);
+
+        // Degenerate up vector still yields a valid orthonormal basis
+        let (right, up, forward) = Camera::new()
+            .with_position(Point3D::new(0.0, 5.0, 0.0))
+            .with_up(Point3D::new(0.0, 1.0, 0.0))
+            .basis();
+        let dot = |a: [f64; 3], b: [f64; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+        assert!(dot(right, up).abs() < 1e-12);
+        assert!(dot(right, forward).abs() < 1e-12);
+        assert!((dot(right, right) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_camera_projection_matrices() {
+        let camera = Camera::new().with_clip_planes(1.0, 100.0);
+
+        // Near and far planes map to the ends of the clip-space depth range
+        let perspective = camera.perspective_matrix();
+        let near = transform(&perspective, [0.0, 0.0, -1.0, 1.0]);
+        let far = transform(&perspective, [0.0, 0.0, -100.0, 1.0]);
+        assert!((near[2] / near[3] + 1.0).abs() < 1e-12);
+        assert!((far[2] / far[3] - 1.0).abs() < 1e-12);
+
+        // The top of the field of view maps to ndc y = 1
+        let half_fov = (camera.fov.to_radians() * 0.5).tan();
+        let top = transform(&perspective, [0.0, half_fov * 10.0, -10.0, 1.0]);
+        assert!((top[1] / top[3] - 1.0).abs() < 1e-12);
+
+        let orthographic = camera.orthographic_matrix(4.0);
+        let corner = transform(&orthographic, [2.0 * camera.aspect_ratio, 2.0, -1.0, 1.0]);
+        assert!((corner[0] - 1.0).abs() < 1e-12);
+        assert!((corner[1] - 1.0).abs() < 1e-12);
+        assert!((corner[2] + 1.0).abs() < 1e-12);
+        assert_eq!(corner[3], 1.0);
+
+        let composed = camera.view_projection_matrix();
+        let expected = camera.projection_matrix() * camera.view_matrix();
+        assert_eq!(composed, expected);
+    }
+
+    #[test]
+    fn test_camera_screen_projection_roundtrip() {
+        let camera = Camera::new()
+            .with_position(Point3D::new(2.0, 1.0, 4.0))
+            .with_target(Point3D::new(0.0, 0.5, 0.0))
+            .with_aspect_ratio(4.0 / 3.0);
+
+        // Target projects to the center of the screen
+        let center = camera.world_to_screen(&camera.target, 800, 600).unwrap();
+        assert!((center[0] - 400.0).abs() < 1e-9);
+        assert!((center[1] - 300.0).abs() < 1e-9);
+
+        // Points behind the camera are rejected
+        assert!(camera.world_to_screen(&Point3D::new(4.0, 1.5, 8.0), 800, 600).is_none());
+
+        // Rays through a pixel project back onto that pixel
+        let ray = camera.screen_to_ray(123.0, 456.0, 800, 600);
+        assert_eq!(ray.origin, camera.position);
+        for t in [0.5, 3.0, 20.0] {
+            let projected = camera.world_to_screen(&ray.at(t), 800, 600).unwrap();
+            assert!((projected[0] - 123.0).abs() < 1e-6);
+            assert!((projected[1] - 456.0).abs() < 1e-6);
+        }
+
+        // Above-center pixels have rays pointing upwards relative to the view
+        let upper = camera.screen_to_ray(400.0, 0.0, 800, 600);
+        let center_ray = camera.screen_to_ray(400.0, 300.0, 800, 600);
+        assert!(upper.direction.y > center_ray.direction.y);
+    }
+
     #[test]
     fn test_world_operations() {
         let mut world = World::new();
@@ -420,6 +1196,52 @@ mod tests {
         assert_eq!(world.splat_count(), 0);
     }
 
+    #[test]
+    fn test_world_id_index() {
+        let mut world = World::new();
+        let ids: Vec<u64> = (0..5).map(|i| world.add_splat_at(Point3D::new(i as f64, 0.0, 0.0))).collect();
+        assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+
+        // Removal swaps the last splat into the freed slot and keeps lookups valid
+        assert_eq!(world.remove_splat(1).unwrap().position.x, 1.0);
+        assert_eq!(world.splats()[1].id, 4);
+        for &id in &[0, 2, 3, 4] {
+            assert_eq!(world.get_splat(id).unwrap().position.x, id as f64);
+        }
+        assert!(world.remove_splat(1).is_none());
+        world.get_splat_mut(4).unwrap().set_opacity(0.5);
+        assert_eq!(world.splats()[1].opacity, 0.5);
+
+        // ID 0 still means "assign one", and auto-assignment skips explicit IDs
+        world.add_splat(GaussianSplat::new(6, Point3D::origin()));
+        assert_eq!(world.add_splat(GaussianSplat::new(0, Point3D::origin())), 5);
+        assert_eq!(world.add_splat(GaussianSplat::new(0, Point3D::origin())), 7);
+
+        // A repeated ID gets a fresh one instead of shadowing the first splat
+        assert_eq!(world.add_splat(GaussianSplat::new(6, Point3D::new(9.0, 0.0, 0.0))), 8);
+        assert_eq!(world.get_splat(6).unwrap().position.x, 0.0);
+        assert_eq!(world.get_splat(8).unwrap().position.x, 9.0);
+        assert!(world.remove_splat(6).is_some());
+        assert_eq!(world.add_splat(GaussianSplat::new(6, Point3D::new(9.0, 0.0, 0.0))), 6);
+        assert_eq!(world.get_splat(6).unwrap().position.x, 9.0);
+
+        // Replacing the splats wholesale resolves repeated IDs the same way
+        let mut replaced = World::new();
+        replaced.replace_splats(vec![
+            GaussianSplat::new(3, Point3D::new(1.0, 0.0, 0.0)),
+            GaussianSplat::new(3, Point3D::new(2.0, 0.0, 0.0)),
+        ]);
+        assert_eq!(replaced.get_splat(3).unwrap().position.x, 1.0);
+        let ids: Vec<u64> = replaced.splats().iter().map(|splat| splat.id).collect();
+        assert_eq!(ids, vec![3, 0]);
+        assert_eq!(replaced.remove_splat(0).unwrap().position.x, 2.0);
+
+        let config = bincode::config::standard();
+        let encoded = bincode::encode_to_vec(&world, config).unwrap();
+        let (decoded, _): (World, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded.get_splat(6).unwrap().id, 6);
+    }
+
     #[test]
     fn test_world_bounding_box() {
         let mut world = World::new();
@@ -437,6 +1259,58 @@ mod tests {
         assert_eq!(max, Point3D::new(2.0, 1.0, 3.0));
     }
 
+    #[test]
+    fn test_world_apply_update() {
+        let mut world = World::new();
+        let id1 = world.add_splat_at(Point3D::new(1.0, 0.0, 0.0));
+        let id2 = world.add_splat_at(Point3D::new(2.0, 0.0, 0.0));
+
+        let update = SceneUpdate::new()
+            .with_removed(id1)
+            .with_modified(GaussianSplat::new(id2, Point3D::new(5.0, 0.0, 0.0)).with_opacity(0.5))
+            .with_added(GaussianSplat::new(0, Point3D::new(3.0, 0.0, 0.0)))
+            .with_camera(Camera::new().with_fov(30.0));
+        assert!(!update.is_empty());
+        assert_eq!(update.splat_change_count(), 3);
+
+        let added = world.apply_update(update).unwrap();
+        assert_eq!(added.len(), 1);
+        assert_eq!(world.splat_count(), 2);
+        assert!(world.get_splat(id1).is_none());
+        assert_eq!(world.get_splat(id2).unwrap().position, Point3D::new(5.0, 0.0, 0.0));
+        assert_eq!(world.get_splat(id2).unwrap().opacity, 0.5);
+        assert_eq!(world.get_splat(added[0]).unwrap().position, Point3D::new(3.0, 0.0, 0.0));
+        assert_eq!(world.camera.fov, 30.0);
+
+        assert!(SceneUpdate::new().is_empty());
+    }
+
+    #[test]
+    fn test_world_apply_update_rejects_unknown_ids() {
+        let mut world = World::new();
+        let id = world.add_splat_at(Point3D::origin());
+
+        // Unknown removal leaves the world untouched
+        let update = SceneUpdate::new()
+            .with_added(GaussianSplat::new(0, Point3D::origin()))
+            .with_removed(999);
+        assert!(world.apply_update(update).unwrap_err().contains("999"));
+        assert_eq!(world.splat_count(), 1);
+
+        // Modifying a splat removed by the same update is rejected
+        let update = SceneUpdate::new()
+            .with_removed(id)
+            .with_modified(GaussianSplat::new(id, Point3D::origin()));
+        assert!(world.apply_update(update).is_err());
+        assert!(world.get_splat(id).is_some());
+
+        // Splats with invalid covariances are rejected
+        let update = SceneUpdate::new()
+            .with_added(GaussianSplat::new(0, Point3D::origin()).with_covariance([0.0; 6]));
+        assert!(world.apply_update(update).is_err());
+        assert_eq!(world.splat_count(), 1);
+    }
+
     #[test]
     fn test_world_metadata() {
         let mut world = World::new();
@@ -0,0 +1,895 @@
+//! Structure-of-arrays splat storage for large scenes.
+//!
+//! `World` keeps one `GaussianSplat` per element, each with its own metadata
+//! map, which wastes memory and scatters the hot data across the heap for
+//! multi-million splat captures. `SplatStorage` keeps every attribute in its
+//! own contiguous array so passes that only touch positions (culling,
+//! sorting) stream through tightly packed memory:
+//!
+//! - `ids`, `positions`, `covariances`, `colors`, `opacities`: one entry per splat
+//! - spherical harmonics: one coefficient block per splat with a fixed stride,
+//!   sized for the highest degree stored, plus a per-splat degree; lower
+//!   degrees are zero-padded, so removing a splat moves one block like any
+//!   other column
+//! - metadata: a sparse side table keyed by splat ID, so splats without
+//!   metadata cost nothing; IDs are expected to be unique, as in `World`
+//! - an ID-to-row index, so splats are found by ID in constant time
+//!
+//! `PrecisionSplatStorage` picks the scalar type from a `DataPrecision` at
+//! runtime, so renderers keep their splat data in the precision they run at.
+
+use crate::numerics::types::half::{BF16, F16};
+use crate::numerics::types::traits::FloatingPoint;
+use crate::renderer::world::{GaussianSplat, Point3D, SceneUpdate, World};
+use crate::renderer::DataPrecision;
+use crate::scene::primitive::Splat;
+use crate::scene::spherical_harmonics::{coefficient_count, SphericalHarmonics, MAX_SH_DEGREE};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// Splat attributes stored as separate contiguous arrays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplatStorage<T: FloatingPoint = f32> {
+    ids: Vec<u64>,
+    positions: Vec<[T; 3]>,
+    covariances: Vec<[T; 6]>,
+    colors: Vec<[T; 4]>,
+    opacities: Vec<T>,
+
+    /// SH degree of each splat, `None` for splats without SH
+    sh_degrees: Vec<Option<u8>>,
+
+    /// Coefficients per splat block, `coefficient_count` of the highest degree stored
+    sh_stride: usize,
+    sh_coefficients: Vec<[T; 3]>,
+
+    metadata: HashMap<u64, HashMap<String, String>>,
+
+    /// Row of each stored ID
+    index: HashMap<u64, usize>,
+
+    /// One past the largest ID stored, for splats added without an ID
+    next_id: u64,
+}
+
+impl<T: FloatingPoint> SplatStorage<T> {
+    /// Create empty storage.
+    pub fn new() -> Self {
+        Self {
+            ids: Vec::new(),
+            positions: Vec::new(),
+            covariances: Vec::new(),
+            colors: Vec::new(),
+            opacities: Vec::new(),
+            sh_degrees: Vec::new(),
+            sh_stride: 0,
+            sh_coefficients: Vec::new(),
+            metadata: HashMap::new(),
+            index: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Create empty storage with room for `capacity` splats without SH.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut storage = Self::new();
+        storage.reserve(capacity);
+        storage
+    }
+
+    /// Reserve room for `additional` more splats.
+    pub fn reserve(&mut self, additional: usize) {
+        self.ids.reserve(additional);
+        self.positions.reserve(additional);
+        self.covariances.reserve(additional);
+        self.colors.reserve(additional);
+        self.opacities.reserve(additional);
+        self.sh_degrees.reserve(additional);
+        self.sh_coefficients.reserve(additional * self.sh_stride);
+        self.index.reserve(additional);
+    }
+
+    /// Number of splats.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether the storage holds no splats.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Append one splat with the given ID, returning the ID it is stored under.
+    ///
+    /// If the ID is already stored, the splat gets a fresh one instead, as in
+    /// `apply_update`. A splat with a higher SH degree than any stored so far
+    /// widens the SH stride, which re-lays out the coefficients of every
+    /// splat once.
+    pub fn push(&mut self, id: u64, splat: &Splat<T>) -> u64 {
+        let id = if self.index.contains_key(&id) { self.next_id } else { id };
+        self.index.insert(id, self.ids.len());
+        self.ids.push(id);
+        self.positions.push(splat.position);
+        self.covariances.push(splat.covariance);
+        self.colors.push(splat.color);
+        self.opacities.push(splat.opacity);
+        self.sh_degrees.push(None);
+        self.sh_coefficients.resize(self.len() * self.sh_stride, [T::zero(); 3]);
+        self.set_spherical_harmonics(self.len() - 1, splat.spherical_harmonics.as_ref());
+        self.next_id = self.next_id.max(id.saturating_add(1));
+        id
+    }
+
+    /// Overwrite the attributes of the splat at `index` in place, keeping its
+    /// ID and metadata.
+    ///
+    /// Returns `false` if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, splat: &Splat<T>) -> bool {
+        if index >= self.len() {
+            return false;
+        }
+        self.positions[index] = splat.position;
+        self.covariances[index] = splat.covariance;
+        self.colors[index] = splat.color;
+        self.opacities[index] = splat.opacity;
+        self.set_spherical_harmonics(index, splat.spherical_harmonics.as_ref());
+        true
+    }
+
+    /// Index of the splat with the given ID.
+    pub fn position_of(&self, id: u64) -> Option<usize> {
+        self.index.get(&id).copied()
+    }
+
+    /// Append a `GaussianSplat`, including its ID and metadata, returning the
+    /// ID it is stored under as `push` does.
+    pub fn push_gaussian(&mut self, splat: &GaussianSplat) -> u64 {
+        self.push_gaussian_as(splat.id, splat)
+    }
+
+    /// Overwrite the splat at `index` with the attributes and metadata of a
+    /// `GaussianSplat`, keeping the stored ID.
+    ///
+    /// Returns `false` if `index` is out of bounds.
+    pub fn set_gaussian(&mut self, index: usize, splat: &GaussianSplat) -> bool {
+        if !self.set(index, &splat.to_splat()) {
+            return false;
+        }
+        let id = self.ids[index];
+        if splat.metadata.is_empty() {
+            self.metadata.remove(&id);
+        } else {
+            self.metadata.insert(id, splat.metadata.clone());
+        }
+        true
+    }
+
+    /// Append every splat of a world.
+    pub fn extend_from_world(&mut self, world: &World) {
+        self.reserve(world.splats.len());
+        for splat in &world.splats {
+            self.push_gaussian(splat);
+        }
+        self.next_id = self.next_id.max(world.next_id);
+    }
+
+    /// Apply an incremental update in place, following the rules of
+    /// [`World::apply_update`]: removed splats are swap-removed, modified
+    /// splats overwrite their row, and added splats are appended, with a
+    /// fresh ID when theirs is 0 or already stored.
+    ///
+    /// Invalid updates are rejected before anything changes. The camera of
+    /// the update is left to the caller.
+    ///
+    /// Returns the IDs assigned to the added splats.
+    pub fn apply_update(&mut self, update: &SceneUpdate) -> Result<Vec<u64>, String> {
+        let removed: HashSet<u64> = update.removed.iter().copied().collect();
+        for id in &update.removed {
+            if self.position_of(*id).is_none() {
+                return Err(format!("Cannot remove splat {}: no such splat", id));
+            }
+        }
+        for splat in &update.modified {
+            if self.position_of(splat.id).is_none() || removed.contains(&splat.id) {
+                return Err(format!("Cannot modify splat {}: no such splat", splat.id));
+            }
+        }
+        for splat in update.added.iter().chain(update.modified.iter()) {
+            splat.validate_covariance()?;
+        }
+
+        for id in &update.removed {
+            if let Some(index) = self.position_of(*id) {
+                self.swap_remove(index);
+            }
+        }
+        for splat in &update.modified {
+            if let Some(index) = self.position_of(splat.id) {
+                self.set_gaussian(index, splat);
+            }
+        }
+        self.reserve(update.added.len());
+        let added_ids = update
+            .added
+            .iter()
+            .map(|splat| self.push_gaussian_as(if splat.id == 0 { self.next_id } else { splat.id }, splat))
+            .collect();
+        Ok(added_ids)
+    }
+
+    /// Convert every attribute to another precision, keeping IDs and metadata.
+    pub fn cast<U: FloatingPoint>(&self) -> SplatStorage<U> {
+        let convert = |value: T| U::from_f64(value.to_f64());
+        SplatStorage {
+            ids: self.ids.clone(),
+            positions: self.positions.iter().map(|position| position.map(convert)).collect(),
+            covariances: self.covariances.iter().map(|covariance| covariance.map(convert)).collect(),
+            colors: self.colors.iter().map(|color| color.map(convert)).collect(),
+            opacities: self.opacities.iter().map(|&opacity| convert(opacity)).collect(),
+            sh_degrees: self.sh_degrees.clone(),
+            sh_stride: self.sh_stride,
+            sh_coefficients: self.sh_coefficients.iter().map(|coefficient| coefficient.map(convert)).collect(),
+            metadata: self.metadata.clone(),
+            index: self.index.clone(),
+            next_id: self.next_id,
+        }
+    }
+
+    /// Move all splats from `other` to the end of this storage, leaving `other` empty.
+    ///
+    /// Splats whose ID is already stored here get fresh IDs, as in `push`.
+    pub fn append(&mut self, other: &mut Self) {
+        self.next_id = self.next_id.max(other.next_id);
+        for id in &mut other.ids {
+            if self.index.contains_key(id) {
+                if let Some(metadata) = other.metadata.remove(id) {
+                    other.metadata.insert(self.next_id, metadata);
+                }
+                *id = self.next_id;
+                self.next_id += 1;
+            }
+        }
+
+        let stride = self.sh_stride.max(other.sh_stride);
+        self.restride(stride);
+        other.restride(stride);
+        let offset = self.len();
+        self.index.extend(other.ids.iter().enumerate().map(|(row, &id)| (id, offset + row)));
+        self.ids.append(&mut other.ids);
+        self.positions.append(&mut other.positions);
+        self.covariances.append(&mut other.covariances);
+        self.colors.append(&mut other.colors);
+        self.opacities.append(&mut other.opacities);
+        self.sh_degrees.append(&mut other.sh_degrees);
+        self.sh_coefficients.append(&mut other.sh_coefficients);
+        self.metadata.extend(other.metadata.drain());
+        other.clear();
+    }
+
+    /// Remove the splat at `index` by moving the last splat into its place.
+    ///
+    /// Returns the removed splat, or `None` if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> Option<GaussianSplat> {
+        if index >= self.len() {
+            return None;
+        }
+        let removed = self.get(index)?.to_gaussian();
+        let last = self.len() - 1;
+
+        // Move the last splat's SH block into the removed one
+        let stride = self.sh_stride;
+        self.sh_coefficients.copy_within(last * stride..(last + 1) * stride, index * stride);
+        self.sh_coefficients.truncate(last * stride);
+        self.sh_degrees.swap_remove(index);
+
+        if self.index.get(&removed.id) == Some(&index) {
+            self.index.remove(&removed.id);
+        }
+        if index != last && self.index.get(&self.ids[last]) == Some(&last) {
+            self.index.insert(self.ids[last], index);
+        }
+        self.ids.swap_remove(index);
+        self.positions.swap_remove(index);
+        self.covariances.swap_remove(index);
+        self.colors.swap_remove(index);
+        self.opacities.swap_remove(index);
+        self.metadata.remove(&removed.id);
+        Some(removed)
+    }
+
+    /// Remove all splats.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Borrow the splat at `index`.
+    pub fn get(&self, index: usize) -> Option<SplatRef<'_, T>> {
+        self.as_slice().get(index)
+    }
+
+    /// Iterate over all splats in order.
+    pub fn iter(&self) -> impl Iterator<Item = SplatRef<'_, T>> + '_ {
+        self.as_slice().iter()
+    }
+
+    /// Borrow all splats as a slice.
+    pub fn as_slice(&self) -> SplatSlice<'_, T> {
+        self.slice(0..self.len())
+    }
+
+    /// Borrow a contiguous range of splats.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice(&self, range: Range<usize>) -> SplatSlice<'_, T> {
+        SplatSlice {
+            ids: &self.ids[range.clone()],
+            positions: &self.positions[range.clone()],
+            covariances: &self.covariances[range.clone()],
+            colors: &self.colors[range.clone()],
+            opacities: &self.opacities[range.clone()],
+            sh_degrees: &self.sh_degrees[range.clone()],
+            sh_stride: self.sh_stride,
+            sh_coefficients: &self.sh_coefficients[range.start * self.sh_stride..range.end * self.sh_stride],
+            metadata: &self.metadata,
+        }
+    }
+
+    /// Splat IDs, in storage order.
+    pub fn ids(&self) -> &[u64] {
+        &self.ids
+    }
+
+    /// Splat positions.
+    pub fn positions(&self) -> &[[T; 3]] {
+        &self.positions
+    }
+
+    /// Mutable splat positions.
+    pub fn positions_mut(&mut self) -> &mut [[T; 3]] {
+        &mut self.positions
+    }
+
+    /// Packed splat covariances `[xx, xy, xz, yy, yz, zz]`.
+    pub fn covariances(&self) -> &[[T; 6]] {
+        &self.covariances
+    }
+
+    /// Mutable splat covariances.
+    pub fn covariances_mut(&mut self) -> &mut [[T; 6]] {
+        &mut self.covariances
+    }
+
+    /// Splat RGBA colors.
+    pub fn colors(&self) -> &[[T; 4]] {
+        &self.colors
+    }
+
+    /// Mutable splat RGBA colors.
+    pub fn colors_mut(&mut self) -> &mut [[T; 4]] {
+        &mut self.colors
+    }
+
+    /// Splat opacities.
+    pub fn opacities(&self) -> &[T] {
+        &self.opacities
+    }
+
+    /// Mutable splat opacities.
+    pub fn opacities_mut(&mut self) -> &mut [T] {
+        &mut self.opacities
+    }
+
+    /// Metadata of the splat with the given ID, if it has any.
+    pub fn metadata(&self, id: u64) -> Option<&HashMap<String, String>> {
+        self.metadata.get(&id)
+    }
+
+    /// Set a metadata entry on the splat with the given ID.
+    pub fn set_metadata(&mut self, id: u64, key: String, value: String) {
+        self.metadata.entry(id).or_default().insert(key, value);
+    }
+
+    /// Approximate heap memory used by the attribute arrays and metadata, in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        let per_splat = std::mem::size_of::<u64>()
+            + std::mem::size_of::<[T; 3]>()
+            + std::mem::size_of::<[T; 6]>()
+            + std::mem::size_of::<[T; 4]>()
+            + std::mem::size_of::<T>()
+            + std::mem::size_of::<Option<u8>>();
+        let metadata: usize = self
+            .metadata
+            .values()
+            .flat_map(|entries| entries.iter())
+            .map(|(key, value)| key.capacity() + value.capacity())
+            .sum();
+        self.len() * per_splat + self.sh_coefficients.len() * std::mem::size_of::<[T; 3]>() + metadata
+    }
+
+    /// Convert every splat back into a `World` with the given camera settings
+    /// taken from `template`. IDs and metadata are preserved.
+    pub fn to_world(&self, template: &World) -> World {
+        let mut world = World::new();
+        world.camera = template.camera.clone();
+        world.background_color = template.background_color;
+        world.metadata = template.metadata.clone();
+        world.next_id = self.next_id.max(template.next_id);
+        world.replace_splats(self.iter().map(|splat| splat.to_gaussian()).collect());
+        world
+    }
+
+    fn push_gaussian_as(&mut self, id: u64, splat: &GaussianSplat) -> u64 {
+        let id = self.push(id, &splat.to_splat());
+        if !splat.metadata.is_empty() {
+            self.metadata.insert(id, splat.metadata.clone());
+        }
+        id
+    }
+
+    /// Store the SH coefficients of the splat at `index`, widening the stride if needed.
+    fn set_spherical_harmonics(&mut self, index: usize, harmonics: Option<&SphericalHarmonics<T>>) {
+        let coefficients = harmonics.map_or(&[][..], |harmonics| harmonics.coefficients());
+        if coefficients.len() > self.sh_stride {
+            self.restride(coefficients.len());
+        }
+        let block = &mut self.sh_coefficients[index * self.sh_stride..(index + 1) * self.sh_stride];
+        block[..coefficients.len()].copy_from_slice(coefficients);
+        block[coefficients.len()..].fill([T::zero(); 3]);
+        self.sh_degrees[index] = harmonics.map(|harmonics| harmonics.degree() as u8);
+    }
+
+    /// Re-lay out the SH blocks with a stride of `stride` coefficients, which
+    /// must not be smaller than the current one.
+    fn restride(&mut self, stride: usize) {
+        if stride == self.sh_stride {
+            return;
+        }
+        let mut coefficients = vec![[T::zero(); 3]; self.len() * stride];
+        if self.sh_stride > 0 {
+            for (block, old) in coefficients.chunks_exact_mut(stride).zip(self.sh_coefficients.chunks_exact(self.sh_stride)) {
+                block[..old.len()].copy_from_slice(old);
+            }
+        }
+        self.sh_coefficients = coefficients;
+        self.sh_stride = stride;
+    }
+}
+
+impl<T: FloatingPoint> Default for SplatStorage<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FloatingPoint> From<&World> for SplatStorage<T> {
+    fn from(world: &World) -> Self {
+        let mut storage = Self::new();
+        storage.extend_from_world(world);
+        storage
+    }
+}
+
+impl<T: FloatingPoint> Extend<Splat<T>> for SplatStorage<T> {
+    /// Append splats with fresh IDs, numbered on from the largest ID stored.
+    fn extend<I: IntoIterator<Item = Splat<T>>>(&mut self, splats: I) {
+        for splat in splats {
+            self.push(self.next_id, &splat);
+        }
+    }
+}
+
+/// A borrowed contiguous range of splats in a `SplatStorage`.
+#[derive(Debug, Clone, Copy)]
+pub struct SplatSlice<'a, T: FloatingPoint = f32> {
+    /// Splat IDs
+    pub ids: &'a [u64],
+
+    /// Splat positions
+    pub positions: &'a [[T; 3]],
+
+    /// Packed splat covariances
+    pub covariances: &'a [[T; 6]],
+
+    /// Splat RGBA colors
+    pub colors: &'a [[T; 4]],
+
+    /// Splat opacities
+    pub opacities: &'a [T],
+
+    sh_degrees: &'a [Option<u8>],
+    sh_stride: usize,
+    sh_coefficients: &'a [[T; 3]],
+    metadata: &'a HashMap<u64, HashMap<String, String>>,
+}
+
+impl<'a, T: FloatingPoint> SplatSlice<'a, T> {
+    /// Number of splats in the slice.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether the slice holds no splats.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Borrow the splat at `index` within the slice.
+    pub fn get(&self, index: usize) -> Option<SplatRef<'a, T>> {
+        if index >= self.len() {
+            return None;
+        }
+        let id = self.ids[index];
+        let sh_count = self.sh_degrees[index].map_or(0, |degree| coefficient_count(degree as usize));
+        let sh_start = index * self.sh_stride;
+        Some(SplatRef {
+            id,
+            position: &self.positions[index],
+            covariance: &self.covariances[index],
+            color: &self.colors[index],
+            opacity: self.opacities[index],
+            sh_coefficients: &self.sh_coefficients[sh_start..sh_start + sh_count],
+            metadata: self.metadata.get(&id),
+        })
+    }
+
+    /// Iterate over the splats in the slice.
+    pub fn iter(self) -> impl Iterator<Item = SplatRef<'a, T>> {
+        (0..self.len()).filter_map(move |index| self.get(index))
+    }
+}
+
+/// A borrowed view of one splat in a `SplatStorage`.
+#[derive(Debug, Clone, Copy)]
+pub struct SplatRef<'a, T: FloatingPoint = f32> {
+    /// Splat ID
+    pub id: u64,
+
+    /// Position [x, y, z]
+    pub position: &'a [T; 3],
+
+    /// Packed covariance `[xx, xy, xz, yy, yz, zz]`
+    pub covariance: &'a [T; 6],
+
+    /// RGBA color
+    pub color: &'a [T; 4],
+
+    /// Opacity
+    pub opacity: T,
+
+    /// Spherical harmonic coefficients; empty when the splat has none
+    pub sh_coefficients: &'a [[T; 3]],
+
+    /// Metadata, if the splat has any
+    pub metadata: Option<&'a HashMap<String, String>>,
+}
+
+impl<T: FloatingPoint> SplatRef<'_, T> {
+    /// Copy into the generic `Splat` model.
+    pub fn to_splat(&self) -> Splat<T> {
+        Splat::from_parts(
+            *self.position,
+            *self.covariance,
+            *self.color,
+            self.opacity,
+            self.spherical_harmonics(),
+        )
+    }
+
+    /// Copy into a `GaussianSplat`, including ID and metadata.
+    pub fn to_gaussian(&self) -> GaussianSplat {
+        GaussianSplat::from_parts(self.id, &self.to_splat(), self.metadata.cloned().unwrap_or_default())
+    }
+
+    /// Position as a `Point3D`.
+    pub fn point(&self) -> Point3D {
+        let [x, y, z] = self.position.map(|c| c.to_f64());
+        Point3D::new(x, y, z)
+    }
+
+    fn spherical_harmonics(&self) -> Option<SphericalHarmonics<T>> {
+        if self.sh_coefficients.is_empty() {
+            return None;
+        }
+        let degree = (0..=MAX_SH_DEGREE).find(|&degree| coefficient_count(degree) == self.sh_coefficients.len())?;
+        SphericalHarmonics::from_coefficients(degree, self.sh_coefficients.to_vec()).ok()
+    }
+}
+
+/// `SplatStorage` in a precision chosen at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrecisionSplatStorage {
+    /// IEEE half precision
+    F16(SplatStorage<F16>),
+
+    /// Single precision
+    F32(SplatStorage<f32>),
+
+    /// Double precision
+    F64(SplatStorage<f64>),
+
+    /// bfloat16
+    BFloat16(SplatStorage<BF16>),
+}
+
+macro_rules! with_storage {
+    ($storage:expr, $inner:ident => $body:expr) => {
+        match $storage {
+            PrecisionSplatStorage::F16($inner) => $body,
+            PrecisionSplatStorage::F32($inner) => $body,
+            PrecisionSplatStorage::F64($inner) => $body,
+            PrecisionSplatStorage::BFloat16($inner) => $body,
+        }
+    };
+}
+
+impl PrecisionSplatStorage {
+    /// Create empty storage for `precision`.
+    pub fn new(precision: DataPrecision) -> Self {
+        match precision {
+            DataPrecision::F16 => Self::F16(SplatStorage::new()),
+            DataPrecision::F32 => Self::F32(SplatStorage::new()),
+            DataPrecision::F64 => Self::F64(SplatStorage::new()),
+            DataPrecision::BFloat16 => Self::BFloat16(SplatStorage::new()),
+        }
+    }
+
+    /// Convert every splat in `world` to `precision`, preserving order.
+    pub fn from_world(world: &World, precision: DataPrecision) -> Self {
+        let mut storage = Self::new(precision);
+        with_storage!(&mut storage, inner => inner.extend_from_world(world));
+        storage
+    }
+
+    /// The precision splat attributes are stored in.
+    pub fn precision(&self) -> DataPrecision {
+        match self {
+            Self::F16(_) => DataPrecision::F16,
+            Self::F32(_) => DataPrecision::F32,
+            Self::F64(_) => DataPrecision::F64,
+            Self::BFloat16(_) => DataPrecision::BFloat16,
+        }
+    }
+
+    /// Number of splats.
+    pub fn len(&self) -> usize {
+        with_storage!(self, inner => inner.len())
+    }
+
+    /// Whether there are no splats.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Approximate heap memory used by the stored splats, in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        with_storage!(self, inner => inner.memory_bytes())
+    }
+
+    /// Apply an incremental update in place; see [`SplatStorage::apply_update`].
+    pub fn apply_update(&mut self, update: &SceneUpdate) -> Result<Vec<u64>, String> {
+        with_storage!(self, inner => inner.apply_update(update))
+    }
+
+    /// Convert the stored splats to `precision`.
+    ///
+    /// Values keep the rounding of the current precision, so narrowing and
+    /// widening again does not restore the original values.
+    pub fn to_precision(&self, precision: DataPrecision) -> Self {
+        match precision {
+            DataPrecision::F16 => Self::F16(with_storage!(self, inner => inner.cast())),
+            DataPrecision::F32 => Self::F32(with_storage!(self, inner => inner.cast())),
+            DataPrecision::F64 => Self::F64(with_storage!(self, inner => inner.cast())),
+            DataPrecision::BFloat16 => Self::BFloat16(with_storage!(self, inner => inner.cast())),
+        }
+    }
+}
+
+impl Default for PrecisionSplatStorage {
+    fn default() -> Self {
+        Self::new(DataPrecision::F32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_world() -> World {
+        let mut world = World::new();
+        world.add_splat(
+            GaussianSplat::new(0, Point3D::new(1.0, 2.0, 3.0))
+                .with_spherical_harmonics(SphericalHarmonics::from_color([0.1, 0.2, 0.3], 1).unwrap())
+                .with_metadata("source".to_string(), "scan".to_string()),
+        );
+        world.add_splat(GaussianSplat::new(0, Point3D::new(-1.0, 0.0, 0.5)).with_opacity(0.25));
+        world.add_splat(
+            GaussianSplat::new(0, Point3D::new(4.0, 4.0, 4.0))
+                .with_spherical_harmonics(SphericalHarmonics::from_color([0.5, 0.5, 0.5], 2).unwrap()),
+        );
+        world
+    }
+
+    #[test]
+    fn test_world_roundtrip() {
+        let world = test_world();
+        let storage = SplatStorage::<f64>::from(&world);
+        assert_eq!(storage.len(), 3);
+        assert_eq!(storage.positions()[1], [-1.0, 0.0, 0.5]);
+        assert_eq!(storage.opacities()[1], 0.25);
+        assert_eq!(storage.metadata(world.splats[0].id).unwrap()["source"], "scan");
+        assert!(storage.metadata(world.splats[1].id).is_none());
+
+        let restored = storage.to_world(&world);
+        for (original, restored) in world.splats.iter().zip(restored.splats.iter()) {
+            assert_eq!(original.id, restored.id);
+            assert_eq!(original.position, restored.position);
+            assert_eq!(original.spherical_harmonics, restored.spherical_harmonics);
+            assert_eq!(original.metadata, restored.metadata);
+        }
+    }
+
+    #[test]
+    fn test_slicing_and_iteration() {
+        let storage = SplatStorage::<f32>::from(&test_world());
+        let slice = storage.slice(1..3);
+        assert_eq!(slice.len(), 2);
+        assert_eq!(slice.positions[0], [-1.0, 0.0, 0.5]);
+        assert!(slice.get(0).unwrap().sh_coefficients.is_empty());
+        assert_eq!(slice.get(1).unwrap().sh_coefficients.len(), 9);
+        assert!(slice.get(2).is_none());
+
+        let degrees: Vec<_> = storage
+            .iter()
+            .map(|splat| splat.to_splat().spherical_harmonics.map(|harmonics| harmonics.degree()))
+            .collect();
+        assert_eq!(degrees, vec![Some(1), None, Some(2)]);
+    }
+
+    #[test]
+    fn test_bulk_append_and_swap_remove() {
+        let world = test_world();
+        let mut storage = SplatStorage::<f64>::from(&world);
+        let mut other = test_world();
+        for splat in &mut other.splats {
+            splat.id += 10;
+        }
+        let mut more = SplatStorage::<f64>::from(&other);
+        storage.append(&mut more);
+        assert!(more.is_empty());
+        assert_eq!(storage.len(), 6);
+        assert_eq!(storage.get(3).unwrap().sh_coefficients.len(), 4);
+
+        // Removing the first splat moves the last one (degree 2) into its place
+        let removed = storage.swap_remove(0).unwrap();
+        assert_eq!(removed.position, Point3D::new(1.0, 2.0, 3.0));
+        assert_eq!(storage.len(), 5);
+        assert_eq!(storage.positions()[0], [4.0, 4.0, 4.0]);
+        assert_eq!(storage.get(0).unwrap().sh_coefficients.len(), 9);
+        assert_eq!(storage.get(3).unwrap().sh_coefficients.len(), 4);
+        assert_eq!(storage.get(4).unwrap().sh_coefficients.len(), 0);
+        assert!(storage.swap_remove(5).is_none());
+
+        // The ID index follows appended and moved rows
+        assert!(storage.position_of(removed.id).is_none());
+        for (row, &id) in storage.ids().iter().enumerate() {
+            assert_eq!(storage.position_of(id), Some(row));
+        }
+
+        // Splats without IDs are numbered after the largest stored ID
+        let largest = *storage.ids().iter().max().unwrap();
+        storage.extend([Splat::new([0.0; 3], 1.0, [1.0; 4]), Splat::new([1.0; 3], 1.0, [1.0; 4])]);
+        assert_eq!(storage.len(), 7);
+        assert_eq!(&storage.ids()[5..], &[largest + 1, largest + 2]);
+    }
+
+    #[test]
+    fn test_repeated_ids() {
+        // Pushing a stored ID does not shadow the splat already under it
+        let mut storage = SplatStorage::<f64>::new();
+        assert_eq!(storage.push(4, &Splat::new([1.0; 3], 1.0, [1.0; 4])), 4);
+        assert_eq!(storage.push(4, &Splat::new([2.0; 3], 1.0, [1.0; 4])), 5);
+        assert_eq!(storage.get(storage.position_of(4).unwrap()).unwrap().position, &[1.0; 3]);
+        assert_eq!(storage.get(storage.position_of(5).unwrap()).unwrap().position, &[2.0; 3]);
+
+        // Appending storage with overlapping IDs renumbers the incoming splats
+        let mut other = SplatStorage::<f64>::from(&test_world());
+        other.set_metadata(0, "source".to_string(), "other".to_string());
+        storage.append(&mut other);
+        assert_eq!(storage.ids(), &[4, 5, 0, 1, 2]);
+        assert_eq!(storage.metadata(0).unwrap()["source"], "other");
+
+        let mut overlapping = SplatStorage::<f64>::from(&test_world());
+        storage.append(&mut overlapping);
+        assert_eq!(storage.ids(), &[4, 5, 0, 1, 2, 6, 7, 8]);
+        assert_eq!(storage.metadata(6).unwrap()["source"], "scan");
+        assert_eq!(storage.metadata(0).unwrap()["source"], "other");
+        for (row, &id) in storage.ids().iter().enumerate() {
+            assert_eq!(storage.position_of(id), Some(row));
+        }
+    }
+
+    #[test]
+    fn test_sh_stride() {
+        // Splats pushed before a higher degree arrives keep their coefficients
+        let mut storage = SplatStorage::<f64>::new();
+        storage.push(1, &Splat::new([0.0; 3], 1.0, [1.0; 4]));
+        let low = SphericalHarmonics::from_color([0.1, 0.2, 0.3], 1).unwrap();
+        storage.push(2, &Splat::new([0.0; 3], 1.0, [1.0; 4]).with_spherical_harmonics(low.clone()));
+        let high = SphericalHarmonics::from_color([0.4, 0.5, 0.6], 3).unwrap();
+        storage.push(3, &Splat::new([0.0; 3], 1.0, [1.0; 4]).with_spherical_harmonics(high.clone()));
+        assert_eq!(storage.sh_stride, 16);
+        assert_eq!(storage.sh_coefficients.len(), 3 * 16);
+        assert!(storage.get(0).unwrap().sh_coefficients.is_empty());
+        assert_eq!(storage.get(1).unwrap().to_splat().spherical_harmonics, Some(low.clone()));
+        assert_eq!(storage.get(2).unwrap().to_splat().spherical_harmonics, Some(high));
+
+        // In-place updates can change or drop the SH of a row
+        assert!(storage.set(2, &Splat::new([5.0; 3], 0.5, [1.0; 4]).with_spherical_harmonics(low.clone())));
+        assert_eq!(storage.get(2).unwrap().sh_coefficients, low.coefficients());
+        assert_eq!(storage.positions()[2], [5.0; 3]);
+        assert!(storage.set(1, &Splat::new([0.0; 3], 1.0, [1.0; 4])));
+        assert!(storage.get(1).unwrap().sh_coefficients.is_empty());
+        assert!(!storage.set(3, &Splat::new([0.0; 3], 1.0, [1.0; 4])));
+        assert_eq!(storage.position_of(3), Some(2));
+
+        storage.swap_remove(0);
+        assert_eq!(storage.ids(), &[3, 2]);
+        assert_eq!(storage.get(0).unwrap().sh_coefficients, low.coefficients());
+        assert_eq!(storage.sh_coefficients.len(), 2 * 16);
+    }
+
+    #[test]
+    fn test_apply_update_in_place() {
+        let world = test_world();
+        let ids: Vec<u64> = world.splats.iter().map(|splat| splat.id).collect();
+        let mut storage = SplatStorage::<f32>::from(&world);
+
+        let update = SceneUpdate::new()
+            .with_removed(ids[0])
+            .with_modified(GaussianSplat::new(ids[2], Point3D::new(7.0, 0.0, 0.0)).with_metadata("tag".to_string(), "moved".to_string()))
+            .with_added(GaussianSplat::new(0, Point3D::new(0.0, 1.0, 0.0)))
+            .with_added(GaussianSplat::new(ids[1], Point3D::new(0.0, 2.0, 0.0)));
+        let added = storage.apply_update(&update).unwrap();
+        assert_eq!(storage.len(), 4);
+        assert!(storage.position_of(ids[0]).is_none());
+        assert!(storage.metadata(ids[0]).is_none());
+
+        // The modified row is overwritten where it stands, SH dropped and metadata replaced
+        let row = storage.position_of(ids[2]).unwrap();
+        assert_eq!(storage.positions()[row], [7.0, 0.0, 0.0]);
+        assert!(storage.get(row).unwrap().sh_coefficients.is_empty());
+        assert_eq!(storage.metadata(ids[2]).unwrap()["tag"], "moved");
+
+        // Missing and taken IDs are replaced by fresh ones
+        assert_eq!(added.len(), 2);
+        assert!(added.iter().all(|id| !ids.contains(id)));
+        assert_ne!(added[0], added[1]);
+
+        // Invalid updates change nothing
+        let before = storage.clone();
+        assert!(storage.apply_update(&SceneUpdate::new().with_removed(ids[0])).is_err());
+        let invalid = SceneUpdate::new().with_removed(ids[1]).with_modified(GaussianSplat::new(ids[1], Point3D::origin()));
+        assert!(storage.apply_update(&invalid).is_err());
+        assert_eq!(storage, before);
+
+        // Casting keeps IDs, metadata and SH layout
+        let half = storage.cast::<F16>();
+        assert_eq!(half.ids(), storage.ids());
+        assert_eq!(half.metadata(ids[2]), storage.metadata(ids[2]));
+        assert_eq!(half.sh_stride, storage.sh_stride);
+        assert_eq!(half.positions()[row].map(|c| c.to_f64()), [7.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_memory_is_smaller_than_world() {
+        let mut world = World::new();
+        for index in 0..1000 {
+            world.add_splat_at(Point3D::new(index as f64, 0.0, 0.0));
+        }
+        let storage = SplatStorage::<f32>::from(&world);
+        let aos = world.splats.len() * std::mem::size_of::<GaussianSplat>();
+        assert!(storage.memory_bytes() * 2 < aos);
+    }
+}
@@ -1,11 +1,20 @@
+//! Scene graph and node definitions for the `fuller` library.
+//!
+//! Node stores children as `Vec<Box<Node<T>>>` to avoid recursive-size issues.
+//! Nodes are generic over the numeric precision `T` so they can carry templated primitives.
+//! Each node has a local transform relative to its parent; world transforms are
+//! composed during traversal.
+
 use std::fmt;
 use crate::numerics;
-/// Scene graph and node definitions for the `fuller` library.
-///
-/// Node stores children as `Vec<Box<Node<T>>>` to avoid recursive-size issues.
-/// Nodes are generic over the numeric precision `T` so they can carry templated primitives.
-
+use crate::numerics::types::matrix::Matrix4x4;
+use crate::renderer::world::{GaussianSplat, World};
 use crate::scene::primitive::Splat;
+use crate::scene::transform::Transform;
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{BorrowDecode, Decode, Encode};
 
 /// A scene graph root container.
 #[derive(Debug, Clone)]
@@ -47,17 +56,38 @@ impl<T: numerics::types::traits::FloatingPoint> SceneGraph<T> {
     pub fn find_node_by_name_mut(&mut self, name: &str) -> Option<&mut Node<T>> {
         self.root.find_by_name_mut(name)
     }
+
+    /// Traverse the scene graph (pre-order), passing each node with its world transform.
+    pub fn traverse_with_transforms<F: FnMut(&Node<T>, &Matrix4x4<f64>)>(&self, mut f: F) {
+        self.root.traverse_with_transforms(&Matrix4x4::identity(), &mut f);
+    }
+
+    /// World transform of the first node with the given name (pre-order).
+    pub fn world_transform(&self, name: &str) -> Option<Matrix4x4<f64>> {
+        self.root.world_transform_of(&Matrix4x4::identity(), name)
+    }
+
+    /// Bake every node's splats into world space.
+    ///
+    /// See [`Node::flatten_into`] for how splats are converted.
+    pub fn flatten(&self) -> Result<World, String> {
+        let mut world = World::new();
+        self.root.flatten_into(&Matrix4x4::identity(), &mut world)?;
+        Ok(world)
+    }
 }
 
 /// A node in the scene graph.
 ///
 /// Minimal fields:
 /// - name: `String`
+/// - transform: local `Transform<T>` relative to the parent node
 /// - children: `Vec<Box<Node<T>>>`
 /// - primitives: a collection of splats attached to the node (optional)
 #[derive(Clone)]
 pub struct Node<T: numerics::types::traits::FloatingPoint = f32> {
     pub name: String,
+    pub transform: Transform<T>,
     pub children: Vec<Box<Node<T>>>,
     pub splats: Vec<Splat<T>>,
 }
@@ -67,11 +97,23 @@ impl<T: numerics::types::traits::FloatingPoint> Node<T> {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
+            transform: Transform::identity(),
             children: Vec::new(),
             splats: Vec::new(),
         }
     }
 
+    /// Set the local transform of this node.
+    pub fn with_transform(mut self, transform: Transform<T>) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// World transform of this node given its parent's world transform.
+    pub fn world_transform(&self, parent: &Matrix4x4<f64>) -> Matrix4x4<f64> {
+        parent.clone() * self.transform.to_matrix()
+    }
+
     /// Convenience: create an empty named node and push it as a child.
     pub fn add_child(&mut self, child: Node<T>) {
         self.children.push(Box::new(child));
@@ -98,6 +140,59 @@ impl<T: numerics::types::traits::FloatingPoint> Node<T> {
         }
     }
 
+    /// Pre-order traversal passing each node with its world transform.
+    pub fn traverse_with_transforms<F: FnMut(&Node<T>, &Matrix4x4<f64>)>(&self, parent: &Matrix4x4<f64>, f: &mut F) {
+        let world = self.world_transform(parent);
+        f(self, &world);
+        for child in &self.children {
+            child.traverse_with_transforms(&world, f);
+        }
+    }
+
+    /// World transform of the first node named `target` in this subtree.
+    pub fn world_transform_of(&self, parent: &Matrix4x4<f64>, target: &str) -> Option<Matrix4x4<f64>> {
+        let world = self.world_transform(parent);
+        if self.name == target {
+            return Some(world);
+        }
+        self.children
+            .iter()
+            .find_map(|child| child.world_transform_of(&world, target))
+    }
+
+    /// Bake the splats of this subtree into world space and add them to `world`.
+    ///
+    /// Each splat is transformed with `Splat::transformed`, so its covariance
+    /// becomes `A * Sigma * A^T` for the node's linear transform `A` and its
+    /// spherical harmonics rotate with the node. The node name is recorded in
+    /// the splat's `node` metadata. Fails if a splat's spherical harmonics
+    /// cannot be rotated, in which case `world` is left unchanged.
+    pub fn flatten_into(&self, parent: &Matrix4x4<f64>, world: &mut World) -> Result<(), String> {
+        let mut baked = Vec::new();
+        let mut result = Ok(());
+        self.traverse_with_transforms(parent, &mut |node, transform| {
+            for splat in &node.splats {
+                if result.is_err() {
+                    return;
+                }
+                match splat.cast::<f64>().transformed(transform) {
+                    Ok(transformed) => baked.push(
+                        GaussianSplat::from_splat(0, &transformed).with_metadata("node".to_string(), node.name.clone()),
+                    ),
+                    Err(error) => result = Err(format!("Cannot flatten node '{}': {}", node.name, error)),
+                }
+            }
+        });
+        result?;
+
+        // Only add splats once the whole subtree has baked
+        world.reserve(baked.len());
+        for splat in baked {
+            world.add_splat(splat);
+        }
+        Ok(())
+    }
+
     /// Find first node by name (immutable).
     pub fn find_by_name(&self, target: &str) -> Option<&Node<T>> {
         if self.name == target {
@@ -125,6 +220,55 @@ impl<T: numerics::types::traits::FloatingPoint> Node<T> {
     }
 }
 
+// Manual impls: the bincode derive does not support defaulted type parameters
+impl<T: numerics::types::traits::FloatingPoint + Encode> Encode for SceneGraph<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.name.encode(encoder)?;
+        self.root.encode(encoder)
+    }
+}
+
+impl<Context, T: numerics::types::traits::FloatingPoint + Decode<Context>> Decode<Context> for SceneGraph<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            name: Decode::decode(decoder)?,
+            root: Decode::decode(decoder)?,
+        })
+    }
+}
+
+impl<'de, Context, T: numerics::types::traits::FloatingPoint + Decode<Context>> BorrowDecode<'de, Context> for SceneGraph<T> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Decode::decode(decoder)
+    }
+}
+
+impl<T: numerics::types::traits::FloatingPoint + Encode> Encode for Node<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.name.encode(encoder)?;
+        self.transform.encode(encoder)?;
+        self.children.encode(encoder)?;
+        self.splats.encode(encoder)
+    }
+}
+
+impl<Context, T: numerics::types::traits::FloatingPoint + Decode<Context>> Decode<Context> for Node<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            name: Decode::decode(decoder)?,
+            transform: Decode::decode(decoder)?,
+            children: Decode::decode(decoder)?,
+            splats: Decode::decode(decoder)?,
+        })
+    }
+}
+
+impl<'de, Context, T: numerics::types::traits::FloatingPoint + Decode<Context>> BorrowDecode<'de, Context> for Node<T> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Decode::decode(decoder)
+    }
+}
+
 impl<T: numerics::types::traits::FloatingPoint> fmt::Debug for Node<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Keep debug concise: name and counts
@@ -145,6 +289,9 @@ impl<T: numerics::types::traits::FloatingPoint> fmt::Display for Node<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::numerics::types::quaternion::Quaternion;
+    use crate::renderer::world::Point3D;
+    use crate::scene::transform::transform_point;
 
     #[test]
     fn scenegraph_basic_operations() {
@@ -165,4 +312,81 @@ mod tests {
         assert!(found.is_some());
         assert_eq!(found.unwrap().splats.len(), 1);
     }
+
+    fn assembly() -> SceneGraph<f64> {
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let mut graph: SceneGraph<f64> = SceneGraph::new("assembly");
+
+        // The table is moved along x; the cup sits on top, rotated 90 degrees about z
+        let mut table = Node::new("table").with_transform(Transform::from_translation([10.0, 0.0, 0.0]));
+        let mut cup = Node::new("cup").with_transform(
+            Transform::identity()
+                .with_translation([0.0, 1.0, 0.0])
+                .with_rotation(Quaternion::new(half, 0.0, 0.0, half))
+                .with_scale([2.0, 1.0, 1.0]),
+        );
+        cup.attach_splat(Splat::new([1.0, 0.0, 0.0], 0.5, [1.0, 0.0, 0.0, 0.5]));
+        table.attach_splat(Splat::new([0.0, 0.0, 0.0], 1.0, [0.0, 1.0, 0.0, 1.0]));
+        table.add_child(cup);
+        graph.add_node_to_root(table);
+        graph
+    }
+
+    #[test]
+    fn scenegraph_world_transforms() {
+        let graph = assembly();
+
+        let cup = graph.world_transform("cup").unwrap();
+        assert_eq!(transform_point(&cup, [0.0, 0.0, 0.0]), [10.0, 1.0, 0.0]);
+        assert!(graph.world_transform("missing").is_none());
+
+        let mut visited = Vec::new();
+        graph.traverse_with_transforms(|node, transform| {
+            visited.push((node.name.clone(), transform_point(transform, [0.0; 3])));
+        });
+        assert_eq!(visited[0], ("root".to_string(), [0.0, 0.0, 0.0]));
+        assert_eq!(visited[1], ("table".to_string(), [10.0, 0.0, 0.0]));
+        assert_eq!(visited[2].0, "cup");
+    }
+
+    #[test]
+    fn scenegraph_flatten() {
+        let world = assembly().flatten().unwrap();
+        assert_eq!(world.splat_count(), 2);
+
+        let table = &world.splats()[0];
+        assert_eq!(table.position, Point3D::new(10.0, 0.0, 0.0));
+        assert_eq!(table.covariance, [1.0, 0.0, 0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(table.metadata.get("node"), Some(&"table".to_string()));
+
+        // Local x is scaled by 2 and rotated onto world y
+        let cup = &world.splats()[1];
+        assert!((cup.position.x - 10.0).abs() < 1e-12);
+        assert!((cup.position.y - 3.0).abs() < 1e-12);
+        let expected = [0.25, 0.0, 0.0, 1.0, 0.0, 0.25];
+        for (actual, expected) in cup.covariance.iter().zip(expected.iter()) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert_eq!(cup.color, [1.0, 0.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn scenegraph_flatten_rotates_spherical_harmonics() {
+        use crate::scene::spherical_harmonics::SphericalHarmonics;
+
+        // Band 1, coefficient 3 responds to -x; after rotating 90 degrees about z it responds to -y
+        let mut harmonics = SphericalHarmonics::from_color([0.5, 0.5, 0.5], 1).unwrap();
+        harmonics.coefficients_mut()[3] = [1.0, 0.0, 0.0];
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let mut node = Node::new("rotated").with_transform(Transform::identity().with_rotation(Quaternion::new(half, 0.0, 0.0, half)));
+        node.attach_splat(Splat::new([0.0; 3], 1.0, [0.5, 0.5, 0.5, 1.0]).with_spherical_harmonics(harmonics.clone()));
+        let mut graph: SceneGraph<f64> = SceneGraph::new("sh");
+        graph.add_node_to_root(node);
+
+        let world = graph.flatten().unwrap();
+        let baked = &world.splats()[0];
+        let expected = harmonics.evaluate([-1.0, 0.0, 0.0]);
+        let actual = baked.color_for_direction([0.0, -1.0, 0.0]);
+        assert!((expected[0] - actual[0]).abs() < 1e-9);
+    }
 }
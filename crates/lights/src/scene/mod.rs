@@ -3,6 +3,10 @@
 //! Public submodules:
 //! - graph (SceneGraph, Node)
 //! - primitive (Splat<T>)
+//! - spherical_harmonics (SphericalHarmonics<T>)
+//! - transform (Transform<T>)
 
 pub mod graph;
 pub mod primitive;
+pub mod spherical_harmonics;
+pub mod transform;
@@ -1,6 +1,10 @@
 //! Primitive definitions for the `fuller` scene module.
 //!
-//! Defines `Splat<T>` which is a simple Gaussian-splat-like primitive.
+//! Defines `Splat<T>`, the precision-generic 3D Gaussian splat shared by the
+//! scene graph, `World` and the renderers. `renderer::world::GaussianSplat`
+//! converts to and from `Splat<f64>` without losing any geometry or
+//! appearance data. Splat IDs and metadata are `World` bookkeeping and have
+//! no place in `Splat`, so they do not survive the conversion.
 //!
 //! The generic parameter `T` is constrained to the project's numeric trait:
 //! `numerics::types::traits::FloatingPoint` (defaults to `f32`).
@@ -9,32 +13,167 @@
 //! in vector/matrix dependencies here. This keeps the surface small while
 //! remaining generic.
 
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{BorrowDecode, Decode, Encode};
 use std::fmt;
 use std::ops::{Add, Mul};
+use crate::numerics::covariance;
+use crate::numerics::types::matrix::Matrix4x4;
+use crate::numerics::types::quaternion::Quaternion;
 use crate::numerics::types::traits::FloatingPoint;
+use crate::scene::spherical_harmonics::SphericalHarmonics;
+use crate::scene::transform::{is_mirrored, rotation_part, transform_covariance, transform_point};
 
-/// A simple Splat (Gaussian splat primitive).
+/// A 3D Gaussian splat.
 ///
 /// Fields:
 /// - `position`: [x, y, z]
-/// - `radius`: scalar radius controlling spread
+/// - `covariance`: packed upper triangle `[xx, xy, xz, yy, yz, zz]`
+/// - `radius`: deprecated scalar spread, see the field docs
 /// - `color`: RGBA 4-tuple in `[0..1]` space
+/// - `opacity`: peak opacity in `[0..1]`, applied on top of the color alpha
+/// - `spherical_harmonics`: optional view-dependent color; when present its DC
+///   term matches the RGB part of `color`
 ///
 /// Generic parameter `T` defaults to `f32` and must implement the project's FloatingPoint trait.
 #[derive(Clone, PartialEq)]
 pub struct Splat<T: FloatingPoint = f32> {
     pub position: [T; 3],
+    pub covariance: [T; 6],
+
+    /// Scalar spread from before splats carried a covariance, kept so
+    /// existing code still compiles. `new` sets it, `scale` scales it and
+    /// `Add` sums it as before; setting or transforming the covariance resets
+    /// it to the largest per-axis standard deviation. Only `covariance` is
+    /// used for rendering.
+    #[deprecated(note = "use `covariance`, or `max_standard_deviation` for the widest axis")]
     pub radius: T,
+
     pub color: [T; 4],
+    pub opacity: T,
+    pub spherical_harmonics: Option<SphericalHarmonics<T>>,
 }
 
+#[allow(deprecated)]
 impl<T: FloatingPoint> Splat<T> {
-    /// Construct a new splat.
+    /// Construct a new isotropic splat whose standard deviation is `radius`.
     pub fn new(position: [T; 3], radius: T, color: [T; 4]) -> Self {
+        let variance = radius * radius;
         Self {
             position,
+            covariance: [variance, T::zero(), T::zero(), variance, T::zero(), variance],
             radius,
             color,
+            opacity: T::one(),
+            spherical_harmonics: None,
+        }
+    }
+
+    /// Construct a splat from a covariance and its appearance, without
+    /// touching the color the way `with_spherical_harmonics` does.
+    pub fn from_parts(
+        position: [T; 3],
+        covariance: [T; 6],
+        color: [T; 4],
+        opacity: T,
+        spherical_harmonics: Option<SphericalHarmonics<T>>,
+    ) -> Self {
+        Self {
+            position,
+            covariance,
+            radius: axis_standard_deviation(&covariance),
+            color,
+            opacity,
+            spherical_harmonics,
+        }
+    }
+
+    /// Set the covariance matrix.
+    pub fn with_covariance(mut self, covariance: [T; 6]) -> Self {
+        self.covariance = covariance;
+        self.radius = axis_standard_deviation(&covariance);
+        self
+    }
+
+    /// Set the covariance from a rotation quaternion and per-axis scale.
+    pub fn with_rotation_scale(self, rotation: Quaternion<T>, scale: [T; 3]) -> Self {
+        let rotation = Quaternion::new(rotation.w.to_f64(), rotation.x.to_f64(), rotation.y.to_f64(), rotation.z.to_f64());
+        let covariance = covariance::covariance_from_rotation_scale(rotation, scale.map(|s| s.to_f64()));
+        self.with_covariance(covariance.map(T::from_f64))
+    }
+
+    /// Set the opacity, clamped to `[0, 1]`.
+    pub fn with_opacity(mut self, opacity: T) -> Self {
+        self.opacity = T::from_f64(opacity.to_f64().clamp(0.0, 1.0));
+        self
+    }
+
+    /// Standard deviation along the widest axis of the Gaussian.
+    ///
+    /// For splats built with `new` this is the radius passed in. Degenerate
+    /// covariances fall back to the largest diagonal entry.
+    pub fn max_standard_deviation(&self) -> T {
+        let packed = self.covariance.map(|c| c.to_f64());
+        match covariance::rotation_scale_from_covariance(&packed) {
+            Ok((_, scale)) => T::from_f64(scale[0]),
+            Err(_) => T::from_f64(packed[0].max(packed[3]).max(packed[5]).max(0.0).sqrt()),
+        }
+    }
+
+    /// Convert to another precision.
+    pub fn cast<U: FloatingPoint>(&self) -> Splat<U> {
+        Splat {
+            position: self.position.map(|c| U::from_f64(c.to_f64())),
+            covariance: self.covariance.map(|c| U::from_f64(c.to_f64())),
+            radius: U::from_f64(self.radius.to_f64()),
+            color: self.color.map(|c| U::from_f64(c.to_f64())),
+            opacity: U::from_f64(self.opacity.to_f64()),
+            spherical_harmonics: self.spherical_harmonics.as_ref().map(|harmonics| harmonics.cast()),
+        }
+    }
+
+    /// Apply an affine transform: the position is mapped through `matrix`,
+    /// the covariance becomes `A * Sigma * A^T` for its linear part `A`, and
+    /// spherical harmonics are rotated with it. A mirrored `A` also inverts
+    /// the harmonics, so view-dependent color is reflected along with it.
+    pub fn transformed(&self, matrix: &Matrix4x4<f64>) -> Result<Self, String> {
+        let position = transform_point(matrix, self.position.map(|c| c.to_f64()));
+        let covariance = transform_covariance(matrix, &self.covariance.map(|c| c.to_f64())).map(T::from_f64);
+        Ok(Self {
+            position: position.map(T::from_f64),
+            covariance,
+            radius: axis_standard_deviation(&covariance),
+            color: self.color,
+            opacity: self.opacity,
+            spherical_harmonics: self
+                .spherical_harmonics
+                .as_ref()
+                .map(|harmonics| {
+                    let rotated = harmonics.rotate(&rotation_part(matrix))?;
+                    Ok::<_, String>(if is_mirrored(matrix) { rotated.invert() } else { rotated })
+                })
+                .transpose()?,
+        })
+    }
+
+    /// Attach spherical harmonic coefficients. The RGB part of `color` is
+    /// updated to the coefficients' base color.
+    pub fn with_spherical_harmonics(mut self, harmonics: SphericalHarmonics<T>) -> Self {
+        let [r, g, b] = harmonics.base_color();
+        self.color = [r, g, b, self.color[3]];
+        self.spherical_harmonics = Some(harmonics);
+        self
+    }
+
+    /// RGB color seen along `direction` (from the viewer towards the splat).
+    ///
+    /// Falls back to the flat color when no SH coefficients are present.
+    pub fn color_for_direction(&self, direction: [T; 3]) -> [T; 3] {
+        match &self.spherical_harmonics {
+            Some(harmonics) => harmonics.evaluate(direction),
+            None => [self.color[0], self.color[1], self.color[2]],
         }
     }
 
@@ -47,11 +186,17 @@ impl<T: FloatingPoint> Splat<T> {
     }
 
     /// Scale splat uniformly by a scalar. Returns new splat (does not mutate).
+    ///
+    /// Positions and standard deviations scale by `scalar`, so the covariance
+    /// scales by `scalar^2`.
     pub fn scale(&self, scalar: T) -> Self {
         Self {
             position: [self.position[0] * scalar, self.position[1] * scalar, self.position[2] * scalar],
+            covariance: self.covariance.map(|c| c * scalar * scalar),
             radius: self.radius * scalar,
             color: self.color,
+            opacity: self.opacity,
+            spherical_harmonics: self.spherical_harmonics.clone(),
         }
     }
 
@@ -59,13 +204,24 @@ impl<T: FloatingPoint> Splat<T> {
     pub fn translate(&self, offset: [T; 3]) -> Self {
         Self {
             position: [self.position[0] + offset[0], self.position[1] + offset[1], self.position[2] + offset[2]],
+            covariance: self.covariance,
             radius: self.radius,
             color: self.color,
+            opacity: self.opacity,
+            spherical_harmonics: self.spherical_harmonics.clone(),
         }
     }
 }
 
-/// Component-wise addition of two splats (adds positions, radii, and blends color by addition).
+/// Square root of the largest diagonal covariance entry, the value kept in
+/// the deprecated `radius` field.
+fn axis_standard_deviation<T: FloatingPoint>(covariance: &[T; 6]) -> T {
+    T::from_f64(covariance[0].to_f64().max(covariance[3].to_f64()).max(covariance[5].to_f64()).max(0.0).sqrt())
+}
+
+/// Component-wise addition of two splats (adds positions, radii, covariances,
+/// opacities, and blends color by addition).
+#[allow(deprecated)]
 impl<T:FloatingPoint> Add for Splat<T> {
     type Output = Self;
 
@@ -76,6 +232,7 @@ impl<T:FloatingPoint> Add for Splat<T> {
                 self.position[1] + rhs.position[1],
                 self.position[2] + rhs.position[2],
             ],
+            covariance: core::array::from_fn(|i| self.covariance[i] + rhs.covariance[i]),
             radius: self.radius + rhs.radius,
             color: [
                 self.color[0] + rhs.color[0],
@@ -83,6 +240,11 @@ impl<T:FloatingPoint> Add for Splat<T> {
                 self.color[2] + rhs.color[2],
                 self.color[3] + rhs.color[3],
             ],
+            opacity: self.opacity + rhs.opacity,
+            spherical_harmonics: match (self.spherical_harmonics, rhs.spherical_harmonics) {
+                (Some(lhs), Some(rhs)) => Some(lhs.combine(&rhs)),
+                (lhs, rhs) => lhs.or(rhs),
+            },
         }
     }
 }
@@ -96,6 +258,39 @@ impl<T:FloatingPoint> Mul<T> for Splat<T> {
     }
 }
 
+// Manual impls: the bincode derive does not support defaulted type parameters
+impl<T: FloatingPoint + Encode> Encode for Splat<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.position.encode(encoder)?;
+        self.covariance.encode(encoder)?;
+        #[allow(deprecated)]
+        self.radius.encode(encoder)?;
+        self.color.encode(encoder)?;
+        self.opacity.encode(encoder)?;
+        self.spherical_harmonics.encode(encoder)
+    }
+}
+
+impl<Context, T: FloatingPoint + Decode<Context>> Decode<Context> for Splat<T> {
+    #[allow(deprecated)]
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            position: Decode::decode(decoder)?,
+            covariance: Decode::decode(decoder)?,
+            radius: Decode::decode(decoder)?,
+            color: Decode::decode(decoder)?,
+            opacity: Decode::decode(decoder)?,
+            spherical_harmonics: Decode::decode(decoder)?,
+        })
+    }
+}
+
+impl<'de, Context, T: FloatingPoint + Decode<Context>> BorrowDecode<'de, Context> for Splat<T> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Decode::decode(decoder)
+    }
+}
+
 impl<T> fmt::Debug for Splat<T>
 where
     T: FloatingPoint + fmt::Display,
@@ -109,7 +304,18 @@ where
                     self.position[0], self.position[1], self.position[2]
                 ),
             )
-            .field("radius", &format_args!("{:.3}", self.radius))
+            .field(
+                "covariance",
+                &format_args!(
+                    "[{:.3}, {:.3}, {:.3}, {:.3}, {:.3}, {:.3}]",
+                    self.covariance[0],
+                    self.covariance[1],
+                    self.covariance[2],
+                    self.covariance[3],
+                    self.covariance[4],
+                    self.covariance[5]
+                ),
+            )
             .field(
                 "color",
                 &format_args!(
@@ -117,11 +323,13 @@ where
                     self.color[0], self.color[1], self.color[2], self.color[3]
                 ),
             )
+            .field("opacity", &format_args!("{:.3}", self.opacity))
             .finish()
     }
 }
 
 impl<T:FloatingPoint + std::fmt::Debug> fmt::Display for Splat<T> {
+    #[allow(deprecated)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Human readable single-line
         write!(
@@ -137,6 +345,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(deprecated)]
     fn splat_basic_ops() {
         let a = Splat::new([0.0f32, 0.0, 0.0], 1.0, [1.0, 0.0, 0.0, 1.0]);
         let b = Splat::new([1.0, 2.0, 3.0], 0.5, [0.0, 1.0, 0.0, 1.0]);
@@ -152,4 +361,98 @@ mod tests {
         let d = a.translate([0.5, 0.0, 0.0]);
         assert_eq!(d.position, [0.5, 0.0, 0.0]);
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn splat_covariance_ops() {
+        let a = Splat::new([0.0f32, 0.0, 0.0], 1.0, [1.0, 0.0, 0.0, 1.0]);
+        let b = Splat::new([1.0, 2.0, 3.0], 0.5, [0.0, 1.0, 0.0, 1.0]);
+
+        // Addition sums covariances alongside the legacy radii
+        let c = a.clone() + b.clone();
+        assert_eq!(c.covariance, [1.25, 0.0, 0.0, 1.25, 0.0, 1.25]);
+
+        let scaled = b * 2.0;
+        assert_eq!(scaled.covariance, [1.0, 0.0, 0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(scaled.max_standard_deviation(), 1.0);
+
+        // Setting the covariance resets the radius to the widest axis
+        let stretched = a.with_covariance([4.0, 0.0, 0.0, 1.0, 0.0, 0.25]);
+        assert_eq!(stretched.radius, 2.0);
+    }
+
+    #[test]
+    fn splat_spherical_harmonics() {
+        let flat = Splat::new([0.0f32, 0.0, 0.0], 1.0, [0.2, 0.4, 0.6, 0.5]);
+        assert_eq!(flat.color_for_direction([0.0, 0.0, 1.0]), [0.2, 0.4, 0.6]);
+
+        let mut harmonics = SphericalHarmonics::from_color([0.3, 0.3, 0.3], 1).unwrap();
+        harmonics.coefficients_mut()[2] = [0.5, 0.0, 0.0];
+        let splat = flat.with_spherical_harmonics(harmonics);
+        assert!((splat.color[0] - 0.3).abs() < 1e-6);
+        assert_eq!(splat.color[3], 0.5);
+
+        let toward = splat.color_for_direction([0.0, 0.0, 1.0]);
+        let away = splat.color_for_direction([0.0, 0.0, -1.0]);
+        assert!(toward[0] > away[0]);
+        assert!((toward[1] - away[1]).abs() < 1e-6);
+
+        // Transforms keep the coefficients
+        assert!(splat.translate([1.0, 0.0, 0.0]).spherical_harmonics.is_some());
+    }
+
+    #[test]
+    fn splat_covariance_and_precision() {
+        let isotropic = Splat::new([0.0f32, 0.0, 0.0], 0.3, [1.0; 4]);
+        assert_eq!(isotropic.max_standard_deviation(), 0.3);
+
+        let splat = Splat::new([1.0f64, 2.0, 3.0], 1.0, [0.5; 4])
+            .with_rotation_scale(Quaternion::new(0.9, 0.1, -0.3, 0.2), [0.5, 2.0, 0.1])
+            .with_opacity(1.5);
+        assert_eq!(splat.opacity, 1.0);
+        assert!((splat.max_standard_deviation() - 2.0).abs() < 1e-12);
+
+        let narrowed: Splat<f32> = splat.cast();
+        assert_eq!(narrowed.position, [1.0, 2.0, 3.0]);
+        assert_eq!(narrowed.cast::<f64>().cast::<f32>(), narrowed);
+    }
+
+    #[test]
+    fn splat_transformed() {
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let matrix = crate::scene::transform::Transform::<f64>::identity()
+            .with_rotation(Quaternion::new(half, 0.0, 0.0, half))
+            .with_scale([2.0, 1.0, 1.0])
+            .with_translation([0.0, 0.0, 1.0])
+            .to_matrix();
+        let moved = Splat::new([1.0f64, 0.0, 0.0], 0.5, [1.0; 4]).transformed(&matrix).unwrap();
+        assert!((moved.position[1] - 2.0).abs() < 1e-12);
+        assert!((moved.covariance[3] - 1.0).abs() < 1e-12);
+        assert!((moved.covariance[0] - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn splat_transformed_mirrored() {
+        let mut harmonics = SphericalHarmonics::<f64>::new(2).unwrap();
+        for (index, coefficient) in harmonics.coefficients_mut().iter_mut().enumerate() {
+            *coefficient = [0.1 * index as f64, -0.05 * index as f64, 0.2];
+        }
+        let splat = Splat::new([0.0f64; 3], 0.5, [1.0; 4]).with_spherical_harmonics(harmonics);
+
+        // Mirror x and turn 90 degrees about z
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let matrix = crate::scene::transform::Transform::<f64>::identity()
+            .with_rotation(Quaternion::new(half, 0.0, 0.0, half))
+            .with_scale([-1.0, 1.0, 1.0])
+            .to_matrix();
+        let mirrored = splat.transformed(&matrix).unwrap();
+        for direction in [[1.0, 0.0, 0.0], [0.2, -0.5, 0.8], [-0.6, 0.3, -0.1]] {
+            let mapped: [f64; 3] = core::array::from_fn(|i| (0..3).map(|k| matrix.data[i][k] * direction[k]).sum());
+            let expected = splat.color_for_direction(direction);
+            let actual = mirrored.color_for_direction(mapped);
+            for channel in 0..3 {
+                assert!((expected[channel] - actual[channel]).abs() < 1e-9);
+            }
+        }
+    }
 }
@@ -0,0 +1,504 @@
+//! Spherical harmonic color coefficients for view-dependent splat appearance.
+//!
+//! Trained 3D Gaussian splatting scenes store color as real spherical harmonic
+//! coefficients of degree 0 to 3 per splat, one RGB triple per basis function.
+//! The layout and basis constants follow the reference 3DGS implementation so
+//! imported coefficients can be used as-is:
+//!
+//! - coefficient 0 is the DC term, `color = 0.5 + SH_C0 * dc`
+//! - coefficients `1..(degree + 1)^2` are the higher bands in order
+//! - the view direction points from the camera towards the splat
+
+use crate::numerics::types::decomposition::Lu;
+use crate::numerics::types::traits::FloatingPoint;
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{BorrowDecode, Decode, Encode};
+
+/// Highest supported spherical harmonic degree.
+pub const MAX_SH_DEGREE: usize = 3;
+
+/// Degree 0 basis constant, `1 / (2 * sqrt(pi))`.
+pub const SH_C0: f64 = 0.28209479177387814;
+
+/// Degree 1 basis constant.
+const SH_C1: f64 = 0.4886025119029199;
+
+/// Degree 2 basis constants.
+const SH_C2: [f64; 5] = [
+    1.0925484305920792,
+    -1.0925484305920792,
+    0.31539156525252005,
+    -1.0925484305920792,
+    0.5462742152960396,
+];
+
+/// Degree 3 basis constants.
+const SH_C3: [f64; 7] = [
+    -0.5900435899266435,
+    2.890611442640554,
+    -0.4570457994644658,
+    0.3731763325901154,
+    -0.4570457994644658,
+    1.445305721320277,
+    -0.5900435899266435,
+];
+
+/// Sample directions used to fit rotated bands. Any set whose basis matrix is
+/// well conditioned for each band works; these are deliberately irregular.
+const ROTATION_SAMPLES: [[f64; 3]; 7] = [
+    [0.9, 0.2, 0.3],
+    [0.1, 0.95, 0.4],
+    [0.3, 0.2, 1.0],
+    [-0.7, 0.5, 0.4],
+    [0.6, -0.8, 0.1],
+    [-0.3, -0.4, 0.9],
+    [0.5, 0.6, -0.7],
+];
+
+/// Number of coefficients per color channel for a given degree.
+pub fn coefficient_count(degree: usize) -> usize {
+    (degree + 1) * (degree + 1)
+}
+
+/// Convert a flat color channel to its DC coefficient.
+pub fn color_to_dc(color: f64) -> f64 {
+    (color - 0.5) / SH_C0
+}
+
+/// Convert a DC coefficient to a flat color channel.
+pub fn dc_to_color(dc: f64) -> f64 {
+    SH_C0 * dc + 0.5
+}
+
+/// RGB spherical harmonic coefficients of a fixed degree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SphericalHarmonics<T: FloatingPoint = f32> {
+    degree: usize,
+    coefficients: Vec<[T; 3]>,
+}
+
+impl<T: FloatingPoint> SphericalHarmonics<T> {
+    /// Create zeroed coefficients of the given degree.
+    ///
+    /// A zeroed set evaluates to mid-grey in every direction.
+    pub fn new(degree: usize) -> Result<Self, String> {
+        validate_degree(degree)?;
+        Ok(Self {
+            degree,
+            coefficients: vec![[T::zero(); 3]; coefficient_count(degree)],
+        })
+    }
+
+    /// Create coefficients whose DC term reproduces a flat RGB color.
+    pub fn from_color(color: [T; 3], degree: usize) -> Result<Self, String> {
+        let mut harmonics = Self::new(degree)?;
+        harmonics.set_base_color(color);
+        Ok(harmonics)
+    }
+
+    /// Create from raw coefficients, DC term first.
+    pub fn from_coefficients(degree: usize, coefficients: Vec<[T; 3]>) -> Result<Self, String> {
+        validate_degree(degree)?;
+        let expected = coefficient_count(degree);
+        if coefficients.len() != expected {
+            return Err(format!(
+                "Degree {} spherical harmonics need {} coefficients, got {}",
+                degree,
+                expected,
+                coefficients.len()
+            ));
+        }
+        Ok(Self { degree, coefficients })
+    }
+
+    /// Spherical harmonic degree (0 to 3).
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// All coefficients, DC term first.
+    pub fn coefficients(&self) -> &[[T; 3]] {
+        &self.coefficients
+    }
+
+    /// Mutable access to all coefficients, DC term first.
+    pub fn coefficients_mut(&mut self) -> &mut [[T; 3]] {
+        &mut self.coefficients
+    }
+
+    /// The DC (degree 0) coefficient.
+    pub fn dc(&self) -> [T; 3] {
+        self.coefficients[0]
+    }
+
+    /// Change the degree, dropping higher bands or padding with zeros.
+    pub fn set_degree(&mut self, degree: usize) -> Result<(), String> {
+        validate_degree(degree)?;
+        self.degree = degree;
+        self.coefficients.resize(coefficient_count(degree), [T::zero(); 3]);
+        Ok(())
+    }
+
+    /// The view-independent color encoded by the DC term.
+    pub fn base_color(&self) -> [T; 3] {
+        self.dc().map(|dc| T::from_f64(dc_to_color(dc.to_f64())))
+    }
+
+    /// Set the DC term so that it reproduces a flat RGB color.
+    pub fn set_base_color(&mut self, color: [T; 3]) {
+        self.coefficients[0] = color.map(|channel| T::from_f64(color_to_dc(channel.to_f64())));
+    }
+
+    /// Evaluate the RGB color seen along a view direction.
+    ///
+    /// The direction does not need to be normalized. Negative results are
+    /// clamped to zero, matching the reference rasterizer.
+    pub fn evaluate(&self, direction: [T; 3]) -> [T; 3] {
+        evaluate_coefficients(&self.coefficients, direction.map(|component| component.to_f64())).map(T::from_f64)
+    }
+
+    /// Rotate the coefficients by a 3x3 rotation matrix.
+    ///
+    /// The result seen along `rotation * d` matches the original seen along
+    /// `d`, which is what a splat needs when its frame is rotated. Each band
+    /// is rotated independently by fitting it at fixed sample directions;
+    /// fails if the fit is singular.
+    pub fn rotate(&self, rotation: &[[f64; 3]; 3]) -> Result<Self, String> {
+        let mut rotated = self.clone();
+        for band in 1..=self.degree {
+            let start = band * band;
+            let size = 2 * band + 1;
+
+            // Rows: basis functions of this band at each sample direction
+            let mut system: Vec<Vec<f64>> = Vec::with_capacity(size);
+            let mut targets: Vec<[f64; 3]> = Vec::with_capacity(size);
+            for sample in ROTATION_SAMPLES.iter().take(size) {
+                let sample_basis = basis(self.degree, *sample);
+                system.push(sample_basis[start..start + size].to_vec());
+
+                // Original band evaluated at R^T * sample
+                let source: [f64; 3] = core::array::from_fn(|i| (0..3).map(|k| rotation[k][i] * sample[k]).sum());
+                let source_basis = basis(self.degree, source);
+                targets.push(core::array::from_fn(|channel| {
+                    (start..start + size)
+                        .map(|index| source_basis[index] * self.coefficients[index][channel].to_f64())
+                        .sum()
+                }));
+            }
+
+            let solution = solve_linear_system(&system, &targets)?;
+            for (coefficient, value) in rotated.coefficients[start..start + size].iter_mut().zip(solution) {
+                *coefficient = value.map(T::from_f64);
+            }
+        }
+        Ok(rotated)
+    }
+
+    /// Reflect the coefficients through the origin.
+    ///
+    /// The result seen along `-d` matches the original seen along `d`. Each
+    /// band has parity `(-1)^l`, so this negates the odd bands; together with
+    /// [`Self::rotate`] it handles mirrored frames.
+    pub fn invert(&self) -> Self {
+        let mut inverted = self.clone();
+        for band in (1..=self.degree).step_by(2) {
+            for coefficient in &mut inverted.coefficients[band * band..(band + 1) * (band + 1)] {
+                *coefficient = coefficient.map(|value| T::zero() - value);
+            }
+        }
+        inverted
+    }
+
+    /// Convert the coefficients to another float type.
+    pub fn cast<U: FloatingPoint>(&self) -> SphericalHarmonics<U> {
+        SphericalHarmonics {
+            degree: self.degree,
+            coefficients: self
+                .coefficients
+                .iter()
+                .map(|coefficient| coefficient.map(|value| U::from_f64(value.to_f64())))
+                .collect(),
+        }
+    }
+
+    /// Add another set of coefficients, padding to the larger degree.
+    pub fn combine(&self, other: &Self) -> Self {
+        let (mut sum, addend) = if self.degree >= other.degree {
+            (self.clone(), other)
+        } else {
+            (other.clone(), self)
+        };
+        for (target, source) in sum.coefficients.iter_mut().zip(addend.coefficients.iter()) {
+            for channel in 0..3 {
+                target[channel] = target[channel] + source[channel];
+            }
+        }
+        sum
+    }
+}
+
+impl<T: FloatingPoint + Encode> Encode for SphericalHarmonics<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.degree.encode(encoder)?;
+        self.coefficients.encode(encoder)
+    }
+}
+
+impl<Context, T: FloatingPoint + Decode<Context>> Decode<Context> for SphericalHarmonics<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let degree = usize::decode(decoder)?;
+        let coefficients = Vec::decode(decoder)?;
+        Self::from_coefficients(degree, coefficients).map_err(DecodeError::OtherString)
+    }
+}
+
+impl<'de, Context, T: FloatingPoint + Decode<Context>> BorrowDecode<'de, Context> for SphericalHarmonics<T> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Decode::decode(decoder)
+    }
+}
+
+/// Evaluate the RGB color of a borrowed coefficient block, as
+/// [`SphericalHarmonics::evaluate`] does, without building a
+/// `SphericalHarmonics`. The degree follows from the number of coefficients.
+pub fn evaluate_coefficients<T: FloatingPoint>(coefficients: &[[T; 3]], direction: [f64; 3]) -> [f64; 3] {
+    let degree = (0..=MAX_SH_DEGREE).rev().find(|&degree| coefficient_count(degree) <= coefficients.len()).unwrap_or(0);
+    let basis = basis(degree, direction);
+    let mut color = [0.5; 3];
+    for (coefficient, weight) in coefficients.iter().zip(basis.iter()) {
+        for (channel, value) in color.iter_mut().enumerate() {
+            *value += weight * coefficient[channel].to_f64();
+        }
+    }
+    color.map(|channel| channel.max(0.0))
+}
+
+/// Real SH basis functions up to `degree` for a (not necessarily unit) direction.
+fn basis(degree: usize, direction: [f64; 3]) -> [f64; 16] {
+    let [x, y, z] = direction;
+    let length = (x * x + y * y + z * z).sqrt();
+    let (x, y, z) = if length > 0.0 {
+        (x / length, y / length, z / length)
+    } else {
+        (0.0, 0.0, 0.0)
+    };
+
+    let mut basis = [0.0; 16];
+    basis[0] = SH_C0;
+    if degree > 0 {
+        basis[1] = -SH_C1 * y;
+        basis[2] = SH_C1 * z;
+        basis[3] = -SH_C1 * x;
+    }
+    if degree > 1 {
+        let (xx, yy, zz) = (x * x, y * y, z * z);
+        basis[4] = SH_C2[0] * x * y;
+        basis[5] = SH_C2[1] * y * z;
+        basis[6] = SH_C2[2] * (2.0 * zz - xx - yy);
+        basis[7] = SH_C2[3] * x * z;
+        basis[8] = SH_C2[4] * (xx - yy);
+        if degree > 2 {
+            basis[9] = SH_C3[0] * y * (3.0 * xx - yy);
+            basis[10] = SH_C3[1] * x * y * z;
+            basis[11] = SH_C3[2] * y * (4.0 * zz - xx - yy);
+            basis[12] = SH_C3[3] * z * (2.0 * zz - 3.0 * xx - 3.0 * yy);
+            basis[13] = SH_C3[4] * x * (4.0 * zz - xx - yy);
+            basis[14] = SH_C3[5] * z * (xx - yy);
+            basis[15] = SH_C3[6] * x * (xx - 3.0 * yy);
+        }
+    }
+    basis
+}
+
+/// Solve `a x = b` for each color channel of `b`, failing if `a` is singular.
+fn solve_linear_system(a: &[Vec<f64>], b: &[[f64; 3]]) -> Result<Vec<[f64; 3]>, String> {
+    // Bands have 2l + 1 coefficients
+    match b.len() {
+        3 => solve_with_lu::<3>(a, b),
+        5 => solve_with_lu::<5>(a, b),
+        7 => solve_with_lu::<7>(a, b),
+        n => Err(format!("Unsupported {}x{} spherical harmonic system", n, n)),
+    }
+}
+
+fn solve_with_lu<const N: usize>(a: &[Vec<f64>], b: &[[f64; 3]]) -> Result<Vec<[f64; 3]>, String> {
+    let lu = Lu::<f64, N>::new(core::array::from_fn(|i| core::array::from_fn(|j| a[i][j])));
+    let mut solution = vec![[0.0; 3]; N];
+    for channel in 0..3 {
+        let x = lu
+            .solve(core::array::from_fn(|i| b[i][channel]))
+            .ok_or_else(|| format!("Singular {}x{} spherical harmonic system", N, N))?;
+        for (value, x) in solution.iter_mut().zip(x) {
+            value[channel] = x;
+        }
+    }
+    Ok(solution)
+}
+
+fn validate_degree(degree: usize) -> Result<(), String> {
+    if degree > MAX_SH_DEGREE {
+        return Err(format!(
+            "Spherical harmonic degree {} exceeds the maximum of {}",
+            degree, MAX_SH_DEGREE
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coefficient_counts() {
+        assert_eq!(coefficient_count(0), 1);
+        assert_eq!(coefficient_count(1), 4);
+        assert_eq!(coefficient_count(2), 9);
+        assert_eq!(coefficient_count(3), 16);
+        assert!(SphericalHarmonics::<f32>::new(4).is_err());
+        assert!(SphericalHarmonics::<f32>::from_coefficients(1, vec![[0.0; 3]; 3]).is_err());
+    }
+
+    #[test]
+    fn test_flat_color_roundtrip() {
+        let harmonics = SphericalHarmonics::<f64>::from_color([0.2, 0.5, 0.9], 3).unwrap();
+        let base = harmonics.base_color();
+        assert!((base[0] - 0.2).abs() < 1e-12);
+        assert!((base[1] - 0.5).abs() < 1e-12);
+        assert!((base[2] - 0.9).abs() < 1e-12);
+
+        // Only the DC term is set, so every direction sees the flat color
+        for direction in [[1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.3, 0.4, -0.5]] {
+            let color = harmonics.evaluate(direction);
+            for channel in 0..3 {
+                assert!((color[channel] - base[channel]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_view_dependent_evaluation() {
+        let mut harmonics = SphericalHarmonics::<f32>::new(1).unwrap();
+        harmonics.set_base_color([0.5, 0.5, 0.5]);
+        // Band 1, coefficient 3 responds to -x
+        harmonics.coefficients_mut()[3] = [1.0, 0.0, 0.0];
+
+        let from_left = harmonics.evaluate([-1.0, 0.0, 0.0]);
+        let from_right = harmonics.evaluate([2.0, 0.0, 0.0]);
+        assert!((from_left[0] - (0.5 + SH_C1 as f32)).abs() < 1e-6);
+        assert!((from_right[0] - (0.5 - SH_C1 as f32)).abs() < 1e-6);
+        assert!((from_left[1] - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_negative_color_is_clamped() {
+        let harmonics = SphericalHarmonics::<f64>::from_coefficients(0, vec![[-10.0, 0.0, 0.0]]).unwrap();
+        assert_eq!(harmonics.evaluate([0.0, 0.0, 1.0])[0], 0.0);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let mut harmonics = SphericalHarmonics::<f64>::new(3).unwrap();
+        for (index, coefficient) in harmonics.coefficients_mut().iter_mut().enumerate() {
+            *coefficient = [0.3 * (index as f64).sin(), 0.2 * (index as f64 * 0.7).cos(), 0.05 * index as f64 / 16.0];
+        }
+
+        // 0.8 rad about a skewed axis
+        let axis = [0.36, 0.48, 0.8];
+        let (sin, cos) = 0.8_f64.sin_cos();
+        let rotation: [[f64; 3]; 3] = core::array::from_fn(|i| {
+            core::array::from_fn(|j| {
+                let identity = if i == j { 1.0 } else { 0.0 };
+                let cross = match (i, j) {
+                    (0, 1) => -axis[2],
+                    (0, 2) => axis[1],
+                    (1, 0) => axis[2],
+                    (1, 2) => -axis[0],
+                    (2, 0) => -axis[1],
+                    (2, 1) => axis[0],
+                    _ => 0.0,
+                };
+                cos * identity + sin * cross + (1.0 - cos) * axis[i] * axis[j]
+            })
+        });
+
+        let rotated = harmonics.rotate(&rotation).unwrap();
+        assert_eq!(rotated.dc(), harmonics.dc());
+        for direction in [[1.0, 0.0, 0.0], [0.2, -0.5, 0.8], [-0.6, 0.3, -0.1]] {
+            let turned: [f64; 3] = core::array::from_fn(|i| (0..3).map(|k| rotation[i][k] * direction[k]).sum());
+            let expected = harmonics.evaluate(direction);
+            let actual = rotated.evaluate(turned);
+            for channel in 0..3 {
+                assert!((expected[channel] - actual[channel]).abs() < 1e-9);
+            }
+        }
+
+        // The identity leaves coefficients unchanged
+        let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        for (a, b) in harmonics.rotate(&identity).unwrap().coefficients().iter().zip(harmonics.coefficients()) {
+            for channel in 0..3 {
+                assert!((a[channel] - b[channel]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inversion() {
+        let mut harmonics = SphericalHarmonics::<f64>::new(3).unwrap();
+        for (index, coefficient) in harmonics.coefficients_mut().iter_mut().enumerate() {
+            *coefficient = [0.3 * (index as f64).sin(), 0.2 * (index as f64 * 0.7).cos(), 0.05 * index as f64 / 16.0];
+        }
+
+        let inverted = harmonics.invert();
+        for direction in [[1.0, 0.0, 0.0], [0.2, -0.5, 0.8], [-0.6, 0.3, -0.1]] {
+            let expected = harmonics.evaluate(direction);
+            let actual = inverted.evaluate(direction.map(|component: f64| -component));
+            for channel in 0..3 {
+                assert!((expected[channel] - actual[channel]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_singular_system() {
+        // The second row is twice the first
+        let system = vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0], vec![0.0, 0.0, 1.0]];
+        assert!(solve_linear_system(&system, &[[1.0; 3]; 3]).is_err());
+
+        let system = vec![vec![2.0, 0.0, 0.0], vec![0.0, 4.0, 0.0], vec![1.0, 0.0, 1.0]];
+        let solution = solve_linear_system(&system, &[[2.0, 4.0, 6.0]; 3]).unwrap();
+        assert_eq!(solution, vec![[1.0, 2.0, 3.0], [0.5, 1.0, 1.5], [1.0, 2.0, 3.0]]);
+    }
+
+    #[test]
+    fn test_bincode_roundtrip() {
+        let config = bincode::config::standard();
+        let harmonics = SphericalHarmonics::<f32>::from_color([0.1, 0.2, 0.3], 2).unwrap();
+        let encoded = bincode::encode_to_vec(&harmonics, config).unwrap();
+        let (decoded, _): (SphericalHarmonics<f32>, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, harmonics);
+
+        // A coefficient count that does not match the degree is rejected
+        let invalid = bincode::encode_to_vec((1usize, vec![[0.0f32; 3]; 2]), config).unwrap();
+        assert!(bincode::decode_from_slice::<SphericalHarmonics<f32>, _>(&invalid, config).is_err());
+    }
+
+    #[test]
+    fn test_set_degree_and_add() {
+        let mut low = SphericalHarmonics::<f64>::from_coefficients(0, vec![[1.0, 2.0, 3.0]]).unwrap();
+        let high = SphericalHarmonics::<f64>::from_coefficients(1, vec![[1.0; 3]; 4]).unwrap();
+
+        let sum = low.combine(&high);
+        assert_eq!(sum.degree(), 1);
+        assert_eq!(sum.coefficients()[0], [2.0, 3.0, 4.0]);
+        assert_eq!(sum.coefficients()[3], [1.0; 3]);
+
+        low.set_degree(2).unwrap();
+        assert_eq!(low.coefficients().len(), 9);
+        assert_eq!(low.coefficients()[8], [0.0; 3]);
+        low.set_degree(0).unwrap();
+        assert_eq!(low.coefficients(), &[[1.0, 2.0, 3.0]]);
+    }
+}
@@ -0,0 +1,257 @@
+//! Node transforms for the scene graph.
+//!
+//! A `Transform` is the local placement of a node relative to its parent:
+//! scale first, then rotate, then translate. World transforms are composed
+//! as `Matrix4x4<f64>` so non-uniform scales under rotated parents stay exact.
+
+use crate::numerics::types::matrix::{Matrix3x3, Matrix4x4};
+use crate::numerics::types::quaternion::Quaternion;
+use crate::numerics::types::traits::FloatingPoint;
+use crate::numerics::types::vector::Vector3;
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{BorrowDecode, Decode, Encode};
+
+/// Local translation, rotation and scale of a scene graph node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transform<T: FloatingPoint = f32> {
+    /// Translation [x, y, z]
+    pub translation: [T; 3],
+
+    /// Rotation, normalized when converted to a matrix
+    pub rotation: Quaternion<T>,
+
+    /// Per-axis scale [x, y, z]
+    pub scale: [T; 3],
+}
+
+impl<T: FloatingPoint> Transform<T> {
+    /// The identity transform.
+    pub fn identity() -> Self {
+        Self {
+            translation: [T::zero(); 3],
+            rotation: Quaternion::identity(),
+            scale: [T::one(); 3],
+        }
+    }
+
+    /// A pure translation.
+    pub fn from_translation(translation: [T; 3]) -> Self {
+        Self::identity().with_translation(translation)
+    }
+
+    /// Set the translation.
+    pub fn with_translation(mut self, translation: [T; 3]) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    /// Set the rotation.
+    pub fn with_rotation(mut self, rotation: Quaternion<T>) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set the per-axis scale.
+    pub fn with_scale(mut self, scale: [T; 3]) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set a uniform scale.
+    pub fn with_uniform_scale(self, scale: T) -> Self {
+        self.with_scale([scale; 3])
+    }
+
+    /// The transform as a matrix, `M = T * R * S`.
+    pub fn to_matrix(&self) -> Matrix4x4<f64> {
+        Matrix4x4::from_trs(
+            Vector3::from(self.translation.map(|t| t.to_f64())),
+            Quaternion::from(<[T; 4]>::from(self.rotation).map(|c| c.to_f64())),
+            Vector3::from(self.scale.map(|s| s.to_f64())),
+        )
+    }
+
+    /// Split an affine matrix back into a transform; see `Matrix4x4::decompose`.
+    pub fn from_matrix(matrix: &Matrix4x4<f64>) -> Option<Self> {
+        let (translation, rotation, scale) = matrix.decompose()?;
+        let (translation, rotation, scale): ([f64; 3], [f64; 4], [f64; 3]) =
+            (translation.into(), rotation.into(), scale.into());
+        Some(Self {
+            translation: translation.map(T::from_f64),
+            rotation: rotation.map(T::from_f64).into(),
+            scale: scale.map(T::from_f64),
+        })
+    }
+}
+
+impl<T: FloatingPoint> Default for Transform<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+// Manual impls: the bincode derive does not support defaulted type parameters
+impl<T: FloatingPoint + Encode> Encode for Transform<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.translation.encode(encoder)?;
+        self.rotation.encode(encoder)?;
+        self.scale.encode(encoder)
+    }
+}
+
+impl<Context, T: FloatingPoint + Decode<Context>> Decode<Context> for Transform<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            translation: Decode::decode(decoder)?,
+            rotation: Decode::decode(decoder)?,
+            scale: Decode::decode(decoder)?,
+        })
+    }
+}
+
+impl<'de, Context, T: FloatingPoint + Decode<Context>> BorrowDecode<'de, Context> for Transform<T> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Decode::decode(decoder)
+    }
+}
+
+/// Apply an affine matrix to a point.
+pub fn transform_point(matrix: &Matrix4x4<f64>, point: [f64; 3]) -> [f64; 3] {
+    matrix.transform_point(point.into()).into()
+}
+
+/// Transform a packed covariance `[xx, xy, xz, yy, yz, zz]` by the linear
+/// part `A` of an affine matrix: `Sigma' = A * Sigma * A^T`.
+pub fn transform_covariance(matrix: &Matrix4x4<f64>, covariance: &[f64; 6]) -> [f64; 6] {
+    let m = &matrix.data;
+    let c = covariance;
+    let sigma = [[c[0], c[1], c[2]], [c[1], c[3], c[4]], [c[2], c[4], c[5]]];
+
+    let mut a_sigma = [[0.0; 3]; 3];
+    for (row, a_sigma_row) in a_sigma.iter_mut().enumerate() {
+        for (column, value) in a_sigma_row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| m[row][k] * sigma[k][column]).sum();
+        }
+    }
+    let entry = |i: usize, j: usize| (0..3).map(|k| a_sigma[i][k] * m[j][k]).sum::<f64>();
+    [entry(0, 0), entry(0, 1), entry(0, 2), entry(1, 1), entry(1, 2), entry(2, 2)]
+}
+
+/// Whether the linear part of an affine matrix flips handedness.
+pub fn is_mirrored(matrix: &Matrix4x4<f64>) -> bool {
+    matrix.linear_part().determinant() < 0.0
+}
+
+/// The rotation in the linear part of an affine matrix.
+///
+/// Columns are orthonormalized with Gram-Schmidt, which removes scale and
+/// is exact whenever the matrix has no shear. For a mirrored matrix (see
+/// [`is_mirrored`]) the orthonormal part `Q` is not a rotation; the result
+/// is then `-Q`, so that `Q` is this rotation followed by a point inversion.
+pub fn rotation_part(matrix: &Matrix4x4<f64>) -> [[f64; 3]; 3] {
+    let m = &matrix.data;
+    let mut columns: [[f64; 3]; 3] = core::array::from_fn(|column| [m[0][column], m[1][column], m[2][column]]);
+
+    for index in 0..3 {
+        for previous in 0..index {
+            let projection: f64 = (0..3).map(|k| columns[index][k] * columns[previous][k]).sum();
+            let previous_column = columns[previous];
+            for (value, previous_value) in columns[index].iter_mut().zip(previous_column) {
+                *value -= projection * previous_value;
+            }
+        }
+        let length = columns[index].iter().map(|v| v * v).sum::<f64>().sqrt();
+        if length > 0.0 {
+            columns[index] = columns[index].map(|v| v / length);
+        }
+    }
+
+    // Split a mirrored frame into a proper rotation and an inversion. The
+    // columns as rows form the transpose, which has the same determinant.
+    if Matrix3x3::new(columns).determinant() < 0.0 {
+        columns = columns.map(|column| column.map(|v| -v));
+    }
+
+    core::array::from_fn(|row| core::array::from_fn(|column| columns[column][row]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_and_translation() {
+        let identity = Transform::<f32>::default().to_matrix();
+        assert_eq!(identity, Matrix4x4::identity());
+
+        let matrix = Transform::<f32>::from_translation([1.0, 2.0, 3.0]).to_matrix();
+        assert_eq!(transform_point(&matrix, [1.0, 1.0, 1.0]), [2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_scale_then_rotate_then_translate() {
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        // 90 degrees about z, scale x by 2, then move up by 1
+        let transform = Transform::<f64>::identity()
+            .with_rotation(Quaternion::new(half, 0.0, 0.0, half))
+            .with_scale([2.0, 1.0, 1.0])
+            .with_translation([0.0, 0.0, 1.0]);
+        let point = transform_point(&transform.to_matrix(), [1.0, 0.0, 0.0]);
+        assert!((point[0] - 0.0).abs() < 1e-12);
+        assert!((point[1] - 2.0).abs() < 1e-12);
+        assert!((point[2] - 1.0).abs() < 1e-12);
+
+        let rotation = rotation_part(&transform.to_matrix());
+        assert!((rotation[1][0] - 1.0).abs() < 1e-12);
+        assert!((rotation[0][1] + 1.0).abs() < 1e-12);
+
+        // The same rotation built from a quaternion
+        let quaternion = Quaternion::from_axis_angle([0.0, 0.0, 1.0].into(), std::f64::consts::FRAC_PI_2);
+        let rebuilt = transform.clone().with_rotation(quaternion);
+        assert_eq!(rebuilt.rotation, quaternion);
+        let point = transform_point(&rebuilt.to_matrix(), [1.0, 0.0, 0.0]);
+        assert!((point[1] - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_from_matrix() {
+        let transform = Transform::<f64>::identity()
+            .with_rotation(Quaternion::from_euler(0.2, 0.4, -0.9))
+            .with_scale([1.5, 0.5, 2.0])
+            .with_translation([3.0, -1.0, 0.25]);
+        let decomposed = Transform::<f64>::from_matrix(&transform.to_matrix()).unwrap();
+        for (a, b) in decomposed.to_matrix().data.iter().flatten().zip(transform.to_matrix().data.iter().flatten()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_covariance_transform() {
+        let transform = Transform::<f64>::identity().with_scale([2.0, 1.0, 0.5]);
+        let covariance = transform_covariance(&transform.to_matrix(), &[1.0, 0.0, 0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(covariance, [4.0, 0.0, 0.0, 1.0, 0.0, 0.25]);
+
+        // Translation does not affect covariance
+        let moved = Transform::<f64>::from_translation([5.0, 5.0, 5.0]).to_matrix();
+        assert_eq!(transform_covariance(&moved, &covariance), covariance);
+    }
+
+    #[test]
+    fn test_mirrored_rotation_part() {
+        let mirrored = Transform::<f64>::identity().with_scale([-1.0, 1.0, 1.0]).to_matrix();
+        let rotation = rotation_part(&mirrored);
+        assert!((Matrix3x3::new(rotation).determinant() - 1.0).abs() < 1e-12);
+        assert!(is_mirrored(&mirrored));
+
+        // Mirroring x is a half turn about x followed by an inversion
+        let expected = [[1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, -1.0]];
+        for (row, expected_row) in rotation.iter().zip(expected) {
+            for (value, expected_value) in row.iter().zip(expected_row) {
+                assert!((value - expected_value).abs() < 1e-12);
+            }
+        }
+        assert!(!is_mirrored(&Transform::<f64>::identity().with_scale([-1.0, -1.0, 1.0]).to_matrix()));
+    }
+}
@@ -0,0 +1,366 @@
+//! Image-difference metrics between two rendered frames.
+//!
+//! Frames are compared on their color channels after clamping to the
+//! displayable `[0, 1]` range, so the numbers describe differences a viewer
+//! could see. PSNR uses a peak value of 1.0 and SSIM follows Wang et al.
+//! (2004) on Rec. 709 luminance with an 11x11 Gaussian window (sigma 1.5).
+
+use crate::renderer::framebuffer::Framebuffer;
+
+/// Half-width of the SSIM window.
+const SSIM_RADIUS: usize = 5;
+
+/// Standard deviation of the SSIM window, in pixels.
+const SSIM_SIGMA: f64 = 1.5;
+
+/// SSIM stabilizer for the luminance term, `(0.01 L)^2` with `L = 1`.
+const SSIM_C1: f64 = 0.01 * 0.01;
+
+/// SSIM stabilizer for the contrast term, `(0.03 L)^2` with `L = 1`.
+const SSIM_C2: f64 = 0.03 * 0.03;
+
+/// Heatmap colors from no error to full-scale error.
+const HEATMAP_STOPS: [[f32; 3]; 5] = [
+    [0.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0],
+    [0.0, 1.0, 1.0],
+    [1.0, 1.0, 0.0],
+    [1.0, 0.0, 0.0],
+];
+
+/// Differences between a reference frame and a test frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageDiff {
+    width: u32,
+    height: u32,
+
+    /// Root-mean-square color error of each pixel, row-major
+    pixel_errors: Vec<f32>,
+
+    /// Largest per-pixel error
+    pub max_error: f64,
+
+    /// Mean per-pixel error
+    pub mean_error: f64,
+
+    /// Mean squared error over all color channels
+    pub mse: f64,
+
+    /// Peak signal-to-noise ratio in decibels; infinite for identical frames
+    pub psnr: f64,
+
+    /// Mean structural similarity, 1.0 for identical frames
+    pub ssim: f64,
+}
+
+impl ImageDiff {
+    /// Compare `test` against `reference`.
+    ///
+    /// Both frames must have the same dimensions.
+    pub fn compare(reference: &Framebuffer, test: &Framebuffer) -> Result<Self, String> {
+        let mse = mean_squared_error(reference, test)?;
+        let pixel_errors: Vec<f32> = reference
+            .pixels()
+            .iter()
+            .zip(test.pixels())
+            .map(|(a, b)| (squared_error(a, b) / 3.0).sqrt() as f32)
+            .collect();
+
+        let count = pixel_errors.len();
+        let (mean_error, max_error) = if count == 0 {
+            (0.0, 0.0)
+        } else {
+            (
+                pixel_errors.iter().map(|&error| error as f64).sum::<f64>() / count as f64,
+                pixel_errors.iter().fold(0.0f64, |max, &error| max.max(error as f64)),
+            )
+        };
+
+        Ok(Self {
+            width: reference.width(),
+            height: reference.height(),
+            pixel_errors,
+            max_error,
+            mean_error,
+            mse,
+            psnr: psnr_from_mse(mse),
+            ssim: ssim(reference, test)?,
+        })
+    }
+
+    /// Width of the compared frames.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height of the compared frames.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Root-mean-square color error of each pixel, in row-major order.
+    pub fn pixel_errors(&self) -> &[f32] {
+        &self.pixel_errors
+    }
+
+    /// Error of the pixel at `(x, y)`, or `None` if out of bounds.
+    pub fn pixel_error(&self, x: u32, y: u32) -> Option<f32> {
+        if x < self.width && y < self.height {
+            Some(self.pixel_errors[y as usize * self.width as usize + x as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Whether the frames are identical after clamping.
+    pub fn is_identical(&self) -> bool {
+        self.max_error == 0.0
+    }
+
+    /// Quality in `[0, 1]` suitable for `PrecisionPerformance::quality_score`.
+    ///
+    /// This is the SSIM clamped to the unit range.
+    pub fn quality_score(&self) -> f32 {
+        self.ssim.clamp(0.0, 1.0) as f32
+    }
+
+    /// Render the per-pixel error as a heatmap.
+    ///
+    /// Errors are scaled so that `full_scale` maps to red; zero error is
+    /// black. Pass `self.max_error` to stretch the map over the observed range.
+    pub fn heatmap(&self, full_scale: f64) -> Framebuffer {
+        let mut heatmap = Framebuffer::new(self.width, self.height);
+        let scale = if full_scale > 0.0 { 1.0 / full_scale } else { 0.0 };
+        for (pixel, &error) in heatmap.pixels_mut().iter_mut().zip(&self.pixel_errors) {
+            let [r, g, b] = heatmap_color((error as f64 * scale) as f32);
+            *pixel = [r, g, b, 1.0];
+        }
+        heatmap
+    }
+}
+
+/// Mean squared error over the color channels of two frames.
+pub fn mean_squared_error(reference: &Framebuffer, test: &Framebuffer) -> Result<f64, String> {
+    check_dimensions(reference, test)?;
+    let count = reference.pixels().len();
+    if count == 0 {
+        return Ok(0.0);
+    }
+    let squared_sum: f64 = reference.pixels().iter().zip(test.pixels()).map(|(a, b)| squared_error(a, b)).sum();
+    Ok(squared_sum / (3 * count) as f64)
+}
+
+/// Peak signal-to-noise ratio of `test` against `reference`, in decibels.
+pub fn psnr(reference: &Framebuffer, test: &Framebuffer) -> Result<f64, String> {
+    mean_squared_error(reference, test).map(psnr_from_mse)
+}
+
+/// Mean structural similarity of `test` against `reference`.
+pub fn ssim(reference: &Framebuffer, test: &Framebuffer) -> Result<f64, String> {
+    check_dimensions(reference, test)?;
+    let (width, height) = (reference.width() as usize, reference.height() as usize);
+    if width == 0 || height == 0 {
+        return Ok(1.0);
+    }
+
+    let x = luminance(reference);
+    let y = luminance(test);
+    let product = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(a, b)| a * b).collect::<Vec<f64>>();
+
+    let kernel = gaussian_kernel();
+    let mu_x = blur(&x, width, height, &kernel);
+    let mu_y = blur(&y, width, height, &kernel);
+    let xx = blur(&product(&x, &x), width, height, &kernel);
+    let yy = blur(&product(&y, &y), width, height, &kernel);
+    let xy = blur(&product(&x, &y), width, height, &kernel);
+
+    let total: f64 = (0..width * height)
+        .map(|i| {
+            let (mx, my) = (mu_x[i], mu_y[i]);
+            let variance_x = xx[i] - mx * mx;
+            let variance_y = yy[i] - my * my;
+            let covariance = xy[i] - mx * my;
+            ((2.0 * mx * my + SSIM_C1) * (2.0 * covariance + SSIM_C2))
+                / ((mx * mx + my * my + SSIM_C1) * (variance_x + variance_y + SSIM_C2))
+        })
+        .sum();
+    Ok(total / (width * height) as f64)
+}
+
+/// Convert a PSNR-style mean squared error into decibels with a peak of 1.0.
+fn psnr_from_mse(mse: f64) -> f64 {
+    if mse > 0.0 {
+        -10.0 * mse.log10()
+    } else {
+        f64::INFINITY
+    }
+}
+
+fn check_dimensions(reference: &Framebuffer, test: &Framebuffer) -> Result<(), String> {
+    if reference.width() != test.width() || reference.height() != test.height() {
+        return Err(format!(
+            "Frame size {}x{} does not match reference {}x{}",
+            test.width(),
+            test.height(),
+            reference.width(),
+            reference.height()
+        ));
+    }
+    Ok(())
+}
+
+/// Sum of squared differences over the clamped color channels of a pixel.
+fn squared_error(a: &[f32; 4], b: &[f32; 4]) -> f64 {
+    (0..3)
+        .map(|channel| {
+            let difference = clamp_channel(a[channel]) - clamp_channel(b[channel]);
+            difference * difference
+        })
+        .sum()
+}
+
+fn clamp_channel(value: f32) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, 1.0) as f64
+    }
+}
+
+fn luminance(framebuffer: &Framebuffer) -> Vec<f64> {
+    framebuffer
+        .pixels()
+        .iter()
+        .map(|pixel| {
+            0.2126 * clamp_channel(pixel[0]) + 0.7152 * clamp_channel(pixel[1]) + 0.0722 * clamp_channel(pixel[2])
+        })
+        .collect()
+}
+
+fn gaussian_kernel() -> [f64; 2 * SSIM_RADIUS + 1] {
+    let mut kernel = [0.0; 2 * SSIM_RADIUS + 1];
+    for (i, weight) in kernel.iter_mut().enumerate() {
+        let offset = i as f64 - SSIM_RADIUS as f64;
+        *weight = (-offset * offset / (2.0 * SSIM_SIGMA * SSIM_SIGMA)).exp();
+    }
+    kernel
+}
+
+/// Separable Gaussian blur. Taps falling outside the image are dropped and
+/// the remaining weights renormalized, so borders are not darkened.
+fn blur(values: &[f64], width: usize, height: usize, kernel: &[f64]) -> Vec<f64> {
+    let pass = |input: &[f64], horizontal: bool| {
+        let mut output = vec![0.0; input.len()];
+        for y in 0..height {
+            for x in 0..width {
+                let (position, limit) = if horizontal { (x, width) } else { (y, height) };
+                let mut sum = 0.0;
+                let mut weight_sum = 0.0;
+                for (i, weight) in kernel.iter().enumerate() {
+                    let Some(tap) = (position + i).checked_sub(SSIM_RADIUS).filter(|&tap| tap < limit) else {
+                        continue;
+                    };
+                    let index = if horizontal { y * width + tap } else { tap * width + x };
+                    sum += weight * input[index];
+                    weight_sum += weight;
+                }
+                output[y * width + x] = sum / weight_sum;
+            }
+        }
+        output
+    };
+    pass(&pass(values, true), false)
+}
+
+fn heatmap_color(t: f32) -> [f32; 3] {
+    let t = if t.is_nan() { 1.0 } else { t.clamp(0.0, 1.0) };
+    let segments = (HEATMAP_STOPS.len() - 1) as f32;
+    let position = t * segments;
+    let index = (position as usize).min(HEATMAP_STOPS.len() - 2);
+    let fraction = position - index as f32;
+    let (from, to) = (HEATMAP_STOPS[index], HEATMAP_STOPS[index + 1]);
+    [0, 1, 2].map(|channel| from[channel] + (to[channel] - from[channel]) * fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient(width: u32, height: u32) -> Framebuffer {
+        let mut framebuffer = Framebuffer::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let value = (x + y) as f32 / (width + height) as f32;
+                framebuffer.set_pixel(x, y, [value, 1.0 - value, 0.5, 1.0]);
+            }
+        }
+        framebuffer
+    }
+
+    #[test]
+    fn test_identical_frames() {
+        let frame = gradient(16, 12);
+        let diff = ImageDiff::compare(&frame, &frame).unwrap();
+        assert!(diff.is_identical());
+        assert_eq!(diff.mse, 0.0);
+        assert_eq!(diff.psnr, f64::INFINITY);
+        assert!((diff.ssim - 1.0).abs() < 1e-12);
+        assert_eq!(diff.quality_score(), 1.0);
+        assert_eq!(diff.heatmap(1.0).pixel(3, 3), Some([0.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_uniform_offset() {
+        let reference = gradient(16, 16);
+        let mut test = reference.clone();
+        for pixel in test.pixels_mut() {
+            pixel[2] += 0.1;
+        }
+
+        // One of three channels off by 0.1, up to f32 rounding
+        let diff = ImageDiff::compare(&reference, &test).unwrap();
+        assert!((diff.mse - 0.01 / 3.0).abs() < 1e-8);
+        assert!((diff.psnr - 10.0 * (3.0f64 / 0.01).log10()).abs() < 1e-4);
+        assert!((diff.max_error - (0.01f64 / 3.0).sqrt()).abs() < 1e-6);
+        assert!((diff.mean_error - diff.max_error).abs() < 1e-6);
+        assert!(diff.ssim < 1.0 && diff.ssim > 0.9);
+        assert!((psnr(&reference, &test).unwrap() - diff.psnr).abs() < 1e-12);
+        assert_eq!(mean_squared_error(&reference, &test).unwrap(), diff.mse);
+        assert!(mean_squared_error(&reference, &gradient(8, 8)).is_err());
+    }
+
+    #[test]
+    fn test_structure_loss_lowers_ssim() {
+        let reference = gradient(24, 24);
+        let mut noisy = reference.clone();
+        for (i, pixel) in noisy.pixels_mut().iter_mut().enumerate() {
+            let noise = if i % 2 == 0 { 0.2 } else { -0.2 };
+            pixel[1] = (pixel[1] + noise).clamp(0.0, 1.0);
+        }
+        let mut flat = Framebuffer::new(24, 24);
+        flat.clear([0.5, 0.5, 0.5, 1.0]);
+
+        let noisy_ssim = ssim(&reference, &noisy).unwrap();
+        let flat_ssim = ssim(&reference, &flat).unwrap();
+        assert!(noisy_ssim < 0.5);
+        assert!(flat_ssim < 0.9);
+        assert!(ImageDiff::compare(&reference, &Framebuffer::new(24, 23)).is_err());
+    }
+
+    #[test]
+    fn test_heatmap_scale() {
+        let reference = Framebuffer::new(2, 1);
+        let mut test = reference.clone();
+        test.set_pixel(1, 0, [1.0, 1.0, 1.0, 1.0]);
+
+        let diff = ImageDiff::compare(&reference, &test).unwrap();
+        assert_eq!(diff.pixel_error(1, 0), Some(1.0));
+        assert_eq!(diff.pixel_error(2, 0), None);
+
+        let heatmap = diff.heatmap(diff.max_error);
+        assert_eq!(heatmap.pixel(0, 0), Some([0.0, 0.0, 0.0, 1.0]));
+        assert_eq!(heatmap.pixel(1, 0), Some([1.0, 0.0, 0.0, 1.0]));
+        // Half scale lands on the middle stop
+        assert_eq!(diff.heatmap(2.0).pixel(1, 0), Some([0.0, 1.0, 1.0, 1.0]));
+    }
+}
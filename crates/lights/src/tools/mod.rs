@@ -0,0 +1,4 @@
+//! Development tools built on top of the renderer.
+
+pub mod image_diff;
+pub mod precision_comparison;
@@ -0,0 +1,204 @@
+//! Render the same scene at several precisions or on several backends and
+//! measure how far each frame is from a reference.
+//!
+//! Every render binds its own copy of the world, so all frames use the
+//! world's camera and background. Renderers are created through their
+//! factories; pass a `viewport_size` parameter (or the backend's equivalent)
+//! so all frames come out the same size.
+
+use crate::renderer::framebuffer::Framebuffer;
+use crate::renderer::manager::RendererManager;
+use crate::renderer::world::World;
+use crate::renderer::{DataPrecision, RendererFactory};
+use crate::tools::image_diff::ImageDiff;
+use std::time::{Duration, Instant};
+
+/// One renderer configuration to render with.
+#[derive(Clone, Copy)]
+pub struct RenderSetup<'a> {
+    /// Factory creating the renderer
+    pub factory: &'a dyn RendererFactory,
+
+    /// Precision the renderer is created with
+    pub precision: DataPrecision,
+
+    /// Factory parameters
+    pub parameters: &'a str,
+}
+
+impl<'a> RenderSetup<'a> {
+    /// Create a setup for `factory` at `precision`.
+    pub fn new(factory: &'a dyn RendererFactory, precision: DataPrecision, parameters: &'a str) -> Self {
+        Self { factory, precision, parameters }
+    }
+
+    /// Name of the renderer, as reported by its factory.
+    pub fn renderer_name(&self) -> String {
+        self.factory.get_info().name
+    }
+
+    /// Render a single frame of `world` and read it back.
+    ///
+    /// Returns the frame and the time spent in `render_frame`.
+    pub fn render(&self, world: &World) -> Result<(Framebuffer, Duration), String> {
+        let mut renderer = self
+            .factory
+            .create(self.precision, self.parameters)
+            .map_err(|error| error.to_string())?;
+        renderer.set_world(world.clone())?;
+        renderer.start()?;
+
+        let start = Instant::now();
+        let rendered = renderer.render_frame();
+        let frame_time = start.elapsed();
+        let frame = rendered.and_then(|_| renderer.read_framebuffer());
+        renderer.stop();
+
+        frame.map(|frame| (frame, frame_time))
+    }
+}
+
+/// A frame compared against the reference frame.
+#[derive(Debug, Clone)]
+pub struct ComparisonEntry {
+    /// Name of the renderer that produced the frame
+    pub renderer: String,
+
+    /// Precision the frame was rendered at
+    pub precision: DataPrecision,
+
+    /// Time spent rendering the frame
+    pub frame_time: Duration,
+
+    /// Differences from the reference frame
+    pub diff: ImageDiff,
+}
+
+/// Frames of one world compared against a reference rendering.
+#[derive(Debug, Clone)]
+pub struct PrecisionComparison {
+    /// Name of the renderer that produced the reference frame
+    pub reference_renderer: String,
+
+    /// Precision of the reference frame
+    pub reference_precision: DataPrecision,
+
+    /// The reference frame
+    pub reference: Framebuffer,
+
+    /// Compared frames, in the order they were requested
+    pub entries: Vec<ComparisonEntry>,
+}
+
+impl PrecisionComparison {
+    /// Render `world` with `reference` and every candidate, and compare each
+    /// candidate frame against the reference frame.
+    pub fn run(world: &World, reference: RenderSetup<'_>, candidates: &[RenderSetup<'_>]) -> Result<Self, String> {
+        let (reference_frame, _) = reference.render(world)?;
+
+        let mut entries = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            let renderer = candidate.renderer_name();
+            let (frame, frame_time) = candidate
+                .render(world)
+                .map_err(|error| format!("{} at {}: {}", renderer, candidate.precision, error))?;
+            let diff = ImageDiff::compare(&reference_frame, &frame)
+                .map_err(|error| format!("{} at {}: {}", renderer, candidate.precision, error))?;
+            entries.push(ComparisonEntry { renderer, precision: candidate.precision, frame_time, diff });
+        }
+
+        Ok(Self {
+            reference_renderer: reference.renderer_name(),
+            reference_precision: reference.precision,
+            reference: reference_frame,
+            entries,
+        })
+    }
+
+    /// Render `world` with one factory at each of `precisions`, using its
+    /// `DataPrecision::F64` frame as the reference.
+    pub fn across_precisions(
+        world: &World,
+        factory: &dyn RendererFactory,
+        parameters: &str,
+        precisions: &[DataPrecision],
+    ) -> Result<Self, String> {
+        let candidates: Vec<RenderSetup<'_>> = precisions
+            .iter()
+            .map(|&precision| RenderSetup::new(factory, precision, parameters))
+            .collect();
+        Self::run(world, RenderSetup::new(factory, DataPrecision::F64, parameters), &candidates)
+    }
+
+    /// The entry for `renderer` at `precision`, if it was compared.
+    pub fn entry(&self, renderer: &str, precision: DataPrecision) -> Option<&ComparisonEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.renderer == renderer && entry.precision == precision)
+    }
+
+    /// Record each entry's quality score with `manager`, so its precision
+    /// matrix reports measured rather than estimated quality.
+    pub fn record_quality_scores(&self, manager: &RendererManager) {
+        for entry in &self.entries {
+            manager.record_quality_score(&entry.renderer, entry.precision, entry.diff.quality_score());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::factory::ReferenceRendererFactory;
+    use crate::renderer::world::{Camera, GaussianSplat, Point3D};
+
+    fn test_world() -> World {
+        let mut world = World::new();
+        world.set_camera(Camera::new().with_aspect_ratio(1.0));
+        for i in 0..12 {
+            let t = i as f64 * 0.55;
+            world.add_splat(
+                GaussianSplat::new(0, Point3D::new(t.cos() * 0.4, t.sin() * 0.4, -(i as f64) * 0.05))
+                    .with_color(0.2 + 0.05 * i as f64, 0.7, 0.4, 1.0)
+                    .with_opacity(0.6)
+                    .with_covariance([0.013, 0.002, 0.0, 0.017, 0.0, 0.011]),
+            );
+        }
+        world
+    }
+
+    #[test]
+    fn test_compare_precisions() {
+        let world = test_world();
+        let factory = ReferenceRendererFactory::new();
+        let precisions = [DataPrecision::F64, DataPrecision::F32, DataPrecision::F16, DataPrecision::BFloat16];
+        let comparison = PrecisionComparison::across_precisions(&world, &factory, "viewport_size=24x24", &precisions).unwrap();
+
+        assert_eq!(comparison.reference_precision, DataPrecision::F64);
+        assert_eq!((comparison.reference.width(), comparison.reference.height()), (24, 24));
+        assert_eq!(comparison.entries.len(), 4);
+
+        let name = comparison.reference_renderer.clone();
+        let quality = |precision| comparison.entry(&name, precision).unwrap().diff.quality_score();
+        assert!(comparison.entry(&name, DataPrecision::F64).unwrap().diff.is_identical());
+        assert!(quality(DataPrecision::F32) > 0.999);
+        // Fewer mantissa bits cost more quality
+        assert!(quality(DataPrecision::F16) >= quality(DataPrecision::BFloat16));
+        assert!(quality(DataPrecision::BFloat16) > 0.5);
+
+        let manager = RendererManager::new();
+        comparison.record_quality_scores(&manager);
+        assert_eq!(manager.measured_quality_score(&name, DataPrecision::F16), Some(quality(DataPrecision::F16)));
+    }
+
+    #[test]
+    fn test_mismatched_frame_sizes_are_rejected() {
+        let world = test_world();
+        let factory = ReferenceRendererFactory::new();
+        let reference = RenderSetup::new(&factory, DataPrecision::F64, "viewport_size=16x16");
+        let candidate = RenderSetup::new(&factory, DataPrecision::F32, "viewport_size=16x8");
+
+        let error = PrecisionComparison::run(&world, reference, &[candidate]).unwrap_err();
+        assert!(error.contains("does not match"));
+    }
+}